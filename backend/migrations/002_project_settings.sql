@@ -0,0 +1,10 @@
+-- Per-project settings
+CREATE TABLE IF NOT EXISTS project_settings (
+    project_id TEXT PRIMARY KEY,
+    drift_metric TEXT NOT NULL DEFAULT 'L1',
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (project_id) REFERENCES projects(project_id)
+);
+
+-- Metric used to score FEATURE_DRIFT alerts
+ALTER TABLE alerts ADD COLUMN drift_metric TEXT;
@@ -0,0 +1,2 @@
+-- FEATURE_DRIFT alerts require the drift test p-value to be below this level
+ALTER TABLE project_settings ADD COLUMN significance_level REAL NOT NULL DEFAULT 0.05;
@@ -0,0 +1,2 @@
+-- DATA_QUALITY alerts fire when the incoming null rate moves further than this from the baseline
+ALTER TABLE project_settings ADD COLUMN null_rate_tolerance REAL NOT NULL DEFAULT 0.05;
@@ -0,0 +1,3 @@
+-- Confidence distribution and calibration captured with each baseline
+ALTER TABLE baselines ADD COLUMN confidence_stats TEXT;
+ALTER TABLE baselines ADD COLUMN calibration TEXT;
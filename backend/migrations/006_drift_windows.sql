@@ -0,0 +1,16 @@
+-- Per-window drift from incoming uploads evaluated with a time window
+CREATE TABLE IF NOT EXISTS drift_windows (
+    window_id TEXT PRIMARY KEY,
+    project_id TEXT NOT NULL,
+    baseline_version INTEGER NOT NULL,
+    window TEXT NOT NULL,
+    window_start TEXT NOT NULL,
+    row_count INTEGER NOT NULL,
+    prediction_rate REAL NOT NULL,
+    prediction_shift REAL NOT NULL,
+    feature_scores TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (project_id) REFERENCES projects(project_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_drift_windows_project_start ON drift_windows(project_id, window_start);
@@ -0,0 +1,31 @@
+-- One record per incoming upload, with the drift results it produced
+CREATE TABLE IF NOT EXISTS evaluations (
+    evaluation_id TEXT PRIMARY KEY,
+    project_id TEXT NOT NULL,
+    baseline_version INTEGER NOT NULL,
+    created_at TEXT NOT NULL,
+    row_count INTEGER NOT NULL,
+    prediction_rate REAL NOT NULL,
+    prediction_shift REAL NOT NULL,
+    drift_metric TEXT NOT NULL,
+    health TEXT NOT NULL,
+    alerts_created INTEGER NOT NULL,
+    content_hash TEXT NOT NULL,
+    FOREIGN KEY (project_id) REFERENCES projects(project_id)
+);
+
+CREATE TABLE IF NOT EXISTS evaluation_features (
+    evaluation_id TEXT NOT NULL,
+    feature_name TEXT NOT NULL,
+    drift_score REAL NOT NULL,
+    sample_size INTEGER NOT NULL,
+    p_value REAL,
+    null_rate REAL,
+    unexpected_fraction REAL,
+    PRIMARY KEY (evaluation_id, feature_name),
+    FOREIGN KEY (evaluation_id) REFERENCES evaluations(evaluation_id)
+);
+
+ALTER TABLE drift_windows ADD COLUMN evaluation_id TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_evaluations_project_created ON evaluations(project_id, created_at);
@@ -0,0 +1,16 @@
+-- Project-level alert thresholds; NULL uses the built-in default
+ALTER TABLE project_settings ADD COLUMN feature_drift_warn_threshold REAL;
+ALTER TABLE project_settings ADD COLUMN feature_drift_critical_threshold REAL;
+ALTER TABLE project_settings ADD COLUMN prediction_shift_warn_threshold REAL;
+ALTER TABLE project_settings ADD COLUMN prediction_shift_critical_threshold REAL;
+
+-- Per-feature drift threshold overrides
+CREATE TABLE IF NOT EXISTS feature_settings (
+    project_id TEXT NOT NULL,
+    feature_name TEXT NOT NULL,
+    drift_warn_threshold REAL,
+    drift_critical_threshold REAL,
+    updated_at TEXT NOT NULL,
+    PRIMARY KEY (project_id, feature_name),
+    FOREIGN KEY (project_id) REFERENCES projects(project_id)
+);
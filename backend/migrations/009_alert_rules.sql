@@ -0,0 +1,2 @@
+-- Custom alert rules as a JSON array; NULL uses the default rules
+ALTER TABLE project_settings ADD COLUMN alert_rules TEXT;
@@ -0,0 +1,11 @@
+-- Repeated alerts update the open alert instead of inserting a new row
+ALTER TABLE alerts ADD COLUMN rule_name TEXT;
+ALTER TABLE alerts ADD COLUMN occurrence_count INTEGER NOT NULL DEFAULT 1;
+ALTER TABLE alerts ADD COLUMN last_seen_at TEXT;
+ALTER TABLE alerts ADD COLUMN acked_at TEXT;
+UPDATE alerts SET last_seen_at = created_at;
+
+-- Minutes after an ACK before the same alert is raised again
+ALTER TABLE project_settings ADD COLUMN alert_cooldown_minutes INTEGER NOT NULL DEFAULT 0;
+
+CREATE INDEX IF NOT EXISTS idx_alerts_dedup ON alerts(project_id, alert_type, feature_name, status);
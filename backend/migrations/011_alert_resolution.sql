@@ -0,0 +1,7 @@
+-- Alerts move to RESOLVED once the drift behind them recovers
+ALTER TABLE alerts ADD COLUMN resolved_at TEXT;
+ALTER TABLE alerts ADD COLUMN resolved_by TEXT;
+ALTER TABLE alerts ADD COLUMN resolution_note TEXT;
+
+-- Fraction below the WARN threshold a metric must fall to before its alert resolves
+ALTER TABLE project_settings ADD COLUMN alert_resolve_hysteresis REAL NOT NULL DEFAULT 0.1;
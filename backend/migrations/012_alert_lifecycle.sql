@@ -0,0 +1,20 @@
+-- Snoozing and assignment of alerts
+ALTER TABLE alerts ADD COLUMN snoozed_until TEXT;
+ALTER TABLE alerts ADD COLUMN assignee TEXT;
+
+-- Append-only audit trail: status transitions, assignments and comments
+CREATE TABLE IF NOT EXISTS alert_events (
+    event_id TEXT PRIMARY KEY,
+    alert_id TEXT NOT NULL,
+    project_id TEXT NOT NULL,
+    event_type TEXT NOT NULL,
+    actor TEXT NOT NULL,
+    from_status TEXT,
+    to_status TEXT,
+    details TEXT,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (alert_id) REFERENCES alerts(alert_id),
+    FOREIGN KEY (project_id) REFERENCES projects(project_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_alert_events_alert ON alert_events(alert_id, created_at);
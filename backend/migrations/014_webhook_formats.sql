@@ -0,0 +1,2 @@
+-- Webhooks can post Slack or Teams messages instead of the raw alert payload
+ALTER TABLE webhooks ADD COLUMN format TEXT NOT NULL DEFAULT 'JSON';
@@ -0,0 +1,11 @@
+-- Per-project email notification settings
+CREATE TABLE IF NOT EXISTS notification_settings (
+    project_id TEXT PRIMARY KEY,
+    email_recipients TEXT NOT NULL DEFAULT '[]', -- JSON array of addresses
+    email_immediate BOOLEAN NOT NULL DEFAULT 1,
+    email_digest BOOLEAN NOT NULL DEFAULT 1,
+    digest_hour INTEGER NOT NULL DEFAULT 8,
+    last_digest_at TEXT,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (project_id) REFERENCES projects(project_id)
+);
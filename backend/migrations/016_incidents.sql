@@ -0,0 +1,16 @@
+-- Incidents group the alerts raised together, e.g. by one upload. Their severity
+-- and status are derived from their alerts.
+CREATE TABLE IF NOT EXISTS incidents (
+    incident_id TEXT PRIMARY KEY,
+    project_id TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (project_id) REFERENCES projects(project_id)
+);
+
+ALTER TABLE alerts ADD COLUMN incident_id TEXT REFERENCES incidents(incident_id);
+
+-- Minutes after an incident opens during which new alerts join it instead of opening another
+ALTER TABLE project_settings ADD COLUMN incident_window_minutes INTEGER NOT NULL DEFAULT 0;
+
+CREATE INDEX IF NOT EXISTS idx_alerts_incident_id ON alerts(incident_id);
+CREATE INDEX IF NOT EXISTS idx_incidents_project_id ON incidents(project_id, created_at);
@@ -6,11 +6,16 @@ mod utils;
 
 use actix_cors::Cors;
 use actix_web::{web, App, HttpServer};
+use services::email::{self, Mailer};
+use services::live_events::LiveEvents;
 use sqlx::sqlite::SqlitePoolOptions;
 use std::env;
 
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
+    // Log at info and above unless RUST_LOG says otherwise
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
+
     // Initialize database
     let database_url =
         env::var("DATABASE_URL").unwrap_or_else(|_| "sqlite:drift_guard.db".to_string());
@@ -27,7 +32,17 @@ async fn main() -> std::io::Result<()> {
         .await
         .expect("Failed to run migrations");
 
-    println!("Starting DriftGuard backend on http://127.0.0.1:8080");
+    // Email notifications are only sent when SMTP is configured
+    let mailer = Mailer::from_env().expect("Invalid SMTP configuration");
+    if let Some(mailer) = mailer.clone() {
+        actix_web::rt::spawn(email::run_digests(pool.clone(), mailer));
+    } else {
+        log::info!("SMTP_HOST is not set; email notifications are disabled");
+    }
+    let mailer = web::Data::new(mailer);
+    let live_events = web::Data::new(LiveEvents::new());
+
+    log::info!("Starting DriftGuard backend on http://127.0.0.1:8080");
 
     HttpServer::new(move || {
         let cors = Cors::default()
@@ -38,6 +53,8 @@ async fn main() -> std::io::Result<()> {
 
         App::new()
             .app_data(web::Data::new(pool.clone()))
+            .app_data(mailer.clone())
+            .app_data(live_events.clone())
             .wrap(cors)
             .route(
                 "/api/projects",
@@ -51,6 +68,42 @@ async fn main() -> std::io::Result<()> {
                 "/api/projects/{project_id}",
                 web::get().to(routes::projects::get_project),
             )
+            .route(
+                "/api/projects/{project_id}/settings",
+                web::get().to(routes::settings::get_settings),
+            )
+            .route(
+                "/api/projects/{project_id}/settings",
+                web::put().to(routes::settings::update_settings),
+            )
+            .route(
+                "/api/projects/{project_id}/settings/features",
+                web::get().to(routes::settings::list_feature_settings),
+            )
+            .route(
+                "/api/projects/{project_id}/settings/features/{feature_name}",
+                web::put().to(routes::settings::update_feature_settings),
+            )
+            .route(
+                "/api/projects/{project_id}/settings/features/{feature_name}",
+                web::delete().to(routes::settings::delete_feature_settings),
+            )
+            .route(
+                "/api/projects/{project_id}/rules",
+                web::get().to(routes::rules::get_rules),
+            )
+            .route(
+                "/api/projects/{project_id}/rules",
+                web::put().to(routes::rules::update_rules),
+            )
+            .route(
+                "/api/projects/{project_id}/rules",
+                web::delete().to(routes::rules::reset_rules),
+            )
+            .route(
+                "/api/projects/{project_id}/rules/validate",
+                web::post().to(routes::rules::validate_rules),
+            )
             .route(
                 "/api/projects/{project_id}/baseline/upload",
                 web::post().to(routes::baseline::upload_baseline),
@@ -63,14 +116,110 @@ async fn main() -> std::io::Result<()> {
                 "/api/projects/{project_id}/incoming/upload",
                 web::post().to(routes::incoming::upload_incoming),
             )
+            .route(
+                "/api/projects/{project_id}/evaluations",
+                web::get().to(routes::evaluations::list_evaluations),
+            )
+            .route(
+                "/api/projects/{project_id}/evaluations/{evaluation_id}",
+                web::get().to(routes::evaluations::get_evaluation),
+            )
+            .route(
+                "/api/projects/{project_id}/features/{feature_name}/history",
+                web::get().to(routes::history::get_feature_history),
+            )
+            .route(
+                "/api/projects/{project_id}/windows",
+                web::get().to(routes::windows::list_windows),
+            )
             .route(
                 "/api/projects/{project_id}/alerts",
                 web::get().to(routes::alerts::list_alerts),
             )
+            .route(
+                "/api/projects/{project_id}/alerts/summary",
+                web::get().to(routes::alerts::alert_summary),
+            )
+            .route(
+                "/api/projects/{project_id}/alerts/stream",
+                web::get().to(routes::alerts::stream_alerts),
+            )
+            .route(
+                "/api/projects/{project_id}/alerts/bulk",
+                web::post().to(routes::alerts::bulk_update_alerts),
+            )
             .route(
                 "/api/projects/{project_id}/alerts/{alert_id}/ack",
                 web::post().to(routes::alerts::ack_alert),
             )
+            .route(
+                "/api/projects/{project_id}/alerts/{alert_id}/resolve",
+                web::post().to(routes::alerts::resolve_alert),
+            )
+            .route(
+                "/api/projects/{project_id}/alerts/{alert_id}/snooze",
+                web::post().to(routes::alerts::snooze_alert),
+            )
+            .route(
+                "/api/projects/{project_id}/alerts/{alert_id}/assign",
+                web::post().to(routes::alerts::assign_alert),
+            )
+            .route(
+                "/api/projects/{project_id}/alerts/{alert_id}/comments",
+                web::post().to(routes::alerts::add_comment),
+            )
+            .route(
+                "/api/projects/{project_id}/alerts/{alert_id}/events",
+                web::get().to(routes::alerts::list_alert_events),
+            )
+            .route(
+                "/api/projects/{project_id}/incidents",
+                web::get().to(routes::incidents::list_incidents),
+            )
+            .route(
+                "/api/projects/{project_id}/incidents/{incident_id}",
+                web::get().to(routes::incidents::get_incident),
+            )
+            .route(
+                "/api/projects/{project_id}/incidents/{incident_id}/ack",
+                web::post().to(routes::incidents::ack_incident),
+            )
+            .route(
+                "/api/projects/{project_id}/incidents/{incident_id}/resolve",
+                web::post().to(routes::incidents::resolve_incident),
+            )
+            .route(
+                "/api/projects/{project_id}/webhooks",
+                web::get().to(routes::webhooks::list_webhooks),
+            )
+            .route(
+                "/api/projects/{project_id}/webhooks",
+                web::post().to(routes::webhooks::create_webhook),
+            )
+            .route(
+                "/api/projects/{project_id}/webhooks/{webhook_id}",
+                web::delete().to(routes::webhooks::delete_webhook),
+            )
+            .route(
+                "/api/projects/{project_id}/webhooks/{webhook_id}/test",
+                web::post().to(routes::webhooks::test_webhook),
+            )
+            .route(
+                "/api/projects/{project_id}/webhooks/{webhook_id}/deliveries",
+                web::get().to(routes::webhooks::list_deliveries),
+            )
+            .route(
+                "/api/projects/{project_id}/notifications",
+                web::get().to(routes::notifications::get_notification_settings),
+            )
+            .route(
+                "/api/projects/{project_id}/notifications",
+                web::put().to(routes::notifications::update_notification_settings),
+            )
+            .route(
+                "/api/projects/{project_id}/notifications/digest",
+                web::post().to(routes::notifications::send_digest),
+            )
     })
     .bind("127.0.0.1:8080")?
     .run()
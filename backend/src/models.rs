@@ -1,6 +1,7 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
+use std::collections::BTreeMap;
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct Project {
@@ -21,6 +22,8 @@ pub struct Baseline {
     pub baseline_version: i64,
     pub prediction_rate: f64,
     pub created_at: DateTime<Utc>,
+    pub confidence_stats: Option<String>, // JSON NumericStats of the confidence column
+    pub calibration: Option<String>,      // JSON CalibrationStats, when labels were present
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
@@ -38,6 +41,8 @@ pub struct BaselineMetadata {
     pub prediction_rate: f64,
     pub created_at: DateTime<Utc>,
     pub features: Vec<FeatureMetadata>,
+    pub confidence: Option<serde_json::Value>,
+    pub calibration: Option<serde_json::Value>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -58,17 +63,272 @@ pub struct Alert {
     pub feature_name: Option<String>,
     pub metric_value: Option<f64>,
     pub message: String,
-    pub status: String, // "OPEN" or "ACK"
+    pub status: String, // "OPEN", "ACK", "SNOOZED" or "RESOLVED"
+    pub drift_metric: Option<String>,
+    pub rule_name: Option<String>, // rule that raised the alert, if any
+    pub occurrence_count: i64,     // evaluations that raised this alert
+    pub last_seen_at: DateTime<Utc>,
+    pub acked_at: Option<DateTime<Utc>>,
+    pub resolved_at: Option<DateTime<Utc>>,
+    pub resolved_by: Option<String>, // "auto" when an evaluation showed recovery, else who resolved it
+    pub resolution_note: Option<String>,
+    pub snoozed_until: Option<DateTime<Utc>>,
+    pub assignee: Option<String>,
+    pub incident_id: Option<String>, // incident the alert was grouped into when it was raised
+}
+
+/// Orders severities so that escalations can be detected: CRITICAL > WARN > anything else.
+pub fn severity_rank(severity: &str) -> u8 {
+    match severity {
+        "CRITICAL" => 2,
+        "WARN" => 1,
+        _ => 0,
+    }
+}
+
+/// One entry of an alert's audit trail.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlertEvent {
+    pub event_id: String,
+    pub alert_id: String,
+    pub project_id: String,
+    pub event_type: String, // "CREATED", "ACK", "SNOOZED", "REOPENED", "RESOLVED", "ASSIGNED" or "COMMENT"
+    pub actor: String,      // "auto" for changes made by evaluations
+    pub from_status: Option<String>,
+    pub to_status: Option<String>,
+    pub details: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AckAlertRequest {
+    pub actor: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ResolveAlertRequest {
+    #[serde(alias = "actor")]
+    pub resolved_by: Option<String>,
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnoozeAlertRequest {
+    pub until: DateTime<Utc>,
+    pub actor: Option<String>,
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssignAlertRequest {
+    pub assignee: Option<String>, // null unassigns the alert
+    pub actor: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlertCommentRequest {
+    pub comment: String,
+    pub actor: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct AlertFilter {
     pub status: Option<String>,
+    pub assignee: Option<String>,
     pub severity: Option<String>,
     pub feature_name: Option<String>,
     pub alert_type: Option<String>,
     pub start_time: Option<String>, // ISO 8601 format
     pub end_time: Option<String>,   // ISO 8601 format
+    pub incident_id: Option<String>,
+}
+
+pub const DEFAULT_ALERT_PAGE_SIZE: i64 = 50;
+pub const MAX_ALERT_PAGE_SIZE: i64 = 500;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertSort {
+    #[default]
+    CreatedAt,
+    Severity,
+    MetricValue,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    Asc,
+    #[default]
+    Desc,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AlertPageParams {
+    #[serde(default)]
+    pub sort: AlertSort,
+    #[serde(default)]
+    pub order: SortOrder,
+    pub limit: Option<i64>,     // DEFAULT_ALERT_PAGE_SIZE when omitted
+    pub cursor: Option<String>, // `next_cursor` of the previous page
+}
+
+/// Where a page of alerts ends: the sort key and id of its last alert. Sent to clients
+/// as an opaque hex string.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AlertCursor {
+    pub sort: AlertSort,
+    pub order: SortOrder,
+    pub value: serde_json::Value,
+    pub alert_id: String,
+}
+
+impl AlertCursor {
+    pub fn encode(&self) -> String {
+        let json = serde_json::to_string(self).expect("cursors serialize");
+        json.bytes().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    /// Reads a cursor for the given sort; None if it is malformed or was issued for
+    /// another sort or order.
+    pub fn decode(cursor: &str, sort: AlertSort, order: SortOrder) -> Option<AlertCursor> {
+        let bytes = (0..cursor.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(cursor.get(i..i + 2)?, 16).ok())
+            .collect::<Option<Vec<u8>>>()?;
+        let cursor: AlertCursor = serde_json::from_slice(&bytes).ok()?;
+        let value_fits = match sort {
+            AlertSort::CreatedAt => cursor.value.is_string(),
+            AlertSort::Severity => cursor.value.is_i64(),
+            AlertSort::MetricValue => cursor.value.is_number(),
+        };
+        (cursor.sort == sort && cursor.order == order && value_fits).then_some(cursor)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlertPage {
+    pub alerts: Vec<Alert>,
+    pub total: i64,                  // alerts matching the filter, across all pages
+    pub next_cursor: Option<String>, // None on the last page
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BulkAlertAction {
+    Ack,
+    Resolve,
+}
+
+/// A status change someone asks for, on one alert or in bulk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlertAction {
+    Ack,
+    Resolve,
+    Snooze,
+}
+
+impl AlertAction {
+    /// Statuses an alert must have for this action to change it. Snoozing a snoozed
+    /// alert moves its end.
+    pub fn allowed_statuses(self) -> &'static [&'static str] {
+        match self {
+            AlertAction::Ack => &["OPEN", "SNOOZED"],
+            AlertAction::Resolve => &["OPEN", "ACK", "SNOOZED"],
+            AlertAction::Snooze => &["OPEN", "ACK", "SNOOZED"],
+        }
+    }
+
+    pub fn allows(self, status: &str) -> bool {
+        self.allowed_statuses().contains(&status)
+    }
+}
+
+impl From<BulkAlertAction> for AlertAction {
+    fn from(action: BulkAlertAction) -> Self {
+        match action {
+            BulkAlertAction::Ack => AlertAction::Ack,
+            BulkAlertAction::Resolve => AlertAction::Resolve,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BulkAlertRequest {
+    pub action: BulkAlertAction,
+    #[serde(default)]
+    pub filter: AlertFilter,
+    pub actor: Option<String>,
+    pub note: Option<String>, // recorded on resolved alerts
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BulkAlertResponse {
+    pub action: BulkAlertAction,
+    pub affected: u64,
+}
+
+/// Bounds on alert creation time for a summary. The list endpoint's other filters
+/// don't apply: open counts are always over OPEN alerts.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AlertSummaryParams {
+    pub start_time: Option<String>, // ISO 8601 format
+    pub end_time: Option<String>,   // ISO 8601 format
+}
+
+/// Aggregates over the alerts created within a time range.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlertSummary {
+    pub open_by_severity: BTreeMap<String, i64>,
+    pub open_by_type: BTreeMap<String, i64>,
+    pub top_features: Vec<FeatureAlertCount>,
+    pub mean_time_to_ack_seconds: Option<f64>, // None when no alert was acknowledged
+    pub acked_alerts: i64,
+    pub alerts_per_day: Vec<DailyAlertCount>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeatureAlertCount {
+    pub feature_name: String,
+    pub alert_count: i64,
+    pub occurrences: i64, // including repeats folded into the alerts
+    pub last_seen_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DailyAlertCount {
+    pub date: NaiveDate, // UTC
+    pub alerts: i64,
+    pub critical: i64,
+    pub warn: i64,
+}
+
+/// Alerts raised together, e.g. by one upload. Severity and status follow its alerts:
+/// the most severe alert sets the severity, and the incident is OPEN while any alert
+/// is OPEN, RESOLVED once all are, and ACK otherwise.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Incident {
+    pub incident_id: String,
+    pub project_id: String,
+    pub severity: String,
+    pub status: String, // "OPEN", "ACK" or "RESOLVED"
+    pub alert_count: i64,
+    pub open_alerts: i64,
+    pub alert_types: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    pub last_seen_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IncidentDetail {
+    #[serde(flatten)]
+    pub incident: Incident,
+    pub alerts: Vec<Alert>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct IncidentFilter {
+    pub status: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -80,13 +340,713 @@ pub struct CreateAlertParams {
     pub feature_name: Option<String>,
     pub metric_value: Option<f64>,
     pub message: String,
+    pub drift_metric: Option<String>,
+    pub rule_name: Option<String>,
+}
+
+/// How a raised alert was stored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AlertOutcome {
+    Created(String),    // new OPEN alert
+    Escalated(String),  // folded into the matching OPEN alert, which became more severe
+    Updated(String), // folded into the matching OPEN alert, or a snoozed one whose snooze is over
+    Suppressed(String), // matching alert is snoozed or was acknowledged within the cooldown
+}
+
+impl AlertOutcome {
+    pub fn is_created(&self) -> bool {
+        matches!(self, AlertOutcome::Created(_))
+    }
+
+    pub fn alert_id(&self) -> &str {
+        match self {
+            AlertOutcome::Created(alert_id)
+            | AlertOutcome::Escalated(alert_id)
+            | AlertOutcome::Updated(alert_id)
+            | AlertOutcome::Suppressed(alert_id) => alert_id,
+        }
+    }
+
+    /// The notification this outcome calls for, if any.
+    pub fn notification(&self) -> Option<AlertNotification> {
+        match self {
+            AlertOutcome::Created(alert_id) => Some(AlertNotification {
+                alert_id: alert_id.clone(),
+                event: NotificationEvent::AlertCreated,
+            }),
+            AlertOutcome::Escalated(alert_id) => Some(AlertNotification {
+                alert_id: alert_id.clone(),
+                event: NotificationEvent::AlertEscalated,
+            }),
+            AlertOutcome::Updated(_) | AlertOutcome::Suppressed(_) => None,
+        }
+    }
+}
+
+/// What came of asking an alert to change status.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AlertTransition {
+    Applied,
+    NotFound,
+    NotAllowed(String), // the alert's current status, which can't make the transition
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NotificationEvent {
+    #[serde(rename = "alert.created")]
+    AlertCreated,
+    #[serde(rename = "alert.escalated")]
+    AlertEscalated,
+    #[serde(rename = "alerts.summary")]
+    AlertsSummary,
+    #[serde(rename = "test")]
+    Test,
+}
+
+impl NotificationEvent {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NotificationEvent::AlertCreated => "alert.created",
+            NotificationEvent::AlertEscalated => "alert.escalated",
+            NotificationEvent::AlertsSummary => "alerts.summary",
+            NotificationEvent::Test => "test",
+        }
+    }
+}
+
+impl std::str::FromStr for NotificationEvent {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "alert.created" => Ok(NotificationEvent::AlertCreated),
+            "alert.escalated" => Ok(NotificationEvent::AlertEscalated),
+            "alerts.summary" => Ok(NotificationEvent::AlertsSummary),
+            "test" => Ok(NotificationEvent::Test),
+            _ => Err(anyhow::anyhow!("Unknown notification event: {}", s)),
+        }
+    }
+}
+
+/// An alert that the notification channels should hear about.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AlertNotification {
+    pub alert_id: String,
+    pub event: NotificationEvent,
+}
+
+/// How a webhook's requests are shaped: the raw alert payload, one request per
+/// alert, or a Slack or Teams message summarising an upload's alerts.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum WebhookFormat {
+    #[default]
+    Json,
+    Slack,
+    Teams,
+}
+
+impl WebhookFormat {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WebhookFormat::Json => "JSON",
+            WebhookFormat::Slack => "SLACK",
+            WebhookFormat::Teams => "TEAMS",
+        }
+    }
+}
+
+impl std::str::FromStr for WebhookFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "JSON" => Ok(WebhookFormat::Json),
+            "SLACK" => Ok(WebhookFormat::Slack),
+            "TEAMS" => Ok(WebhookFormat::Teams),
+            _ => Err(anyhow::anyhow!("Unknown webhook format: {}", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Webhook {
+    pub webhook_id: String,
+    pub project_id: String,
+    pub url: String,
+    #[serde(skip_serializing)]
+    pub secret: String, // HMAC-SHA256 key, only returned when the webhook is created
+    pub min_severity: String, // "WARN" or "CRITICAL"
+    pub format: WebhookFormat,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateWebhookRequest {
+    pub url: String,
+    pub secret: Option<String>, // generated when absent
+    pub min_severity: Option<String>,
+    #[serde(default)]
+    pub format: WebhookFormat,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateWebhookResponse {
+    #[serde(flatten)]
+    pub webhook: Webhook,
+    pub secret: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookDelivery {
+    pub delivery_id: String,
+    pub webhook_id: String,
+    pub project_id: String,
+    pub alert_id: Option<String>, // None for test deliveries and upload summaries
+    pub event: NotificationEvent,
+    pub payload: serde_json::Value,
+    pub status: String, // "SUCCESS" or "FAILED"
+    pub attempts: i64,
+    pub response_status: Option<i64>, // HTTP status of the last attempt, if one was received
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Who is emailed about a project's alerts, and when.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotificationSettings {
+    pub project_id: String,
+    pub email_recipients: Vec<String>,
+    pub email_immediate: bool, // mail CRITICAL alerts as soon as they are created or escalated
+    pub email_digest: bool,    // mail a daily summary of open alerts
+    pub digest_hour: i64,      // UTC hour after which the daily digest is sent
+    pub last_digest_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>, // None while the project uses defaults
+}
+
+impl NotificationSettings {
+    pub fn defaults(project_id: &str) -> Self {
+        NotificationSettings {
+            project_id: project_id.to_string(),
+            email_recipients: Vec::new(),
+            email_immediate: true,
+            email_digest: true,
+            digest_hour: 8,
+            last_digest_at: None,
+            updated_at: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateNotificationSettingsRequest {
+    pub email_recipients: Option<Vec<String>>,
+    pub email_immediate: Option<bool>,
+    pub email_digest: Option<bool>,
+    pub digest_hour: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DigestResponse {
+    pub sent: bool, // false when there were no open alerts to report
+    pub recipients: usize,
+    pub open_alerts: usize,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UploadIncomingResponse {
+    pub evaluation_id: String,
     pub rows_processed: usize,
     pub alerts_created: usize,
-    pub health: String, // "OK", "WARN", "CRITICAL"
+    pub alerts_resolved: usize,
+    pub incident_id: Option<String>, // incident of the alerts created, if any were
+    pub health: String,              // "OK", "WARN", "CRITICAL"
+    pub drift_metric: DriftMetric,
+    pub window: Option<TimeWindow>,
+    pub windows: Vec<WindowDrift>, // empty unless a window was requested
+    pub skipped_timestamps: usize, // rows left out of the windows for a missing or bad timestamp
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimeWindow {
+    Hourly,
+    Daily,
+}
+
+impl TimeWindow {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TimeWindow::Hourly => "hourly",
+            TimeWindow::Daily => "daily",
+        }
+    }
+}
+
+impl std::str::FromStr for TimeWindow {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "hourly" => Ok(TimeWindow::Hourly),
+            "daily" => Ok(TimeWindow::Daily),
+            other => Err(anyhow::anyhow!("Unknown time window: {}", other)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct IncomingUploadParams {
+    pub window: Option<TimeWindow>,
+}
+
+/// Drift of the incoming rows whose timestamp falls in one window.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WindowDrift {
+    pub window_start: DateTime<Utc>,
+    pub row_count: usize,
+    pub prediction_rate: f64,
+    pub prediction_shift: f64,
+    pub feature_scores: std::collections::HashMap<String, f64>,
+}
+
+/// A persisted window from an earlier incoming upload.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DriftWindow {
+    pub window_id: String,
+    pub project_id: String,
+    pub evaluation_id: Option<String>, // None for windows stored before evaluations were recorded
+    pub baseline_version: i64,
+    pub window: TimeWindow,
+    pub window_start: DateTime<Utc>,
+    pub row_count: usize,
+    pub prediction_rate: f64,
+    pub prediction_shift: f64,
+    pub feature_scores: std::collections::HashMap<String, f64>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct DriftWindowFilter {
+    pub window: Option<TimeWindow>,
+}
+
+/// A stored incoming upload and the drift results it produced.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Evaluation {
+    pub evaluation_id: String,
+    pub project_id: String,
+    pub baseline_version: i64,
+    pub created_at: DateTime<Utc>,
+    pub row_count: usize,
+    pub prediction_rate: f64,
+    pub prediction_shift: f64,
+    pub drift_metric: DriftMetric,
+    pub health: String,
+    pub alerts_created: usize,
+    pub content_hash: String, // SHA-256 of the uploaded file
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EvaluationFeature {
+    pub feature_name: String,
+    pub drift_score: f64,
+    pub sample_size: usize,
+    pub p_value: Option<f64>,
+    pub null_rate: Option<f64>,
+    pub unexpected_fraction: Option<f64>,
+}
+
+impl EvaluationFeature {
+    pub fn from_drift(feature_name: &str, drift: &FeatureDrift) -> Self {
+        EvaluationFeature {
+            feature_name: feature_name.to_string(),
+            drift_score: drift.score,
+            sample_size: drift.sample_size,
+            p_value: drift.significance.as_ref().map(|test| test.p_value),
+            null_rate: drift.null_rate,
+            unexpected_fraction: drift.unexpected_values.as_ref().map(|u| u.fraction),
+        }
+    }
+}
+
+/// Drift of one feature in one evaluation, or the average over several
+/// consecutive evaluations once a history is downsampled.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeatureHistoryPoint {
+    pub evaluation_id: Option<String>, // None when the point covers several evaluations
+    pub created_at: DateTime<Utc>,     // latest evaluation in the point
+    pub drift_score: f64,
+    pub max_drift_score: f64,
+    pub drift_metric: DriftMetric,
+    pub sample_size: usize,
+    pub baseline_version: i64,
+    pub evaluations: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeatureHistory {
+    pub feature_name: String,
+    pub total_evaluations: usize,
+    pub points: Vec<FeatureHistoryPoint>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct FeatureHistoryParams {
+    pub start_time: Option<DateTime<Utc>>,
+    pub end_time: Option<DateTime<Utc>>,
+    pub max_points: Option<usize>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateEvaluationParams {
+    pub project_id: String,
+    pub baseline_version: i64,
+    pub row_count: usize,
+    pub prediction_rate: f64,
+    pub prediction_shift: f64,
+    pub drift_metric: DriftMetric,
+    pub health: String,
+    pub alerts_created: usize,
+    pub content_hash: String,
+    pub features: Vec<EvaluationFeature>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EvaluationDetail {
+    #[serde(flatten)]
+    pub evaluation: Evaluation,
+    pub features: Vec<EvaluationFeature>,
+}
+
+/// Outcome of a two-sample test between baseline and incoming values.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignificanceTest {
+    pub test: String, // "KS" or "CHI_SQUARED"
+    pub statistic: f64,
+    pub p_value: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ValueCount {
+    pub value: String,
+    pub count: usize,
+}
+
+/// Incoming values the baseline never saw: numeric values outside the baseline
+/// range, or categories missing from the baseline frequencies.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnexpectedValues {
+    pub count: usize,
+    pub fraction: f64, // share of the feature's non-empty incoming values
+    pub top_values: Vec<ValueCount>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeatureDrift {
+    pub score: f64,
+    pub sample_size: usize,
+    pub significance: Option<SignificanceTest>, // None when the baseline sample size is unknown
+    pub unexpected_values: Option<UnexpectedValues>, // None when every value was seen in the baseline
+    pub null_rate: Option<f64>, // share of incoming rows with an empty value (None if the column is absent)
+    pub type_mismatches: Option<UnexpectedValues>, // non-numeric values in a numeric feature
+}
+
+/// One bin of a reliability diagram.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReliabilityBin {
+    pub lower: f64,
+    pub upper: f64,
+    pub count: usize,
+    pub mean_confidence: f64,
+    pub observed_rate: f64, // share of rows in the bin with label 1
+}
+
+/// Calibration of `confidence` (read as P(label = 1)) against the `label` column.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CalibrationStats {
+    pub count: usize,
+    pub expected_calibration_error: f64,
+    pub mean_confidence: f64,
+    pub observed_rate: f64,
+    pub bins: Vec<ReliabilityBin>,
+}
+
+/// Distance used to score feature drift. Selected per project.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum DriftMetric {
+    /// Halved L1 (total variation) distance, bounded to 0-1.
+    #[default]
+    #[serde(rename = "L1")]
+    L1,
+    /// Population Stability Index.
+    #[serde(rename = "PSI")]
+    Psi,
+    /// Jensen-Shannon divergence (base 2), symmetric and bounded to 0-1.
+    #[serde(rename = "JS")]
+    JensenShannon,
+    /// Hellinger distance, bounded to 0-1.
+    #[serde(rename = "HELLINGER")]
+    Hellinger,
+    /// Wasserstein-1 distance in baseline standard deviations (numeric features only).
+    #[serde(rename = "WASSERSTEIN")]
+    Wasserstein,
+}
+
+impl DriftMetric {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DriftMetric::L1 => "L1",
+            DriftMetric::Psi => "PSI",
+            DriftMetric::JensenShannon => "JS",
+            DriftMetric::Hellinger => "HELLINGER",
+            DriftMetric::Wasserstein => "WASSERSTEIN",
+        }
+    }
+}
+
+impl std::str::FromStr for DriftMetric {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "L1" => Ok(DriftMetric::L1),
+            "PSI" => Ok(DriftMetric::Psi),
+            "JS" => Ok(DriftMetric::JensenShannon),
+            "HELLINGER" => Ok(DriftMetric::Hellinger),
+            "WASSERSTEIN" => Ok(DriftMetric::Wasserstein),
+            other => Err(anyhow::anyhow!("Unknown drift metric: {}", other)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectSettings {
+    pub project_id: String,
+    pub drift_metric: DriftMetric,
+    pub significance_level: f64,  // max p-value for a FEATURE_DRIFT alert
+    pub null_rate_tolerance: f64, // allowed change in null rate before a DATA_QUALITY alert
+    // Alert thresholds; None uses the built-in default (for feature drift, the metric's default)
+    pub feature_drift_warn_threshold: Option<f64>,
+    pub feature_drift_critical_threshold: Option<f64>,
+    pub prediction_shift_warn_threshold: Option<f64>,
+    pub prediction_shift_critical_threshold: Option<f64>,
+    pub alert_cooldown_minutes: i64, // minutes after an ACK before the same alert is raised again
+    pub alert_resolve_hysteresis: f64, // fraction below the WARN threshold needed to resolve an alert
+    pub incident_window_minutes: i64, // minutes after an incident opens that new alerts still join it
+    pub updated_at: Option<DateTime<Utc>>, // None while the project uses defaults
+}
+
+impl ProjectSettings {
+    pub fn defaults(project_id: &str) -> Self {
+        ProjectSettings {
+            project_id: project_id.to_string(),
+            drift_metric: DriftMetric::default(),
+            significance_level: 0.05,
+            null_rate_tolerance: 0.05,
+            feature_drift_warn_threshold: None,
+            feature_drift_critical_threshold: None,
+            prediction_shift_warn_threshold: None,
+            prediction_shift_critical_threshold: None,
+            alert_cooldown_minutes: 0,
+            alert_resolve_hysteresis: 0.1,
+            incident_window_minutes: 0,
+            updated_at: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateProjectSettingsRequest {
+    pub drift_metric: Option<DriftMetric>,
+    pub significance_level: Option<f64>,
+    pub null_rate_tolerance: Option<f64>,
+    pub alert_cooldown_minutes: Option<i64>,
+    pub alert_resolve_hysteresis: Option<f64>,
+    pub incident_window_minutes: Option<i64>,
+    // Threshold fields: absent leaves the value unchanged, null resets it to the default
+    #[serde(default, deserialize_with = "deserialize_some")]
+    pub feature_drift_warn_threshold: Option<Option<f64>>,
+    #[serde(default, deserialize_with = "deserialize_some")]
+    pub feature_drift_critical_threshold: Option<Option<f64>>,
+    #[serde(default, deserialize_with = "deserialize_some")]
+    pub prediction_shift_warn_threshold: Option<Option<f64>>,
+    #[serde(default, deserialize_with = "deserialize_some")]
+    pub prediction_shift_critical_threshold: Option<Option<f64>>,
+}
+
+/// Per-feature override of the project's drift thresholds.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeatureSettings {
+    pub project_id: String,
+    pub feature_name: String,
+    pub drift_warn_threshold: Option<f64>, // None uses the project threshold
+    pub drift_critical_threshold: Option<f64>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateFeatureSettingsRequest {
+    #[serde(default, deserialize_with = "deserialize_some")]
+    pub drift_warn_threshold: Option<Option<f64>>,
+    #[serde(default, deserialize_with = "deserialize_some")]
+    pub drift_critical_threshold: Option<Option<f64>>,
+}
+
+/// A declarative alert rule, stored as JSON per project.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlertRule {
+    pub name: String,
+    pub severity: String, // "WARN" or "CRITICAL"
+    #[serde(default = "default_rule_alert_type")]
+    pub alert_type: String,
+    /// Evaluate the rule once per feature; `feature_drift` conditions without a
+    /// `features` list then refer to that feature, and each match is its own alert.
+    #[serde(default)]
+    pub per_feature: bool,
+    pub condition: RuleCondition,
+    pub message: Option<String>, // prefix of the alert message; defaults to the rule name
+    /// Minutes after an ACK before the rule can raise the alert again; None uses the project's.
+    #[serde(default)]
+    pub cooldown_minutes: Option<i64>,
+}
+
+fn default_rule_alert_type() -> String {
+    "RULE".to_string()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RuleCondition {
+    FeatureDrift {
+        features: Option<Vec<String>>, // None: every feature (or the rule's feature when per_feature)
+        #[serde(default, rename = "match")]
+        match_mode: FeatureMatch,
+        #[serde(default)]
+        op: Comparison,
+        value: RuleThreshold,
+        /// Also require the drift test p-value to be below the project's significance level.
+        #[serde(default = "default_true")]
+        require_significance: bool,
+    },
+    PredictionShift {
+        #[serde(default)]
+        op: Comparison,
+        value: RuleThreshold,
+    },
+    RowCount {
+        #[serde(default)]
+        op: Comparison,
+        value: f64,
+    },
+    /// The condition holds for this evaluation and the `count - 1` before it.
+    Consecutive {
+        count: usize,
+        condition: Box<RuleCondition>,
+    },
+    All {
+        conditions: Vec<RuleCondition>,
+    },
+    Any {
+        conditions: Vec<RuleCondition>,
+    },
+}
+
+fn default_true() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FeatureMatch {
+    #[default]
+    Any,
+    All,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Comparison {
+    Gt,
+    #[default]
+    Gte,
+    Lt,
+    Lte,
+}
+
+impl Comparison {
+    pub fn holds(&self, value: f64, threshold: f64) -> bool {
+        match self {
+            Comparison::Gt => value > threshold,
+            Comparison::Gte => value >= threshold,
+            Comparison::Lt => value < threshold,
+            Comparison::Lte => value <= threshold,
+        }
+    }
+
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Comparison::Gt => ">",
+            Comparison::Gte => ">=",
+            Comparison::Lt => "<",
+            Comparison::Lte => "<=",
+        }
+    }
+}
+
+/// A fixed value, or the configured WARN / CRITICAL threshold.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum RuleThreshold {
+    Value(f64),
+    Named(NamedThreshold),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NamedThreshold {
+    Warn,
+    Critical,
+}
+
+/// An alert a rule set produced, before it is stored.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RuleAlert {
+    pub rule_name: String,
+    pub severity: String,
+    pub alert_type: String,
+    pub feature_name: Option<String>,
+    pub metric_value: Option<f64>,
+    pub message: String,
+    pub drift_metric: Option<DriftMetric>,
+    pub cooldown_minutes: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlertRulesResponse {
+    pub custom: bool, // false when the defaults derived from the thresholds are in use
+    pub rules: Vec<AlertRule>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ValidateRulesRequest {
+    pub rules: Option<Vec<AlertRule>>, // None dry-runs the project's current rules
+    pub evaluation_id: Option<String>, // None uses the latest evaluation
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ValidateRulesResponse {
+    pub evaluation_id: String,
+    pub alerts: Vec<RuleAlert>,
+}
+
+/// Distinguishes an explicit `null` (`Some(None)`) from an absent field (`None`).
+fn deserialize_some<'de, T, D>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    T: Deserialize<'de>,
+    D: serde::Deserializer<'de>,
+{
+    T::deserialize(deserializer).map(Some)
 }
 
 #[derive(Debug, Clone)]
@@ -108,13 +1068,106 @@ pub enum FeatureStatsData {
     Categorical(CategoricalStats),
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct NumericStats {
     pub bins: Vec<f64>,          // bin edges (11 values for 10 bins)
     pub probabilities: Vec<f64>, // probabilities for each bin
+    #[serde(default)]
+    pub count: usize, // number of values the histogram was built from (0 if unknown)
+    #[serde(default)]
+    pub binning: BinningStrategy,
+    #[serde(default)]
+    pub underflow: f64, // probability below bins[0]
+    #[serde(default)]
+    pub overflow: f64, // probability above the last edge
+    #[serde(default)]
+    pub null_rate: Option<f64>, // share of baseline rows with an empty value (None if unknown)
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BinningStrategy {
+    /// Equal-width bins between the baseline min and max.
+    #[default]
+    EqualWidth,
+    /// Equal-frequency bins at baseline quantiles.
+    Quantile,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct BinningConfig {
+    pub strategy: BinningStrategy,
+    pub num_bins: usize,
+}
+
+impl Default for BinningConfig {
+    fn default() -> Self {
+        BinningConfig {
+            strategy: BinningStrategy::EqualWidth,
+            num_bins: 10,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct BaselineUploadParams {
+    pub binning: Option<BinningStrategy>,
+    pub num_bins: Option<usize>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct CategoricalStats {
     pub frequencies: std::collections::HashMap<String, f64>, // normalized frequencies
+    #[serde(default)]
+    pub count: usize, // number of values the frequencies were built from (0 if unknown)
+    #[serde(default)]
+    pub null_rate: Option<f64>, // share of baseline rows with an empty value (None if unknown)
+}
+
+impl FeatureType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FeatureType::Numeric => "numeric",
+            FeatureType::Categorical => "categorical",
+        }
+    }
+}
+
+impl FeatureStats {
+    pub fn null_rate(&self) -> Option<f64> {
+        match &self.stats {
+            FeatureStatsData::Numeric(numeric) => numeric.null_rate,
+            FeatureStatsData::Categorical(categorical) => categorical.null_rate,
+        }
+    }
+
+    /// JSON stored in `baseline_features.metadata`.
+    pub fn metadata_json(&self) -> serde_json::Value {
+        match &self.stats {
+            FeatureStatsData::Numeric(numeric) => serde_json::to_value(numeric),
+            FeatureStatsData::Categorical(categorical) => serde_json::to_value(categorical),
+        }
+        .unwrap_or_else(|_| serde_json::json!({}))
+    }
+
+    /// Rebuilds feature statistics from a stored baseline feature row.
+    pub fn from_baseline_feature(feature: &BaselineFeature) -> Option<FeatureStats> {
+        let (feature_type, stats) = match feature.feature_type.as_str() {
+            "numeric" => (
+                FeatureType::Numeric,
+                FeatureStatsData::Numeric(serde_json::from_str(&feature.metadata).ok()?),
+            ),
+            "categorical" => (
+                FeatureType::Categorical,
+                FeatureStatsData::Categorical(serde_json::from_str(&feature.metadata).ok()?),
+            ),
+            _ => return None,
+        };
+
+        Some(FeatureStats {
+            name: feature.feature_name.clone(),
+            feature_type,
+            stats,
+        })
+    }
 }
@@ -1,41 +1,381 @@
-use crate::models::AlertFilter;
-use crate::storage::alerts;
+use crate::models::{
+    AckAlertRequest, AlertCommentRequest, AlertCursor, AlertFilter, AlertPageParams,
+    AlertSummaryParams, AlertTransition, AssignAlertRequest, BulkAlertRequest, BulkAlertResponse,
+    ResolveAlertRequest, SnoozeAlertRequest, DEFAULT_ALERT_PAGE_SIZE, MAX_ALERT_PAGE_SIZE,
+};
+use crate::services::live_events::{self, LiveEvents};
+use crate::storage::{alert_events, alerts, projects};
 use actix_web::{web, HttpResponse, Responder};
+use chrono::Utc;
 use sqlx::SqlitePool;
 
+// Actor recorded in the audit trail when a request doesn't name one
+pub(crate) const DEFAULT_ACTOR: &str = "user";
+
+/// A page of the project's alerts matching the query's filters, with the total count.
 pub async fn list_alerts(
     pool: web::Data<SqlitePool>,
     path: web::Path<String>,
     query: web::Query<AlertFilter>,
+    page: web::Query<AlertPageParams>,
 ) -> impl Responder {
     let project_id = path.into_inner();
-    let filter = AlertFilter {
-        status: query.status.clone(),
-        severity: query.severity.clone(),
-        feature_name: query.feature_name.clone(),
-        alert_type: query.alert_type.clone(),
-        start_time: query.start_time.clone(),
-        end_time: query.end_time.clone(),
+    let filter = query.into_inner();
+
+    let limit = page.limit.unwrap_or(DEFAULT_ALERT_PAGE_SIZE);
+    if !(1..=MAX_ALERT_PAGE_SIZE).contains(&limit) {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": format!("limit must be between 1 and {}", MAX_ALERT_PAGE_SIZE)
+        }));
+    }
+
+    let after = match &page.cursor {
+        Some(cursor) => match AlertCursor::decode(cursor, page.sort, page.order) {
+            Some(after) => Some(after),
+            None => {
+                return HttpResponse::BadRequest().json(serde_json::json!({
+                    "error": "Invalid cursor; cursors only work with the sort and order they were returned for"
+                }));
+            }
+        },
+        None => None,
     };
 
-    match alerts::list_alerts(&pool, &project_id, &filter).await {
-        Ok(alerts_list) => HttpResponse::Ok().json(alerts_list),
+    match alerts::list_alerts_page(
+        &pool,
+        &project_id,
+        &filter,
+        page.sort,
+        page.order,
+        limit,
+        after.as_ref(),
+    )
+    .await
+    {
+        Ok(alerts_page) => HttpResponse::Ok().json(alerts_page),
+        Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": e.to_string()
+        })),
+    }
+}
+
+/// Aggregates over the project's alerts created within the query's time bounds.
+pub async fn alert_summary(
+    pool: web::Data<SqlitePool>,
+    path: web::Path<String>,
+    query: web::Query<AlertSummaryParams>,
+) -> impl Responder {
+    let project_id = path.into_inner();
+
+    match projects::get_project(&pool, &project_id).await {
+        Ok(Some(_)) => {}
+        Ok(None) => {
+            return HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Project not found"
+            }));
+        }
+        Err(e) => {
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": e.to_string()
+            }));
+        }
+    }
+
+    match alerts::alert_summary(&pool, &project_id, &query).await {
+        Ok(summary) => HttpResponse::Ok().json(summary),
         Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
             "error": e.to_string()
         })),
     }
 }
 
+/// Streams the project's new and updated alerts and completed evaluations as
+/// Server-Sent Events until the client disconnects.
+pub async fn stream_alerts(
+    pool: web::Data<SqlitePool>,
+    live_events: web::Data<LiveEvents>,
+    path: web::Path<String>,
+) -> impl Responder {
+    let project_id = path.into_inner();
+
+    match projects::get_project(&pool, &project_id).await {
+        Ok(Some(_)) => {}
+        Ok(None) => {
+            return HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Project not found"
+            }));
+        }
+        Err(e) => {
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": e.to_string()
+            }));
+        }
+    }
+
+    HttpResponse::Ok()
+        .content_type("text/event-stream")
+        .insert_header(("Cache-Control", "no-cache"))
+        .streaming(live_events::sse_stream(
+            live_events.subscribe(),
+            project_id,
+            live_events::KEEPALIVE_INTERVAL,
+        ))
+}
+
 pub async fn ack_alert(
     pool: web::Data<SqlitePool>,
+    live_events: web::Data<LiveEvents>,
     path: web::Path<(String, String)>,
+    req: Option<web::Json<AckAlertRequest>>,
 ) -> impl Responder {
-    let (_project_id, alert_id) = path.into_inner();
+    let (project_id, alert_id) = path.into_inner();
+    let req = req.map(|r| r.into_inner()).unwrap_or_default();
+    let actor = req.actor.as_deref().unwrap_or(DEFAULT_ACTOR);
 
-    match alerts::ack_alert(&pool, &alert_id).await {
-        Ok(_) => HttpResponse::Ok().json(serde_json::json!({
-            "message": "Alert acknowledged"
+    match alerts::ack_alert(&pool, &project_id, &alert_id, actor).await {
+        Ok(AlertTransition::Applied) => {
+            live_events
+                .publish_updated(&pool, &project_id, &[alert_id])
+                .await;
+            HttpResponse::Ok().json(serde_json::json!({
+                "message": "Alert acknowledged"
+            }))
+        }
+        Ok(AlertTransition::NotFound) => HttpResponse::NotFound().json(serde_json::json!({
+            "error": "Alert not found"
         })),
+        Ok(AlertTransition::NotAllowed(status)) => {
+            HttpResponse::Conflict().json(serde_json::json!({
+                "error": format!("Cannot acknowledge an alert that is {}", status)
+            }))
+        }
+        Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": e.to_string()
+        })),
+    }
+}
+
+pub async fn resolve_alert(
+    pool: web::Data<SqlitePool>,
+    live_events: web::Data<LiveEvents>,
+    path: web::Path<(String, String)>,
+    req: Option<web::Json<ResolveAlertRequest>>,
+) -> impl Responder {
+    let (project_id, alert_id) = path.into_inner();
+    let req = req.map(|r| r.into_inner()).unwrap_or_default();
+    let resolved_by = req.resolved_by.as_deref().unwrap_or(DEFAULT_ACTOR);
+
+    match alerts::resolve_alert(
+        &pool,
+        &project_id,
+        &alert_id,
+        resolved_by,
+        req.note.as_deref(),
+    )
+    .await
+    {
+        Ok(AlertTransition::Applied) => {
+            live_events
+                .publish_updated(&pool, &project_id, &[alert_id])
+                .await;
+            HttpResponse::Ok().json(serde_json::json!({
+                "message": "Alert resolved"
+            }))
+        }
+        Ok(AlertTransition::NotFound) => HttpResponse::NotFound().json(serde_json::json!({
+            "error": "Alert not found"
+        })),
+        Ok(AlertTransition::NotAllowed(status)) => {
+            HttpResponse::Conflict().json(serde_json::json!({
+                "error": format!("Cannot resolve an alert that is {}", status)
+            }))
+        }
+        Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": e.to_string()
+        })),
+    }
+}
+
+pub async fn snooze_alert(
+    pool: web::Data<SqlitePool>,
+    live_events: web::Data<LiveEvents>,
+    path: web::Path<(String, String)>,
+    req: web::Json<SnoozeAlertRequest>,
+) -> impl Responder {
+    let (project_id, alert_id) = path.into_inner();
+
+    if req.until <= Utc::now() {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "until must be in the future"
+        }));
+    }
+    let actor = req.actor.as_deref().unwrap_or(DEFAULT_ACTOR);
+
+    match alerts::snooze_alert(
+        &pool,
+        &project_id,
+        &alert_id,
+        req.until,
+        actor,
+        req.note.as_deref(),
+    )
+    .await
+    {
+        Ok(AlertTransition::Applied) => {
+            live_events
+                .publish_updated(&pool, &project_id, &[alert_id])
+                .await;
+            HttpResponse::Ok().json(serde_json::json!({
+                "message": "Alert snoozed"
+            }))
+        }
+        Ok(AlertTransition::NotFound) => HttpResponse::NotFound().json(serde_json::json!({
+            "error": "Alert not found"
+        })),
+        Ok(AlertTransition::NotAllowed(status)) => {
+            HttpResponse::Conflict().json(serde_json::json!({
+                "error": format!("Cannot snooze an alert that is {}", status)
+            }))
+        }
+        Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": e.to_string()
+        })),
+    }
+}
+
+pub async fn assign_alert(
+    pool: web::Data<SqlitePool>,
+    live_events: web::Data<LiveEvents>,
+    path: web::Path<(String, String)>,
+    req: web::Json<AssignAlertRequest>,
+) -> impl Responder {
+    let (project_id, alert_id) = path.into_inner();
+
+    let assignee = req.assignee.as_deref().map(str::trim);
+    if assignee == Some("") {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "assignee must not be empty; use null to unassign"
+        }));
+    }
+    let actor = req.actor.as_deref().unwrap_or(DEFAULT_ACTOR);
+
+    match alerts::assign_alert(&pool, &project_id, &alert_id, assignee, actor).await {
+        Ok(true) => {
+            live_events
+                .publish_updated(&pool, &project_id, &[alert_id])
+                .await;
+            HttpResponse::Ok().json(serde_json::json!({
+                "message": "Alert assigned"
+            }))
+        }
+        Ok(false) => HttpResponse::NotFound().json(serde_json::json!({
+            "error": "Alert not found"
+        })),
+        Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": e.to_string()
+        })),
+    }
+}
+
+pub async fn add_comment(
+    pool: web::Data<SqlitePool>,
+    path: web::Path<(String, String)>,
+    req: web::Json<AlertCommentRequest>,
+) -> impl Responder {
+    let (project_id, alert_id) = path.into_inner();
+
+    let comment = req.comment.trim();
+    if comment.is_empty() {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "comment must not be empty"
+        }));
+    }
+    let actor = req.actor.as_deref().unwrap_or(DEFAULT_ACTOR);
+
+    match alerts::add_comment(&pool, &project_id, &alert_id, actor, comment).await {
+        Ok(true) => HttpResponse::Created().json(serde_json::json!({
+            "message": "Comment added"
+        })),
+        Ok(false) => HttpResponse::NotFound().json(serde_json::json!({
+            "error": "Alert not found"
+        })),
+        Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": e.to_string()
+        })),
+    }
+}
+
+pub async fn list_alert_events(
+    pool: web::Data<SqlitePool>,
+    path: web::Path<(String, String)>,
+) -> impl Responder {
+    let (project_id, alert_id) = path.into_inner();
+
+    match alerts::alert_exists(&pool, &project_id, &alert_id).await {
+        Ok(true) => {}
+        Ok(false) => {
+            return HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Alert not found"
+            }));
+        }
+        Err(e) => {
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": e.to_string()
+            }));
+        }
+    }
+
+    match alert_events::list_alert_events(&pool, &alert_id).await {
+        Ok(events) => HttpResponse::Ok().json(events),
+        Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": e.to_string()
+        })),
+    }
+}
+
+pub async fn bulk_update_alerts(
+    pool: web::Data<SqlitePool>,
+    live_events: web::Data<LiveEvents>,
+    path: web::Path<String>,
+    req: web::Json<BulkAlertRequest>,
+) -> impl Responder {
+    let project_id = path.into_inner();
+
+    match projects::get_project(&pool, &project_id).await {
+        Ok(Some(_)) => {}
+        Ok(None) => {
+            return HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Project not found"
+            }));
+        }
+        Err(e) => {
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": e.to_string()
+            }));
+        }
+    }
+
+    let actor = req.actor.as_deref().unwrap_or(DEFAULT_ACTOR);
+
+    match alerts::bulk_update_alerts(
+        &pool,
+        &project_id,
+        &req.filter,
+        req.action,
+        actor,
+        req.note.as_deref(),
+    )
+    .await
+    {
+        Ok(alert_ids) => {
+            live_events
+                .publish_updated(&pool, &project_id, &alert_ids)
+                .await;
+            HttpResponse::Ok().json(BulkAlertResponse {
+                action: req.action,
+                affected: alert_ids.len() as u64,
+            })
+        }
         Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
             "error": e.to_string()
         })),
@@ -1,4 +1,4 @@
-use crate::models::BaselineMetadata;
+use crate::models::{BaselineMetadata, BaselineUploadParams, BinningConfig};
 use crate::services::baseline_builder;
 use crate::storage::baselines;
 use actix_multipart::Multipart;
@@ -7,13 +7,31 @@ use futures_util::TryStreamExt;
 use sqlx::SqlitePool;
 use std::io::Cursor;
 
+fn parse_optional_json(value: Option<&str>) -> Option<serde_json::Value> {
+    value.and_then(|v| serde_json::from_str(v).ok())
+}
+
 pub async fn upload_baseline(
     pool: web::Data<SqlitePool>,
     path: web::Path<String>,
+    query: web::Query<BaselineUploadParams>,
     mut payload: Multipart,
 ) -> impl Responder {
     let project_id = path.into_inner();
 
+    let mut binning = BinningConfig::default();
+    if let Some(strategy) = query.binning {
+        binning.strategy = strategy;
+    }
+    if let Some(num_bins) = query.num_bins {
+        if !(1..=100).contains(&num_bins) {
+            return HttpResponse::BadRequest().json(serde_json::json!({
+                "error": "num_bins must be between 1 and 100"
+            }));
+        }
+        binning.num_bins = num_bins;
+    }
+
     // Verify project exists
     match crate::storage::projects::get_project(&pool, &project_id).await {
         Ok(Some(_)) => {}
@@ -47,44 +65,25 @@ pub async fn upload_baseline(
     }
 
     let cursor = Cursor::new(file_data);
-    match baseline_builder::build_baseline_from_csv(&pool, &project_id, cursor).await {
+    match baseline_builder::build_baseline_from_csv(&pool, &project_id, cursor, &binning).await {
         Ok((baseline_version, feature_stats)) => {
             // Get actual prediction rate from baseline
             if let Ok(Some(baseline)) = baselines::get_latest_baseline(&pool, &project_id).await {
                 let features: Vec<_> = feature_stats
                     .iter()
-                    .map(|fs| {
-                        let metadata = match &fs.stats {
-                            crate::models::FeatureStatsData::Numeric(n) => {
-                                serde_json::json!({
-                                    "bins": n.bins,
-                                    "probabilities": n.probabilities
-                                })
-                            }
-                            crate::models::FeatureStatsData::Categorical(c) => {
-                                serde_json::json!({
-                                    "frequencies": c.frequencies
-                                })
-                            }
-                        };
-
-                        crate::models::FeatureMetadata {
-                            name: fs.name.clone(),
-                            feature_type: match fs.feature_type {
-                                crate::models::FeatureType::Numeric => "numeric".to_string(),
-                                crate::models::FeatureType::Categorical => {
-                                    "categorical".to_string()
-                                }
-                            },
-                            metadata,
-                        }
+                    .map(|fs| crate::models::FeatureMetadata {
+                        name: fs.name.clone(),
+                        feature_type: fs.feature_type.as_str().to_string(),
+                        metadata: fs.metadata_json(),
                     })
                     .collect();
 
                 HttpResponse::Ok().json(serde_json::json!({
                     "baseline_version": baseline_version,
                     "prediction_rate": baseline.prediction_rate,
-                    "features": features
+                    "features": features,
+                    "confidence": parse_optional_json(baseline.confidence_stats.as_deref()),
+                    "calibration": parse_optional_json(baseline.calibration.as_deref())
                 }))
             } else {
                 HttpResponse::InternalServerError().json(serde_json::json!({
@@ -123,6 +122,8 @@ pub async fn get_baseline(pool: web::Data<SqlitePool>, path: web::Path<String>)
                         prediction_rate: baseline.prediction_rate,
                         created_at: baseline.created_at,
                         features: feature_metadata,
+                        confidence: parse_optional_json(baseline.confidence_stats.as_deref()),
+                        calibration: parse_optional_json(baseline.calibration.as_deref()),
                     };
 
                     HttpResponse::Ok().json(metadata)
@@ -0,0 +1,48 @@
+use crate::storage::{evaluations, projects};
+use actix_web::{web, HttpResponse, Responder};
+use sqlx::SqlitePool;
+
+pub async fn list_evaluations(
+    pool: web::Data<SqlitePool>,
+    path: web::Path<String>,
+) -> impl Responder {
+    let project_id = path.into_inner();
+
+    match projects::get_project(&pool, &project_id).await {
+        Ok(Some(_)) => {}
+        Ok(None) => {
+            return HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Project not found"
+            }));
+        }
+        Err(e) => {
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": e.to_string()
+            }));
+        }
+    }
+
+    match evaluations::list_evaluations(&pool, &project_id).await {
+        Ok(evaluation_list) => HttpResponse::Ok().json(evaluation_list),
+        Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": e.to_string()
+        })),
+    }
+}
+
+pub async fn get_evaluation(
+    pool: web::Data<SqlitePool>,
+    path: web::Path<(String, String)>,
+) -> impl Responder {
+    let (project_id, evaluation_id) = path.into_inner();
+
+    match evaluations::get_evaluation(&pool, &project_id, &evaluation_id).await {
+        Ok(Some(evaluation)) => HttpResponse::Ok().json(evaluation),
+        Ok(None) => HttpResponse::NotFound().json(serde_json::json!({
+            "error": "Evaluation not found"
+        })),
+        Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": e.to_string()
+        })),
+    }
+}
@@ -0,0 +1,70 @@
+use crate::models::{FeatureHistory, FeatureHistoryParams};
+use crate::storage::{history, projects};
+use crate::utils::downsample_history;
+use actix_web::{web, HttpResponse, Responder};
+use sqlx::SqlitePool;
+
+const DEFAULT_MAX_POINTS: usize = 500;
+
+pub async fn get_feature_history(
+    pool: web::Data<SqlitePool>,
+    path: web::Path<(String, String)>,
+    query: web::Query<FeatureHistoryParams>,
+) -> impl Responder {
+    let (project_id, feature_name) = path.into_inner();
+
+    let max_points = query.max_points.unwrap_or(DEFAULT_MAX_POINTS);
+    if max_points == 0 {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "max_points must be at least 1"
+        }));
+    }
+
+    match projects::get_project(&pool, &project_id).await {
+        Ok(Some(_)) => {}
+        Ok(None) => {
+            return HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Project not found"
+            }));
+        }
+        Err(e) => {
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": e.to_string()
+            }));
+        }
+    }
+
+    match history::get_feature_history(
+        &pool,
+        &project_id,
+        &feature_name,
+        query.start_time,
+        query.end_time,
+    )
+    .await
+    {
+        Ok(points) if points.is_empty() => {
+            match history::feature_exists(&pool, &project_id, &feature_name).await {
+                Ok(true) => HttpResponse::Ok().json(FeatureHistory {
+                    feature_name,
+                    total_evaluations: 0,
+                    points,
+                }),
+                Ok(false) => HttpResponse::NotFound().json(serde_json::json!({
+                    "error": "Feature not found"
+                })),
+                Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
+                    "error": e.to_string()
+                })),
+            }
+        }
+        Ok(points) => HttpResponse::Ok().json(FeatureHistory {
+            feature_name,
+            total_evaluations: points.len(),
+            points: downsample_history(points, max_points),
+        }),
+        Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": e.to_string()
+        })),
+    }
+}
@@ -0,0 +1,167 @@
+use super::alerts::DEFAULT_ACTOR;
+use crate::models::{
+    AckAlertRequest, AlertFilter, BulkAlertAction, IncidentDetail, IncidentFilter,
+    ResolveAlertRequest,
+};
+use crate::services::live_events::LiveEvents;
+use crate::storage::{alerts, incidents, projects};
+use actix_web::{web, HttpResponse, Responder};
+use sqlx::SqlitePool;
+
+pub async fn list_incidents(
+    pool: web::Data<SqlitePool>,
+    path: web::Path<String>,
+    query: web::Query<IncidentFilter>,
+) -> impl Responder {
+    let project_id = path.into_inner();
+
+    match projects::get_project(&pool, &project_id).await {
+        Ok(Some(_)) => {}
+        Ok(None) => {
+            return HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Project not found"
+            }));
+        }
+        Err(e) => {
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": e.to_string()
+            }));
+        }
+    }
+
+    match incidents::list_incidents(&pool, &project_id, query.status.as_deref()).await {
+        Ok(incidents) => HttpResponse::Ok().json(incidents),
+        Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": e.to_string()
+        })),
+    }
+}
+
+/// An incident with its alerts, newest first.
+pub async fn get_incident(
+    pool: web::Data<SqlitePool>,
+    path: web::Path<(String, String)>,
+) -> impl Responder {
+    let (project_id, incident_id) = path.into_inner();
+
+    let incident = match incidents::get_incident(&pool, &project_id, &incident_id).await {
+        Ok(Some(incident)) => incident,
+        Ok(None) => {
+            return HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Incident not found"
+            }));
+        }
+        Err(e) => {
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": e.to_string()
+            }));
+        }
+    };
+
+    let filter = AlertFilter {
+        incident_id: Some(incident_id),
+        ..AlertFilter::default()
+    };
+    match alerts::list_alerts(&pool, &project_id, &filter).await {
+        Ok(alerts) => HttpResponse::Ok().json(IncidentDetail { incident, alerts }),
+        Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": e.to_string()
+        })),
+    }
+}
+
+pub async fn ack_incident(
+    pool: web::Data<SqlitePool>,
+    live_events: web::Data<LiveEvents>,
+    path: web::Path<(String, String)>,
+    req: Option<web::Json<AckAlertRequest>>,
+) -> impl Responder {
+    let (project_id, incident_id) = path.into_inner();
+    let req = req.map(|r| r.into_inner()).unwrap_or_default();
+    let actor = req.actor.as_deref().unwrap_or(DEFAULT_ACTOR);
+
+    update_incident(
+        &pool,
+        &live_events,
+        &project_id,
+        &incident_id,
+        BulkAlertAction::Ack,
+        actor,
+        None,
+    )
+    .await
+}
+
+pub async fn resolve_incident(
+    pool: web::Data<SqlitePool>,
+    live_events: web::Data<LiveEvents>,
+    path: web::Path<(String, String)>,
+    req: Option<web::Json<ResolveAlertRequest>>,
+) -> impl Responder {
+    let (project_id, incident_id) = path.into_inner();
+    let req = req.map(|r| r.into_inner()).unwrap_or_default();
+    let resolved_by = req.resolved_by.as_deref().unwrap_or(DEFAULT_ACTOR);
+
+    update_incident(
+        &pool,
+        &live_events,
+        &project_id,
+        &incident_id,
+        BulkAlertAction::Resolve,
+        resolved_by,
+        req.note.as_deref(),
+    )
+    .await
+}
+
+/// Acks or resolves all of an incident's alerts and returns the updated incident.
+async fn update_incident(
+    pool: &SqlitePool,
+    live_events: &LiveEvents,
+    project_id: &str,
+    incident_id: &str,
+    action: BulkAlertAction,
+    actor: &str,
+    note: Option<&str>,
+) -> HttpResponse {
+    match incidents::get_incident(pool, project_id, incident_id).await {
+        Ok(Some(_)) => {}
+        Ok(None) => {
+            return HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Incident not found"
+            }));
+        }
+        Err(e) => {
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": e.to_string()
+            }));
+        }
+    }
+
+    let filter = AlertFilter {
+        incident_id: Some(incident_id.to_string()),
+        ..AlertFilter::default()
+    };
+    match alerts::bulk_update_alerts(pool, project_id, &filter, action, actor, note).await {
+        Ok(alert_ids) => {
+            live_events
+                .publish_updated(pool, project_id, &alert_ids)
+                .await
+        }
+        Err(e) => {
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": e.to_string()
+            }));
+        }
+    }
+
+    match incidents::get_incident(pool, project_id, incident_id).await {
+        Ok(Some(incident)) => HttpResponse::Ok().json(incident),
+        Ok(None) => HttpResponse::NotFound().json(serde_json::json!({
+            "error": "Incident not found"
+        })),
+        Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": e.to_string()
+        })),
+    }
+}
@@ -1,19 +1,29 @@
-use crate::models::UploadIncomingResponse;
+use crate::models::{
+    CreateEvaluationParams, EvaluationFeature, IncomingUploadParams, UploadIncomingResponse,
+};
+use crate::services::email::{self, Mailer};
+use crate::services::live_events::{LiveEventKind, LiveEvents};
+use crate::services::webhooks::{self, RetryPolicy};
 use crate::services::{alert_engine, drift_evaluator};
-use crate::storage::baselines;
+use crate::storage::{baselines, evaluations, windows};
 use crate::utils::parse_csv;
 use actix_multipart::Multipart;
 use actix_web::{web, HttpResponse, Responder};
 use futures_util::TryStreamExt;
+use sha2::{Digest, Sha256};
 use sqlx::SqlitePool;
 use std::io::Cursor;
 
 pub async fn upload_incoming(
     pool: web::Data<SqlitePool>,
+    mailer: web::Data<Option<Mailer>>,
+    live_events: web::Data<LiveEvents>,
     path: web::Path<String>,
+    query: web::Query<IncomingUploadParams>,
     mut payload: Multipart,
 ) -> impl Responder {
     let project_id = path.into_inner();
+    let window = query.window;
 
     // Verify project exists
     match crate::storage::projects::get_project(&pool, &project_id).await {
@@ -75,10 +85,18 @@ pub async fn upload_incoming(
 
     let rows_processed = incoming_records.len();
 
+    if window.is_some() && !incoming_headers.iter().any(|h| h == "timestamp") {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "A timestamp column is required for windowed evaluation"
+        }));
+    }
+
+    let content_hash = format!("{:x}", Sha256::digest(&file_data));
+
     // Evaluate drift
     let cursor_for_drift = Cursor::new(file_data);
     let drift_result =
-        match drift_evaluator::evaluate_drift(&pool, &project_id, cursor_for_drift).await {
+        match drift_evaluator::evaluate_drift(&pool, &project_id, cursor_for_drift, window).await {
             Ok(result) => result,
             Err(e) => {
                 return HttpResponse::InternalServerError().json(serde_json::json!({
@@ -100,53 +118,34 @@ pub async fn upload_incoming(
 
     let baseline_features: Vec<crate::models::FeatureStats> = baseline_features_db
         .iter()
-        .filter_map(|bf| {
-            let metadata: serde_json::Value = serde_json::from_str(&bf.metadata).ok()?;
-            let stats = match bf.feature_type.as_str() {
-                "numeric" => {
-                    let bins = metadata["bins"]
-                        .as_array()?
-                        .iter()
-                        .filter_map(|v| v.as_f64())
-                        .collect();
-                    let probabilities = metadata["probabilities"]
-                        .as_array()?
-                        .iter()
-                        .filter_map(|v| v.as_f64())
-                        .collect();
-                    crate::models::FeatureStatsData::Numeric(crate::models::NumericStats {
-                        bins,
-                        probabilities,
-                    })
-                }
-                "categorical" => {
-                    let frequencies: std::collections::HashMap<String, f64> = metadata
-                        ["frequencies"]
-                        .as_object()?
-                        .iter()
-                        .filter_map(|(k, v)| Some((k.clone(), v.as_f64()?)))
-                        .collect();
-                    crate::models::FeatureStatsData::Categorical(crate::models::CategoricalStats {
-                        frequencies,
-                    })
-                }
-                _ => return None,
-            };
-
-            Some(crate::models::FeatureStats {
-                name: bf.feature_name.clone(),
-                feature_type: match bf.feature_type.as_str() {
-                    "numeric" => crate::models::FeatureType::Numeric,
-                    _ => crate::models::FeatureType::Categorical,
-                },
-                stats,
-            })
-        })
+        .filter_map(crate::models::FeatureStats::from_baseline_feature)
         .collect();
 
+    // Read before the transaction opens, so the upload only holds one connection
+    let alert_context = match alert_engine::AlertContext::load(&pool, &project_id).await {
+        Ok(context) => context,
+        Err(e) => {
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": format!("Failed to load alert settings: {}", e)
+            }));
+        }
+    };
+
+    // Alerts, the evaluation and its windows are stored together, so a failed upload
+    // leaves nothing behind for a retry to be deduplicated against
+    let mut tx = match pool.begin().await {
+        Ok(tx) => tx,
+        Err(e) => {
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": e.to_string()
+            }));
+        }
+    };
+
     // Generate alerts
-    let (alerts_created, health) = match alert_engine::generate_alerts(
-        &pool,
+    let generated = match alert_engine::generate_alerts(
+        &mut tx,
+        &alert_context,
         &project_id,
         baseline.baseline_version,
         &drift_result,
@@ -163,9 +162,126 @@ pub async fn upload_incoming(
         }
     };
 
-    HttpResponse::Ok().json(UploadIncomingResponse {
+    // Record the evaluation, so results below the alert thresholds are kept too
+    let mut features: Vec<EvaluationFeature> = drift_result
+        .feature_drifts
+        .iter()
+        .map(|(name, drift)| EvaluationFeature::from_drift(name, drift))
+        .collect();
+    features.sort_by(|a, b| a.feature_name.cmp(&b.feature_name));
+
+    let evaluation = match evaluations::create_evaluation(
+        &mut *tx,
+        &CreateEvaluationParams {
+            project_id: project_id.clone(),
+            baseline_version: baseline.baseline_version,
+            row_count: rows_processed,
+            prediction_rate: drift_result.incoming_prediction_rate,
+            prediction_shift: drift_result.prediction_shift,
+            drift_metric: drift_result.drift_metric,
+            health: generated.health.clone(),
+            alerts_created: generated.created,
+            content_hash,
+            features,
+        },
+    )
+    .await
+    {
+        Ok(evaluation) => evaluation,
+        Err(e) => {
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": format!("Failed to store evaluation: {}", e)
+            }));
+        }
+    };
+    let evaluation_id = evaluation.evaluation.evaluation_id.clone();
+
+    if let Some(window) = window {
+        if let Err(e) = windows::create_drift_windows(
+            &mut *tx,
+            &project_id,
+            &evaluation_id,
+            baseline.baseline_version,
+            window,
+            &drift_result.windows,
+        )
+        .await
+        {
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": format!("Failed to store drift windows: {}", e)
+            }));
+        }
+    }
+
+    if let Err(e) = tx.commit().await {
+        return HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": format!("Failed to store evaluation: {}", e)
+        }));
+    }
+
+    let response = UploadIncomingResponse {
+        evaluation_id,
         rows_processed,
-        alerts_created,
-        health,
-    })
+        alerts_created: generated.created,
+        alerts_resolved: generated.resolved,
+        incident_id: generated.incident_id,
+        health: generated.health,
+        drift_metric: drift_result.drift_metric,
+        window,
+        windows: drift_result.windows,
+        skipped_timestamps: drift_result.skipped_timestamps,
+    };
+
+    // Published once committed, so subscribers never see alerts that were rolled back
+    if let Err(e) = live_events
+        .publish_alerts(&pool, &project_id, &generated.changes)
+        .await
+    {
+        log::error!("Failed to publish alerts for project {}: {}", project_id, e);
+    }
+    live_events.publish(&project_id, LiveEventKind::EvaluationCompleted, &response);
+
+    // Notify webhooks and email in separate background tasks, so slow endpoints and
+    // retries hold up neither the upload nor each other
+    if !generated.notifications.is_empty() {
+        {
+            let pool = pool.clone();
+            let project_id = project_id.clone();
+            let upload = response.clone();
+            let notifications = generated.notifications.clone();
+            actix_web::rt::spawn(async move {
+                if let Err(e) = webhooks::notify_alerts(
+                    &pool,
+                    &project_id,
+                    &evaluation,
+                    &upload,
+                    &notifications,
+                    RetryPolicy::default(),
+                )
+                .await
+                {
+                    log::error!(
+                        "Failed to notify webhooks for project {}: {}",
+                        project_id,
+                        e
+                    );
+                }
+            });
+        }
+
+        if let Some(mailer) = mailer.get_ref().clone() {
+            let pool = pool.clone();
+            let project_id = project_id.clone();
+            let notifications = generated.notifications;
+            actix_web::rt::spawn(async move {
+                if let Err(e) =
+                    email::notify_alerts(&pool, &mailer, &project_id, &notifications).await
+                {
+                    log::error!("Failed to email alerts for project {}: {}", project_id, e);
+                }
+            });
+        }
+    }
+
+    HttpResponse::Ok().json(response)
 }
@@ -1,4 +1,12 @@
 pub mod alerts;
 pub mod baseline;
+pub mod evaluations;
+pub mod history;
+pub mod incidents;
 pub mod incoming;
+pub mod notifications;
 pub mod projects;
+pub mod rules;
+pub mod settings;
+pub mod webhooks;
+pub mod windows;
@@ -0,0 +1,155 @@
+use crate::models::UpdateNotificationSettingsRequest;
+use crate::services::email::{self, Mailer};
+use crate::storage::{notifications, projects};
+use actix_web::{web, HttpResponse, Responder};
+use chrono::Utc;
+use sqlx::SqlitePool;
+
+pub async fn get_notification_settings(
+    pool: web::Data<SqlitePool>,
+    path: web::Path<String>,
+) -> impl Responder {
+    let project_id = path.into_inner();
+
+    match projects::get_project(&pool, &project_id).await {
+        Ok(Some(_)) => {}
+        Ok(None) => {
+            return HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Project not found"
+            }));
+        }
+        Err(e) => {
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": e.to_string()
+            }));
+        }
+    }
+
+    match notifications::get_notification_settings(&pool, &project_id).await {
+        Ok(settings) => HttpResponse::Ok().json(settings),
+        Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": e.to_string()
+        })),
+    }
+}
+
+pub async fn update_notification_settings(
+    pool: web::Data<SqlitePool>,
+    path: web::Path<String>,
+    req: web::Json<UpdateNotificationSettingsRequest>,
+) -> impl Responder {
+    let project_id = path.into_inner();
+
+    match projects::get_project(&pool, &project_id).await {
+        Ok(Some(_)) => {}
+        Ok(None) => {
+            return HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Project not found"
+            }));
+        }
+        Err(e) => {
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": e.to_string()
+            }));
+        }
+    }
+
+    let mut settings = match notifications::get_notification_settings(&pool, &project_id).await {
+        Ok(s) => s,
+        Err(e) => {
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": e.to_string()
+            }));
+        }
+    };
+
+    if let Some(recipients) = &req.email_recipients {
+        let mut email_recipients: Vec<String> = Vec::new();
+        for recipient in recipients {
+            let recipient = recipient.trim();
+            if !email::is_valid_address(recipient) {
+                return HttpResponse::BadRequest().json(serde_json::json!({
+                    "error": format!("Invalid email address: {}", recipient)
+                }));
+            }
+            if !email_recipients.iter().any(|r| r == recipient) {
+                email_recipients.push(recipient.to_string());
+            }
+        }
+        settings.email_recipients = email_recipients;
+    }
+
+    if let Some(email_immediate) = req.email_immediate {
+        settings.email_immediate = email_immediate;
+    }
+
+    if let Some(email_digest) = req.email_digest {
+        settings.email_digest = email_digest;
+    }
+
+    if let Some(digest_hour) = req.digest_hour {
+        if !(0..=23).contains(&digest_hour) {
+            return HttpResponse::BadRequest().json(serde_json::json!({
+                "error": "digest_hour must be between 0 and 23"
+            }));
+        }
+        settings.digest_hour = digest_hour;
+    }
+
+    match notifications::upsert_notification_settings(&pool, &settings).await {
+        Ok(settings) => HttpResponse::Ok().json(settings),
+        Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": e.to_string()
+        })),
+    }
+}
+
+/// Sends the project's digest of open alerts now, whether or not one is due.
+pub async fn send_digest(
+    pool: web::Data<SqlitePool>,
+    mailer: web::Data<Option<Mailer>>,
+    path: web::Path<String>,
+) -> impl Responder {
+    let project_id = path.into_inner();
+
+    match projects::get_project(&pool, &project_id).await {
+        Ok(Some(_)) => {}
+        Ok(None) => {
+            return HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Project not found"
+            }));
+        }
+        Err(e) => {
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": e.to_string()
+            }));
+        }
+    }
+
+    let Some(mailer) = mailer.get_ref() else {
+        return HttpResponse::ServiceUnavailable().json(serde_json::json!({
+            "error": "Email is not configured; set SMTP_HOST"
+        }));
+    };
+
+    match notifications::get_notification_settings(&pool, &project_id).await {
+        Ok(settings) if settings.email_recipients.is_empty() => {
+            return HttpResponse::BadRequest().json(serde_json::json!({
+                "error": "The project has no email recipients"
+            }));
+        }
+        Ok(_) => {}
+        Err(e) => {
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": e.to_string()
+            }));
+        }
+    }
+
+    match email::send_digest(&pool, mailer, &project_id, Utc::now()).await {
+        Ok(digest) => HttpResponse::Ok().json(digest),
+        Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": e.to_string()
+        })),
+    }
+}
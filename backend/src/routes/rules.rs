@@ -0,0 +1,225 @@
+use crate::models::{AlertRule, AlertRulesResponse, ValidateRulesRequest, ValidateRulesResponse};
+use crate::services::rule_engine::{self, RuleContext, RuleInput};
+use crate::storage::{evaluations, projects, settings};
+use actix_web::{web, HttpResponse, Responder};
+use sqlx::SqlitePool;
+use std::collections::HashMap;
+
+pub async fn get_rules(pool: web::Data<SqlitePool>, path: web::Path<String>) -> impl Responder {
+    let project_id = path.into_inner();
+
+    match projects::get_project(&pool, &project_id).await {
+        Ok(Some(_)) => {}
+        Ok(None) => {
+            return HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Project not found"
+            }));
+        }
+        Err(e) => {
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": e.to_string()
+            }));
+        }
+    }
+
+    match settings::get_alert_rules(&pool, &project_id).await {
+        Ok(Some(rules)) => HttpResponse::Ok().json(AlertRulesResponse {
+            custom: true,
+            rules,
+        }),
+        Ok(None) => HttpResponse::Ok().json(AlertRulesResponse {
+            custom: false,
+            rules: rule_engine::default_rules(),
+        }),
+        Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": e.to_string()
+        })),
+    }
+}
+
+pub async fn update_rules(
+    pool: web::Data<SqlitePool>,
+    path: web::Path<String>,
+    req: web::Json<Vec<AlertRule>>,
+) -> impl Responder {
+    let project_id = path.into_inner();
+
+    match projects::get_project(&pool, &project_id).await {
+        Ok(Some(_)) => {}
+        Ok(None) => {
+            return HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Project not found"
+            }));
+        }
+        Err(e) => {
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": e.to_string()
+            }));
+        }
+    }
+
+    let rules = req.into_inner();
+    let errors = rule_engine::validate_rules(&rules);
+    if !errors.is_empty() {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "Invalid rules",
+            "details": errors
+        }));
+    }
+
+    match settings::set_alert_rules(&pool, &project_id, Some(&rules)).await {
+        Ok(_) => HttpResponse::Ok().json(AlertRulesResponse {
+            custom: true,
+            rules,
+        }),
+        Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": e.to_string()
+        })),
+    }
+}
+
+pub async fn reset_rules(pool: web::Data<SqlitePool>, path: web::Path<String>) -> impl Responder {
+    let project_id = path.into_inner();
+
+    match projects::get_project(&pool, &project_id).await {
+        Ok(Some(_)) => {}
+        Ok(None) => {
+            return HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Project not found"
+            }));
+        }
+        Err(e) => {
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": e.to_string()
+            }));
+        }
+    }
+
+    match settings::set_alert_rules(&pool, &project_id, None).await {
+        Ok(_) => HttpResponse::Ok().json(AlertRulesResponse {
+            custom: false,
+            rules: rule_engine::default_rules(),
+        }),
+        Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": e.to_string()
+        })),
+    }
+}
+
+/// Dry-runs rules against a stored evaluation without creating alerts.
+pub async fn validate_rules(
+    pool: web::Data<SqlitePool>,
+    path: web::Path<String>,
+    req: web::Json<ValidateRulesRequest>,
+) -> impl Responder {
+    let project_id = path.into_inner();
+
+    match projects::get_project(&pool, &project_id).await {
+        Ok(Some(_)) => {}
+        Ok(None) => {
+            return HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Project not found"
+            }));
+        }
+        Err(e) => {
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": e.to_string()
+            }));
+        }
+    }
+
+    let req = req.into_inner();
+    let rules = match req.rules {
+        Some(rules) => rules,
+        None => match settings::get_alert_rules(&pool, &project_id).await {
+            Ok(rules) => rules.unwrap_or_else(rule_engine::default_rules),
+            Err(e) => {
+                return HttpResponse::InternalServerError().json(serde_json::json!({
+                    "error": e.to_string()
+                }));
+            }
+        },
+    };
+
+    let errors = rule_engine::validate_rules(&rules);
+    if !errors.is_empty() {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "Invalid rules",
+            "details": errors
+        }));
+    }
+
+    let evaluation = match &req.evaluation_id {
+        Some(evaluation_id) => evaluations::get_evaluation(&pool, &project_id, evaluation_id).await,
+        None => evaluations::list_recent_evaluation_details(&pool, &project_id, None, 1)
+            .await
+            .map(|latest| latest.into_iter().next()),
+    };
+    let evaluation = match evaluation {
+        Ok(Some(evaluation)) => evaluation,
+        Ok(None) => {
+            return HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Evaluation not found"
+            }));
+        }
+        Err(e) => {
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": e.to_string()
+            }));
+        }
+    };
+
+    let history = match evaluations::list_recent_evaluation_details(
+        &pool,
+        &project_id,
+        Some(evaluation.evaluation.created_at),
+        rule_engine::history_depth(&rules),
+    )
+    .await
+    {
+        Ok(history) => history,
+        Err(e) => {
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": e.to_string()
+            }));
+        }
+    };
+
+    let project_settings = match settings::get_project_settings(&pool, &project_id).await {
+        Ok(s) => s,
+        Err(e) => {
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": e.to_string()
+            }));
+        }
+    };
+    let feature_settings: HashMap<_, _> =
+        match settings::list_feature_settings(&pool, &project_id).await {
+            Ok(all) => all
+                .into_iter()
+                .map(|f| (f.feature_name.clone(), f))
+                .collect(),
+            Err(e) => {
+                return HttpResponse::InternalServerError().json(serde_json::json!({
+                    "error": e.to_string()
+                }));
+            }
+        };
+
+    let history: Vec<RuleInput> = history.iter().map(RuleInput::from_evaluation).collect();
+    let alerts = rule_engine::evaluate_rules(
+        &rules,
+        &RuleInput::from_evaluation(&evaluation),
+        &history,
+        &RuleContext {
+            drift_metric: evaluation.evaluation.drift_metric,
+            project_settings: &project_settings,
+            feature_settings: &feature_settings,
+        },
+    );
+
+    HttpResponse::Ok().json(ValidateRulesResponse {
+        evaluation_id: evaluation.evaluation.evaluation_id,
+        alerts,
+    })
+}
@@ -0,0 +1,273 @@
+use crate::models::{FeatureSettings, UpdateFeatureSettingsRequest, UpdateProjectSettingsRequest};
+use crate::storage::{projects, settings};
+use actix_web::{web, HttpResponse, Responder};
+use sqlx::SqlitePool;
+
+pub async fn get_settings(pool: web::Data<SqlitePool>, path: web::Path<String>) -> impl Responder {
+    let project_id = path.into_inner();
+
+    match projects::get_project(&pool, &project_id).await {
+        Ok(Some(_)) => {}
+        Ok(None) => {
+            return HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Project not found"
+            }));
+        }
+        Err(e) => {
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": e.to_string()
+            }));
+        }
+    }
+
+    match settings::get_project_settings(&pool, &project_id).await {
+        Ok(project_settings) => HttpResponse::Ok().json(project_settings),
+        Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": e.to_string()
+        })),
+    }
+}
+
+pub async fn update_settings(
+    pool: web::Data<SqlitePool>,
+    path: web::Path<String>,
+    req: web::Json<UpdateProjectSettingsRequest>,
+) -> impl Responder {
+    let project_id = path.into_inner();
+
+    match projects::get_project(&pool, &project_id).await {
+        Ok(Some(_)) => {}
+        Ok(None) => {
+            return HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Project not found"
+            }));
+        }
+        Err(e) => {
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": e.to_string()
+            }));
+        }
+    }
+
+    let mut project_settings = match settings::get_project_settings(&pool, &project_id).await {
+        Ok(s) => s,
+        Err(e) => {
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": e.to_string()
+            }));
+        }
+    };
+
+    if let Some(drift_metric) = req.drift_metric {
+        project_settings.drift_metric = drift_metric;
+    }
+
+    if let Some(significance_level) = req.significance_level {
+        if !(significance_level > 0.0 && significance_level <= 1.0) {
+            return HttpResponse::BadRequest().json(serde_json::json!({
+                "error": "significance_level must be in (0, 1]"
+            }));
+        }
+        project_settings.significance_level = significance_level;
+    }
+
+    if let Some(null_rate_tolerance) = req.null_rate_tolerance {
+        if !(0.0..=1.0).contains(&null_rate_tolerance) {
+            return HttpResponse::BadRequest().json(serde_json::json!({
+                "error": "null_rate_tolerance must be between 0 and 1"
+            }));
+        }
+        project_settings.null_rate_tolerance = null_rate_tolerance;
+    }
+
+    if let Some(alert_cooldown_minutes) = req.alert_cooldown_minutes {
+        if alert_cooldown_minutes < 0 {
+            return HttpResponse::BadRequest().json(serde_json::json!({
+                "error": "alert_cooldown_minutes must not be negative"
+            }));
+        }
+        project_settings.alert_cooldown_minutes = alert_cooldown_minutes;
+    }
+
+    if let Some(alert_resolve_hysteresis) = req.alert_resolve_hysteresis {
+        if !(0.0..1.0).contains(&alert_resolve_hysteresis) {
+            return HttpResponse::BadRequest().json(serde_json::json!({
+                "error": "alert_resolve_hysteresis must be in [0, 1)"
+            }));
+        }
+        project_settings.alert_resolve_hysteresis = alert_resolve_hysteresis;
+    }
+
+    if let Some(incident_window_minutes) = req.incident_window_minutes {
+        if incident_window_minutes < 0 {
+            return HttpResponse::BadRequest().json(serde_json::json!({
+                "error": "incident_window_minutes must not be negative"
+            }));
+        }
+        project_settings.incident_window_minutes = incident_window_minutes;
+    }
+
+    if let Some(threshold) = req.feature_drift_warn_threshold {
+        project_settings.feature_drift_warn_threshold = threshold;
+    }
+    if let Some(threshold) = req.feature_drift_critical_threshold {
+        project_settings.feature_drift_critical_threshold = threshold;
+    }
+    if let Some(threshold) = req.prediction_shift_warn_threshold {
+        project_settings.prediction_shift_warn_threshold = threshold;
+    }
+    if let Some(threshold) = req.prediction_shift_critical_threshold {
+        project_settings.prediction_shift_critical_threshold = threshold;
+    }
+
+    let threshold_error = threshold_pair_error(
+        "feature_drift",
+        project_settings.feature_drift_warn_threshold,
+        project_settings.feature_drift_critical_threshold,
+    )
+    .or_else(|| {
+        threshold_pair_error(
+            "prediction_shift",
+            project_settings.prediction_shift_warn_threshold,
+            project_settings.prediction_shift_critical_threshold,
+        )
+    });
+    if let Some(error) = threshold_error {
+        return HttpResponse::BadRequest().json(serde_json::json!({ "error": error }));
+    }
+
+    match settings::upsert_project_settings(&pool, &project_settings).await {
+        Ok(saved) => HttpResponse::Ok().json(saved),
+        Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": e.to_string()
+        })),
+    }
+}
+
+/// Checks a (warn, critical) threshold pair, returning an error message if it is invalid.
+fn threshold_pair_error(name: &str, warn: Option<f64>, critical: Option<f64>) -> Option<String> {
+    for (level, threshold) in [("warn", warn), ("critical", critical)] {
+        if let Some(threshold) = threshold {
+            if !threshold.is_finite() || threshold < 0.0 {
+                return Some(format!(
+                    "{}_{}_threshold must be a non-negative number",
+                    name, level
+                ));
+            }
+        }
+    }
+    match (warn, critical) {
+        (Some(warn), Some(critical)) if warn > critical => Some(format!(
+            "{}_warn_threshold must not exceed {}_critical_threshold",
+            name, name
+        )),
+        _ => None,
+    }
+}
+
+pub async fn list_feature_settings(
+    pool: web::Data<SqlitePool>,
+    path: web::Path<String>,
+) -> impl Responder {
+    let project_id = path.into_inner();
+
+    match projects::get_project(&pool, &project_id).await {
+        Ok(Some(_)) => {}
+        Ok(None) => {
+            return HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Project not found"
+            }));
+        }
+        Err(e) => {
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": e.to_string()
+            }));
+        }
+    }
+
+    match settings::list_feature_settings(&pool, &project_id).await {
+        Ok(feature_settings) => HttpResponse::Ok().json(feature_settings),
+        Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": e.to_string()
+        })),
+    }
+}
+
+pub async fn update_feature_settings(
+    pool: web::Data<SqlitePool>,
+    path: web::Path<(String, String)>,
+    req: web::Json<UpdateFeatureSettingsRequest>,
+) -> impl Responder {
+    let (project_id, feature_name) = path.into_inner();
+
+    match projects::get_project(&pool, &project_id).await {
+        Ok(Some(_)) => {}
+        Ok(None) => {
+            return HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Project not found"
+            }));
+        }
+        Err(e) => {
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": e.to_string()
+            }));
+        }
+    }
+
+    let existing = match settings::list_feature_settings(&pool, &project_id).await {
+        Ok(all) => all.into_iter().find(|f| f.feature_name == feature_name),
+        Err(e) => {
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": e.to_string()
+            }));
+        }
+    };
+    let mut feature_settings = existing.unwrap_or(FeatureSettings {
+        project_id: project_id.clone(),
+        feature_name: feature_name.clone(),
+        drift_warn_threshold: None,
+        drift_critical_threshold: None,
+        updated_at: None,
+    });
+
+    if let Some(threshold) = req.drift_warn_threshold {
+        feature_settings.drift_warn_threshold = threshold;
+    }
+    if let Some(threshold) = req.drift_critical_threshold {
+        feature_settings.drift_critical_threshold = threshold;
+    }
+
+    if let Some(error) = threshold_pair_error(
+        "drift",
+        feature_settings.drift_warn_threshold,
+        feature_settings.drift_critical_threshold,
+    ) {
+        return HttpResponse::BadRequest().json(serde_json::json!({ "error": error }));
+    }
+
+    match settings::upsert_feature_settings(&pool, &feature_settings).await {
+        Ok(saved) => HttpResponse::Ok().json(saved),
+        Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": e.to_string()
+        })),
+    }
+}
+
+pub async fn delete_feature_settings(
+    pool: web::Data<SqlitePool>,
+    path: web::Path<(String, String)>,
+) -> impl Responder {
+    let (project_id, feature_name) = path.into_inner();
+
+    match settings::delete_feature_settings(&pool, &project_id, &feature_name).await {
+        Ok(true) => HttpResponse::Ok().json(serde_json::json!({
+            "message": "Feature settings removed"
+        })),
+        Ok(false) => HttpResponse::NotFound().json(serde_json::json!({
+            "error": "Feature settings not found"
+        })),
+        Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": e.to_string()
+        })),
+    }
+}
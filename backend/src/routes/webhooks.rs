@@ -0,0 +1,208 @@
+use crate::models::{CreateWebhookRequest, CreateWebhookResponse, NotificationEvent};
+use crate::services::chat_messages;
+use crate::services::webhooks::{self, RetryPolicy};
+use crate::storage::{self, projects};
+use actix_web::{web, HttpResponse, Responder};
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+const DELIVERY_LOG_LIMIT: i64 = 100;
+
+pub async fn list_webhooks(pool: web::Data<SqlitePool>, path: web::Path<String>) -> impl Responder {
+    let project_id = path.into_inner();
+
+    match projects::get_project(&pool, &project_id).await {
+        Ok(Some(_)) => {}
+        Ok(None) => {
+            return HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Project not found"
+            }));
+        }
+        Err(e) => {
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": e.to_string()
+            }));
+        }
+    }
+
+    match storage::webhooks::list_webhooks(&pool, &project_id).await {
+        Ok(webhooks) => HttpResponse::Ok().json(webhooks),
+        Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": e.to_string()
+        })),
+    }
+}
+
+pub async fn create_webhook(
+    pool: web::Data<SqlitePool>,
+    path: web::Path<String>,
+    req: web::Json<CreateWebhookRequest>,
+) -> impl Responder {
+    let project_id = path.into_inner();
+
+    match projects::get_project(&pool, &project_id).await {
+        Ok(Some(_)) => {}
+        Ok(None) => {
+            return HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Project not found"
+            }));
+        }
+        Err(e) => {
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": e.to_string()
+            }));
+        }
+    }
+
+    let url = req.url.trim();
+    if !(url.starts_with("http://") || url.starts_with("https://")) {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "url must be an http:// or https:// URL"
+        }));
+    }
+    let min_severity = req.min_severity.as_deref().unwrap_or("WARN");
+    if !matches!(min_severity, "WARN" | "CRITICAL") {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "min_severity must be WARN or CRITICAL"
+        }));
+    }
+    let secret = match req.secret.as_deref() {
+        Some("") => {
+            return HttpResponse::BadRequest().json(serde_json::json!({
+                "error": "secret must not be empty"
+            }));
+        }
+        Some(secret) => secret.to_string(),
+        None => Uuid::new_v4().simple().to_string(),
+    };
+
+    match storage::webhooks::create_webhook(
+        &pool,
+        &project_id,
+        url,
+        &secret,
+        min_severity,
+        req.format,
+    )
+    .await
+    {
+        Ok(webhook) => HttpResponse::Created().json(CreateWebhookResponse { webhook, secret }),
+        Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": e.to_string()
+        })),
+    }
+}
+
+pub async fn delete_webhook(
+    pool: web::Data<SqlitePool>,
+    path: web::Path<(String, String)>,
+) -> impl Responder {
+    let (project_id, webhook_id) = path.into_inner();
+
+    match storage::webhooks::delete_webhook(&pool, &project_id, &webhook_id).await {
+        Ok(true) => HttpResponse::NoContent().finish(),
+        Ok(false) => HttpResponse::NotFound().json(serde_json::json!({
+            "error": "Webhook not found"
+        })),
+        Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": e.to_string()
+        })),
+    }
+}
+
+/// Sends a test payload, or a test message for Slack and Teams webhooks, once
+/// without retries, and returns the delivery.
+pub async fn test_webhook(
+    pool: web::Data<SqlitePool>,
+    path: web::Path<(String, String)>,
+) -> impl Responder {
+    let (project_id, webhook_id) = path.into_inner();
+
+    let project = match projects::get_project(&pool, &project_id).await {
+        Ok(Some(project)) => project,
+        Ok(None) => {
+            return HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Project not found"
+            }));
+        }
+        Err(e) => {
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": e.to_string()
+            }));
+        }
+    };
+    let webhook = match storage::webhooks::get_webhook(&pool, &project_id, &webhook_id).await {
+        Ok(Some(webhook)) => webhook,
+        Ok(None) => {
+            return HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Webhook not found"
+            }));
+        }
+        Err(e) => {
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": e.to_string()
+            }));
+        }
+    };
+
+    let payload = chat_messages::test_message(
+        webhook.format,
+        &project,
+        &chat_messages::project_alerts_url(&project_id),
+    )
+    .unwrap_or_else(|| webhooks::test_payload(&project));
+
+    let result = match webhooks::client() {
+        Ok(client) => {
+            webhooks::deliver(
+                &pool,
+                &client,
+                &webhook,
+                None,
+                NotificationEvent::Test,
+                payload,
+                RetryPolicy {
+                    max_attempts: 1,
+                    ..RetryPolicy::default()
+                },
+            )
+            .await
+        }
+        Err(e) => Err(e),
+    };
+
+    match result {
+        Ok(delivery) => HttpResponse::Ok().json(delivery),
+        Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": e.to_string()
+        })),
+    }
+}
+
+pub async fn list_deliveries(
+    pool: web::Data<SqlitePool>,
+    path: web::Path<(String, String)>,
+) -> impl Responder {
+    let (project_id, webhook_id) = path.into_inner();
+
+    match storage::webhooks::get_webhook(&pool, &project_id, &webhook_id).await {
+        Ok(Some(_)) => {}
+        Ok(None) => {
+            return HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Webhook not found"
+            }));
+        }
+        Err(e) => {
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": e.to_string()
+            }));
+        }
+    }
+
+    match storage::webhooks::list_deliveries(&pool, &webhook_id, DELIVERY_LOG_LIMIT).await {
+        Ok(deliveries) => HttpResponse::Ok().json(deliveries),
+        Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": e.to_string()
+        })),
+    }
+}
@@ -0,0 +1,33 @@
+use crate::models::DriftWindowFilter;
+use crate::storage::{projects, windows};
+use actix_web::{web, HttpResponse, Responder};
+use sqlx::SqlitePool;
+
+pub async fn list_windows(
+    pool: web::Data<SqlitePool>,
+    path: web::Path<String>,
+    query: web::Query<DriftWindowFilter>,
+) -> impl Responder {
+    let project_id = path.into_inner();
+
+    match projects::get_project(&pool, &project_id).await {
+        Ok(Some(_)) => {}
+        Ok(None) => {
+            return HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Project not found"
+            }));
+        }
+        Err(e) => {
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": e.to_string()
+            }));
+        }
+    }
+
+    match windows::list_drift_windows(&pool, &project_id, query.window).await {
+        Ok(window_list) => HttpResponse::Ok().json(window_list),
+        Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": e.to_string()
+        })),
+    }
+}
@@ -1,51 +1,300 @@
-use crate::models::{CreateAlertParams, FeatureStats};
+use crate::models::{
+    AlertNotification, AlertOutcome, AlertRule, CreateAlertParams, DriftMetric, FeatureSettings,
+    FeatureStats, FeatureType, NotificationEvent, ProjectSettings,
+};
 use crate::services::drift_evaluator::DriftResult;
-use crate::storage::alerts;
-use sqlx::SqlitePool;
+use crate::services::live_events::LiveEventKind;
+use crate::services::rule_engine::{self, RuleContext, RuleInput};
+use crate::storage::{alerts, evaluations, incidents, settings};
+use crate::utils::is_special_column;
+use sqlx::{SqliteConnection, SqlitePool};
+use std::collections::{HashMap, HashSet};
 
 const FEATURE_DRIFT_WARN_THRESHOLD: f64 = 0.10;
 const FEATURE_DRIFT_CRITICAL_THRESHOLD: f64 = 0.20;
+const PSI_WARN_THRESHOLD: f64 = 0.10;
+const PSI_CRITICAL_THRESHOLD: f64 = 0.25;
+// JS and Hellinger cut-offs are just under the scores of an even two-bin split shifted
+// by 0.10 / 0.20, i.e. L1 0.10 / 0.20: JS 0.0073 / 0.0303 bits, Hellinger 0.071 / 0.145
+const JS_WARN_THRESHOLD: f64 = 0.007;
+const JS_CRITICAL_THRESHOLD: f64 = 0.03;
+const HELLINGER_WARN_THRESHOLD: f64 = 0.07;
+const HELLINGER_CRITICAL_THRESHOLD: f64 = 0.14;
+// Share of a feature's incoming values that are out of range / unseen in the baseline
+const UNEXPECTED_VALUES_WARN_THRESHOLD: f64 = 0.01;
+const UNEXPECTED_VALUES_CRITICAL_THRESHOLD: f64 = 0.10;
+// Increase in expected calibration error over the baseline
+const CALIBRATION_WARN_THRESHOLD: f64 = 0.05;
+const CALIBRATION_CRITICAL_THRESHOLD: f64 = 0.10;
+// A null rate change beyond this multiple of the project tolerance is CRITICAL
+const DATA_QUALITY_CRITICAL_MULTIPLIER: f64 = 2.0;
 const PREDICTION_SHIFT_WARN_THRESHOLD: f64 = 0.10;
 const PREDICTION_SHIFT_CRITICAL_THRESHOLD: f64 = 0.20;
 
+/// Returns the (warn, critical) feature drift thresholds for a metric.
+fn feature_drift_thresholds(metric: DriftMetric) -> (f64, f64) {
+    match metric {
+        // Categorical features fall back to L1 under Wasserstein, so they share thresholds
+        DriftMetric::L1 | DriftMetric::Wasserstein => (
+            FEATURE_DRIFT_WARN_THRESHOLD,
+            FEATURE_DRIFT_CRITICAL_THRESHOLD,
+        ),
+        DriftMetric::Psi => (PSI_WARN_THRESHOLD, PSI_CRITICAL_THRESHOLD),
+        DriftMetric::JensenShannon => (JS_WARN_THRESHOLD, JS_CRITICAL_THRESHOLD),
+        DriftMetric::Hellinger => (HELLINGER_WARN_THRESHOLD, HELLINGER_CRITICAL_THRESHOLD),
+    }
+}
+
+/// Returns the (warn, critical) drift thresholds for a feature: a feature override
+/// wins over the project threshold, which wins over the metric default.
+pub fn resolve_feature_drift_thresholds(
+    metric: DriftMetric,
+    project_settings: &ProjectSettings,
+    feature_settings: Option<&FeatureSettings>,
+) -> (f64, f64) {
+    let (default_warn, default_critical) = feature_drift_thresholds(metric);
+    let warn = feature_settings
+        .and_then(|f| f.drift_warn_threshold)
+        .or(project_settings.feature_drift_warn_threshold)
+        .unwrap_or(default_warn);
+    let critical = feature_settings
+        .and_then(|f| f.drift_critical_threshold)
+        .or(project_settings.feature_drift_critical_threshold)
+        .unwrap_or(default_critical);
+    (warn, critical)
+}
+
+/// Returns the (warn, critical) prediction shift thresholds for a project.
+pub fn prediction_shift_thresholds(project_settings: &ProjectSettings) -> (f64, f64) {
+    (
+        project_settings
+            .prediction_shift_warn_threshold
+            .unwrap_or(PREDICTION_SHIFT_WARN_THRESHOLD),
+        project_settings
+            .prediction_shift_critical_threshold
+            .unwrap_or(PREDICTION_SHIFT_CRITICAL_THRESHOLD),
+    )
+}
+
+/// A metric from the current evaluation that can show an earlier alert has recovered.
+#[derive(Debug, Clone, PartialEq)]
+struct RecoveryCheck {
+    alert_type: &'static str,
+    feature_name: Option<String>,
+    value: f64,
+    warn_threshold: f64,
+}
+
+/// Whether `value` has fallen far enough under the WARN threshold to resolve an alert.
+/// The `hysteresis` margin keeps a metric hovering around the threshold from
+/// resolving and re-raising its alert on every upload.
+fn has_recovered(value: f64, warn_threshold: f64, hysteresis: f64) -> bool {
+    value < warn_threshold * (1.0 - hysteresis)
+}
+
+/// The recovery checks for every threshold-based alert the evaluation measured.
+/// Schema alerts and custom rule types have no single metric and are left alone.
+fn recovery_checks(
+    drift_result: &DriftResult,
+    baseline_features: &[FeatureStats],
+    project_settings: &ProjectSettings,
+    feature_settings: &HashMap<String, FeatureSettings>,
+) -> Vec<RecoveryCheck> {
+    let drift_metric = drift_result.drift_metric;
+    let mut checks = Vec::new();
+
+    for (feature_name, feature_drift) in &drift_result.feature_drifts {
+        let baseline_feature = baseline_features.iter().find(|f| &f.name == feature_name);
+
+        let (warn_threshold, _) = resolve_feature_drift_thresholds(
+            drift_metric,
+            project_settings,
+            feature_settings.get(feature_name),
+        );
+        checks.push(RecoveryCheck {
+            alert_type: "FEATURE_DRIFT",
+            feature_name: Some(feature_name.clone()),
+            value: feature_drift.score,
+            warn_threshold,
+        });
+
+        let is_numeric = baseline_feature.is_some_and(|f| f.feature_type == FeatureType::Numeric);
+        checks.push(RecoveryCheck {
+            alert_type: if is_numeric {
+                "OUT_OF_RANGE"
+            } else {
+                "NEW_CATEGORY"
+            },
+            feature_name: Some(feature_name.clone()),
+            value: feature_drift
+                .unexpected_values
+                .as_ref()
+                .map_or(0.0, |u| u.fraction),
+            warn_threshold: UNEXPECTED_VALUES_WARN_THRESHOLD,
+        });
+
+        if let (Some(incoming_null_rate), Some(baseline_null_rate)) = (
+            feature_drift.null_rate,
+            baseline_feature.and_then(|f| f.null_rate()),
+        ) {
+            checks.push(RecoveryCheck {
+                alert_type: "DATA_QUALITY",
+                feature_name: Some(feature_name.clone()),
+                value: (incoming_null_rate - baseline_null_rate).abs(),
+                warn_threshold: project_settings.null_rate_tolerance,
+            });
+        }
+    }
+
+    let (prediction_shift_warn, _) = prediction_shift_thresholds(project_settings);
+    checks.push(RecoveryCheck {
+        alert_type: "PREDICTION_SHIFT",
+        feature_name: None,
+        value: drift_result.prediction_shift,
+        warn_threshold: prediction_shift_warn,
+    });
+
+    if let Some(confidence_drift) = &drift_result.confidence_drift {
+        let (warn_threshold, _) = resolve_feature_drift_thresholds(
+            drift_metric,
+            project_settings,
+            feature_settings.get("confidence"),
+        );
+        checks.push(RecoveryCheck {
+            alert_type: "CONFIDENCE_DRIFT",
+            feature_name: Some("confidence".to_string()),
+            value: confidence_drift.score,
+            warn_threshold,
+        });
+    }
+
+    if let Some(calibration) = &drift_result.calibration {
+        let baseline_ece = drift_result
+            .baseline_calibration
+            .as_ref()
+            .map_or(0.0, |c| c.expected_calibration_error);
+        checks.push(RecoveryCheck {
+            alert_type: "CALIBRATION",
+            feature_name: None,
+            value: calibration.expected_calibration_error - baseline_ece,
+            warn_threshold: CALIBRATION_WARN_THRESHOLD,
+        });
+    }
+
+    checks
+}
+
+/// What `generate_alerts` did for an evaluation.
+#[derive(Debug, Clone, Default)]
+pub struct GeneratedAlerts {
+    pub created: usize,
+    pub resolved: usize,
+    pub health: String,                        // "OK", "WARN" or "CRITICAL"
+    pub notifications: Vec<AlertNotification>, // new and escalated alerts
+    pub incident_id: Option<String>,           // incident of the new alerts
+    pub changes: Vec<(LiveEventKind, String)>, // alerts to push to live streams
+}
+
+impl GeneratedAlerts {
+    fn record(&mut self, outcome: AlertOutcome) {
+        let kind = if outcome.is_created() {
+            self.created += 1;
+            LiveEventKind::AlertCreated
+        } else {
+            LiveEventKind::AlertUpdated
+        };
+        self.changes.push((kind, outcome.alert_id().to_string()));
+        self.notifications.extend(outcome.notification());
+    }
+}
+
+/// The settings, rules and recent evaluations a project's alerts are generated from.
+pub struct AlertContext {
+    pub project_settings: ProjectSettings,
+    pub feature_settings: HashMap<String, FeatureSettings>,
+    pub rules: Vec<AlertRule>,
+    pub history: Vec<RuleInput>, // as many recent evaluations as the rules look back over
+}
+
+impl AlertContext {
+    /// Loads the project's context. Done before the upload's transaction opens, so
+    /// generating alerts needs no connection besides the transaction's.
+    pub async fn load(pool: &SqlitePool, project_id: &str) -> anyhow::Result<Self> {
+        let project_settings = settings::get_project_settings(pool, project_id).await?;
+        let feature_settings = settings::list_feature_settings(pool, project_id)
+            .await?
+            .into_iter()
+            .map(|f| (f.feature_name.clone(), f))
+            .collect();
+        let rules = settings::get_alert_rules(pool, project_id)
+            .await?
+            .unwrap_or_else(rule_engine::default_rules);
+        let history = match rule_engine::history_depth(&rules) {
+            0 => Vec::new(),
+            depth => evaluations::list_recent_evaluation_details(pool, project_id, None, depth)
+                .await?
+                .iter()
+                .map(RuleInput::from_evaluation)
+                .collect(),
+        };
+        Ok(AlertContext {
+            project_settings,
+            feature_settings,
+            rules,
+            history,
+        })
+    }
+}
+
+/// Raises alerts for an evaluation and resolves the ones it shows have recovered.
+/// Alerts are read and written through `conn`, so a caller can store them in the same
+/// transaction as the evaluation.
 pub async fn generate_alerts(
-    pool: &SqlitePool,
+    conn: &mut SqliteConnection,
+    context: &AlertContext,
     project_id: &str,
     baseline_version: i64,
     drift_result: &DriftResult,
     baseline_features: &[FeatureStats],
     incoming_headers: &[String],
-) -> anyhow::Result<(usize, String)> {
-    let mut alerts_created = 0;
+) -> anyhow::Result<GeneratedAlerts> {
+    let mut generated = GeneratedAlerts::default();
     let mut max_severity = "OK".to_string();
 
+    let project_settings = &context.project_settings;
+    let cooldown_minutes = project_settings.alert_cooldown_minutes;
+
     // Schema validation
-    let baseline_feature_names: std::collections::HashSet<String> =
+    let baseline_feature_names: HashSet<String> =
         baseline_features.iter().map(|f| f.name.clone()).collect();
 
-    let incoming_feature_names: std::collections::HashSet<String> = incoming_headers
+    let incoming_feature_names: HashSet<String> = incoming_headers
         .iter()
-        .filter(|h| *h != "prediction" && *h != "confidence" && *h != "timestamp")
+        .filter(|h| !is_special_column(h, incoming_headers))
         .cloned()
         .collect();
 
     // Check for missing features
     for feature_name in &baseline_feature_names {
         if !incoming_feature_names.contains(feature_name) {
-            alerts::create_alert(
-                pool,
-                &CreateAlertParams {
-                    project_id: project_id.to_string(),
-                    baseline_version,
-                    severity: "CRITICAL".to_string(),
-                    alert_type: "SCHEMA".to_string(),
-                    feature_name: Some(feature_name.clone()),
-                    metric_value: None,
-                    message: format!("Missing required feature: {}", feature_name),
-                },
-            )
-            .await?;
-            alerts_created += 1;
+            generated.record(
+                alerts::record_alert(
+                    &mut *conn,
+                    &CreateAlertParams {
+                        project_id: project_id.to_string(),
+                        baseline_version,
+                        severity: "CRITICAL".to_string(),
+                        alert_type: "SCHEMA".to_string(),
+                        feature_name: Some(feature_name.clone()),
+                        metric_value: None,
+                        message: format!("Missing required feature: {}", feature_name),
+                        drift_metric: None,
+                        rule_name: None,
+                    },
+                    cooldown_minutes,
+                )
+                .await?,
+            );
             max_severity = "CRITICAL".to_string();
         }
     }
@@ -53,59 +302,183 @@ pub async fn generate_alerts(
     // Check for extra features
     for feature_name in &incoming_feature_names {
         if !baseline_feature_names.contains(feature_name) {
-            alerts::create_alert(
-                pool,
-                &CreateAlertParams {
-                    project_id: project_id.to_string(),
-                    baseline_version,
-                    severity: "WARN".to_string(),
-                    alert_type: "SCHEMA".to_string(),
-                    feature_name: Some(feature_name.clone()),
-                    metric_value: None,
-                    message: format!("Extra feature detected: {}", feature_name),
-                },
-            )
-            .await?;
-            alerts_created += 1;
+            generated.record(
+                alerts::record_alert(
+                    &mut *conn,
+                    &CreateAlertParams {
+                        project_id: project_id.to_string(),
+                        baseline_version,
+                        severity: "WARN".to_string(),
+                        alert_type: "SCHEMA".to_string(),
+                        feature_name: Some(feature_name.clone()),
+                        metric_value: None,
+                        message: format!("Extra feature detected: {}", feature_name),
+                        drift_metric: None,
+                        rule_name: None,
+                    },
+                    cooldown_minutes,
+                )
+                .await?,
+            );
             if max_severity == "OK" {
                 max_severity = "WARN".to_string();
             }
         }
     }
 
-    // Feature drift alerts
-    for (feature_name, drift_value) in &drift_result.feature_drifts {
-        let severity = if *drift_value >= FEATURE_DRIFT_CRITICAL_THRESHOLD {
-            "CRITICAL"
-        } else if *drift_value >= FEATURE_DRIFT_WARN_THRESHOLD {
-            "WARN"
-        } else {
-            continue;
+    // Type mismatches: non-numeric values arriving for a numeric feature
+    for (feature_name, feature_drift) in &drift_result.feature_drifts {
+        let mismatches = match &feature_drift.type_mismatches {
+            Some(mismatches) => mismatches,
+            None => continue,
         };
 
-        alerts::create_alert(
-            pool,
+        let samples = mismatches
+            .top_values
+            .iter()
+            .map(|v| format!("'{}' ({})", v.value, v.count))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        generated.record(alerts::record_alert(
+            &mut *conn,
             &CreateAlertParams {
                 project_id: project_id.to_string(),
                 baseline_version,
-                severity: severity.to_string(),
-                alert_type: "FEATURE_DRIFT".to_string(),
+                severity: "CRITICAL".to_string(),
+                alert_type: "SCHEMA".to_string(),
                 feature_name: Some(feature_name.clone()),
-                metric_value: Some(*drift_value),
+                metric_value: Some(mismatches.fraction),
                 message: format!(
-                    "Feature '{}' drift detected: {:.4} (threshold: {})",
+                    "Type mismatch for numeric feature '{}': {} non-numeric values ({:.1}%; samples: {})",
                     feature_name,
-                    drift_value,
-                    if *drift_value >= FEATURE_DRIFT_CRITICAL_THRESHOLD {
-                        "CRITICAL"
-                    } else {
-                        "WARN"
-                    }
+                    mismatches.count,
+                    mismatches.fraction * 100.0,
+                    samples
                 ),
+                drift_metric: None,
+                rule_name: None,
             },
-        )
-        .await?;
-        alerts_created += 1;
+            cooldown_minutes,
+        ).await?);
+        max_severity = "CRITICAL".to_string();
+    }
+
+    let feature_settings = &context.feature_settings;
+
+    let significance_level = project_settings.significance_level;
+    let drift_metric = drift_result.drift_metric;
+
+    // Feature drift and prediction shift alerts come from the project's rules
+    let rule_alerts = rule_engine::evaluate_rules(
+        &context.rules,
+        &RuleInput::from_drift_result(drift_result),
+        &context.history,
+        &RuleContext {
+            drift_metric,
+            project_settings,
+            feature_settings,
+        },
+    );
+    // Rules can fire below the WARN threshold, so don't resolve what they just raised
+    let raised_by_rules: HashSet<(String, Option<String>)> = rule_alerts
+        .iter()
+        .map(|a| (a.alert_type.clone(), a.feature_name.clone()))
+        .collect();
+    for rule_alert in rule_alerts {
+        generated.record(
+            alerts::record_alert(
+                &mut *conn,
+                &CreateAlertParams {
+                    project_id: project_id.to_string(),
+                    baseline_version,
+                    severity: rule_alert.severity.clone(),
+                    alert_type: rule_alert.alert_type,
+                    feature_name: rule_alert.feature_name,
+                    metric_value: rule_alert.metric_value,
+                    message: rule_alert.message,
+                    drift_metric: rule_alert.drift_metric.map(|m| m.as_str().to_string()),
+                    rule_name: Some(rule_alert.rule_name),
+                },
+                rule_alert.cooldown_minutes.unwrap_or(cooldown_minutes),
+            )
+            .await?,
+        );
+
+        if rule_alert.severity == "CRITICAL" {
+            max_severity = "CRITICAL".to_string();
+        } else if max_severity == "OK" {
+            max_severity = "WARN".to_string();
+        }
+    }
+
+    // Out-of-range numeric values and unseen categories
+    for (feature_name, feature_drift) in &drift_result.feature_drifts {
+        let unexpected = match &feature_drift.unexpected_values {
+            Some(unexpected) => unexpected,
+            None => continue,
+        };
+
+        let severity = if unexpected.fraction >= UNEXPECTED_VALUES_CRITICAL_THRESHOLD {
+            "CRITICAL"
+        } else if unexpected.fraction >= UNEXPECTED_VALUES_WARN_THRESHOLD {
+            "WARN"
+        } else {
+            continue;
+        };
+
+        let top_values = unexpected
+            .top_values
+            .iter()
+            .map(|v| format!("{} ({})", v.value, v.count))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let is_numeric = baseline_features
+            .iter()
+            .any(|f| &f.name == feature_name && f.feature_type == FeatureType::Numeric);
+        let (alert_type, message) = if is_numeric {
+            (
+                "OUT_OF_RANGE",
+                format!(
+                    "Feature '{}' has {:.1}% of values outside the baseline range ({} rows; top values: {})",
+                    feature_name,
+                    unexpected.fraction * 100.0,
+                    unexpected.count,
+                    top_values
+                ),
+            )
+        } else {
+            (
+                "NEW_CATEGORY",
+                format!(
+                    "Feature '{}' has {:.1}% of values in categories unseen in the baseline ({} rows; top values: {})",
+                    feature_name,
+                    unexpected.fraction * 100.0,
+                    unexpected.count,
+                    top_values
+                ),
+            )
+        };
+
+        generated.record(
+            alerts::record_alert(
+                &mut *conn,
+                &CreateAlertParams {
+                    project_id: project_id.to_string(),
+                    baseline_version,
+                    severity: severity.to_string(),
+                    alert_type: alert_type.to_string(),
+                    feature_name: Some(feature_name.clone()),
+                    metric_value: Some(unexpected.fraction),
+                    message,
+                    drift_metric: None,
+                    rule_name: None,
+                },
+                cooldown_minutes,
+            )
+            .await?,
+        );
 
         if severity == "CRITICAL" {
             max_severity = "CRITICAL".to_string();
@@ -114,35 +487,55 @@ pub async fn generate_alerts(
         }
     }
 
-    // Prediction shift alert
-    let severity = if drift_result.prediction_shift >= PREDICTION_SHIFT_CRITICAL_THRESHOLD {
-        "CRITICAL"
-    } else if drift_result.prediction_shift >= PREDICTION_SHIFT_WARN_THRESHOLD {
-        "WARN"
-    } else {
-        "OK"
-    };
+    // Null rate changes
+    let tolerance = project_settings.null_rate_tolerance;
+    for (feature_name, feature_drift) in &drift_result.feature_drifts {
+        let incoming_null_rate = match feature_drift.null_rate {
+            Some(rate) => rate,
+            None => continue,
+        };
+        let baseline_null_rate = match baseline_features
+            .iter()
+            .find(|f| &f.name == feature_name)
+            .and_then(|f| f.null_rate())
+        {
+            Some(rate) => rate,
+            None => continue,
+        };
 
-    if severity != "OK" {
-        alerts::create_alert(
-            pool,
-            &CreateAlertParams {
-                project_id: project_id.to_string(),
-                baseline_version,
-                severity: severity.to_string(),
-                alert_type: "PREDICTION_SHIFT".to_string(),
-                feature_name: None,
-                metric_value: Some(drift_result.prediction_shift),
-                message: format!(
-                    "Prediction rate shift detected: {:.4} (baseline: {:.4}, incoming: {:.4})",
-                    drift_result.prediction_shift,
-                    drift_result.incoming_prediction_rate - drift_result.prediction_shift,
-                    drift_result.incoming_prediction_rate
+        let change = (incoming_null_rate - baseline_null_rate).abs();
+        let severity = if change > tolerance * DATA_QUALITY_CRITICAL_MULTIPLIER {
+            "CRITICAL"
+        } else if change > tolerance {
+            "WARN"
+        } else {
+            continue;
+        };
+
+        generated.record(
+            alerts::record_alert(
+                &mut *conn,
+                &CreateAlertParams {
+                    project_id: project_id.to_string(),
+                    baseline_version,
+                    severity: severity.to_string(),
+                    alert_type: "DATA_QUALITY".to_string(),
+                    feature_name: Some(feature_name.clone()),
+                    metric_value: Some(incoming_null_rate),
+                    message: format!(
+                    "Feature '{}' null rate changed: {:.1}% (baseline: {:.1}%, tolerance: {:.1}%)",
+                    feature_name,
+                    incoming_null_rate * 100.0,
+                    baseline_null_rate * 100.0,
+                    tolerance * 100.0
                 ),
-            },
-        )
-        .await?;
-        alerts_created += 1;
+                    drift_metric: None,
+                    rule_name: None,
+                },
+                cooldown_minutes,
+            )
+            .await?,
+        );
 
         if severity == "CRITICAL" {
             max_severity = "CRITICAL".to_string();
@@ -151,7 +544,175 @@ pub async fn generate_alerts(
         }
     }
 
-    Ok((alerts_created, max_severity))
+    // Confidence distribution drift, scored like a numeric feature
+    if let Some(confidence_drift) = &drift_result.confidence_drift {
+        let (warn_threshold, critical_threshold) = resolve_feature_drift_thresholds(
+            drift_metric,
+            project_settings,
+            feature_settings.get("confidence"),
+        );
+        let drift_value = confidence_drift.score;
+        let (significant, significance_note) = match &confidence_drift.significance {
+            Some(test) if test.p_value >= significance_level => (false, String::new()),
+            Some(test) => (
+                true,
+                format!(", {} p-value: {:.4}", test.test, test.p_value),
+            ),
+            None => (true, String::new()),
+        };
+        let severity = if drift_value >= critical_threshold {
+            "CRITICAL"
+        } else if drift_value >= warn_threshold {
+            "WARN"
+        } else {
+            "OK"
+        };
+
+        if severity != "OK" && significant {
+            generated.record(
+                alerts::record_alert(
+                    &mut *conn,
+                    &CreateAlertParams {
+                        project_id: project_id.to_string(),
+                        baseline_version,
+                        severity: severity.to_string(),
+                        alert_type: "CONFIDENCE_DRIFT".to_string(),
+                        feature_name: Some("confidence".to_string()),
+                        metric_value: Some(drift_value),
+                        message: format!(
+                            "Confidence distribution drift detected: {} {:.4} (threshold: {}{})",
+                            drift_metric.as_str(),
+                            drift_value,
+                            severity,
+                            significance_note
+                        ),
+                        drift_metric: Some(drift_metric.as_str().to_string()),
+                        rule_name: None,
+                    },
+                    cooldown_minutes,
+                )
+                .await?,
+            );
+
+            if severity == "CRITICAL" {
+                max_severity = "CRITICAL".to_string();
+            } else if max_severity == "OK" {
+                max_severity = "WARN".to_string();
+            }
+        }
+    }
+
+    // Calibration, when the incoming data is labelled
+    if let Some(calibration) = &drift_result.calibration {
+        let baseline_ece = drift_result
+            .baseline_calibration
+            .as_ref()
+            .map_or(0.0, |c| c.expected_calibration_error);
+        let increase = calibration.expected_calibration_error - baseline_ece;
+        let severity = if increase >= CALIBRATION_CRITICAL_THRESHOLD {
+            "CRITICAL"
+        } else if increase >= CALIBRATION_WARN_THRESHOLD {
+            "WARN"
+        } else {
+            "OK"
+        };
+
+        if severity != "OK" {
+            let direction = if calibration.mean_confidence > calibration.observed_rate {
+                "over-confident"
+            } else {
+                "under-confident"
+            };
+            generated.record(alerts::record_alert(
+                &mut *conn,
+                &CreateAlertParams {
+                    project_id: project_id.to_string(),
+                    baseline_version,
+                    severity: severity.to_string(),
+                    alert_type: "CALIBRATION".to_string(),
+                    feature_name: None,
+                    metric_value: Some(calibration.expected_calibration_error),
+                    message: format!(
+                        "Model is {}: expected calibration error {:.4} (baseline: {:.4}; mean confidence {:.4} vs observed rate {:.4})",
+                        direction,
+                        calibration.expected_calibration_error,
+                        baseline_ece,
+                        calibration.mean_confidence,
+                        calibration.observed_rate
+                    ),
+                    drift_metric: None,
+                    rule_name: None,
+                },
+                cooldown_minutes,
+            ).await?);
+
+            if severity == "CRITICAL" {
+                max_severity = "CRITICAL".to_string();
+            } else if max_severity == "OK" {
+                max_severity = "WARN".to_string();
+            }
+        }
+    }
+
+    // Resolve alerts whose metric has recovered
+    let hysteresis = project_settings.alert_resolve_hysteresis;
+    for check in recovery_checks(
+        drift_result,
+        baseline_features,
+        project_settings,
+        feature_settings,
+    ) {
+        let key = (check.alert_type.to_string(), check.feature_name.clone());
+        if raised_by_rules.contains(&key)
+            || !has_recovered(check.value, check.warn_threshold, hysteresis)
+        {
+            continue;
+        }
+
+        let note = format!(
+            "Recovered to {:.4} (WARN threshold: {}, hysteresis: {:.0}%)",
+            check.value,
+            check.warn_threshold,
+            hysteresis * 100.0
+        );
+        let resolved = alerts::resolve_matching_alerts(
+            &mut *conn,
+            project_id,
+            check.alert_type,
+            check.feature_name.as_deref(),
+            "auto",
+            &note,
+        )
+        .await?;
+        generated.resolved += resolved.len();
+        generated.changes.extend(
+            resolved
+                .into_iter()
+                .map(|alert_id| (LiveEventKind::AlertUpdated, alert_id)),
+        );
+    }
+
+    // Group the alerts this evaluation created into one incident
+    let created_ids: Vec<String> = generated
+        .notifications
+        .iter()
+        .filter(|n| n.event == NotificationEvent::AlertCreated)
+        .map(|n| n.alert_id.clone())
+        .collect();
+    if !created_ids.is_empty() {
+        generated.incident_id = Some(
+            incidents::group_alerts(
+                &mut *conn,
+                project_id,
+                &created_ids,
+                project_settings.incident_window_minutes,
+            )
+            .await?,
+        );
+    }
+
+    generated.health = max_severity;
+    Ok(generated)
 }
 
 #[cfg(test)]
@@ -164,6 +725,109 @@ mod tests {
         assert_eq!(FEATURE_DRIFT_CRITICAL_THRESHOLD, 0.20);
         assert_eq!(PREDICTION_SHIFT_WARN_THRESHOLD, 0.10);
         assert_eq!(PREDICTION_SHIFT_CRITICAL_THRESHOLD, 0.20);
+        assert_eq!(UNEXPECTED_VALUES_WARN_THRESHOLD, 0.01);
+        assert_eq!(UNEXPECTED_VALUES_CRITICAL_THRESHOLD, 0.10);
+        assert_eq!(CALIBRATION_WARN_THRESHOLD, 0.05);
+        assert_eq!(CALIBRATION_CRITICAL_THRESHOLD, 0.10);
+        assert_eq!(PSI_WARN_THRESHOLD, 0.10);
+        assert_eq!(PSI_CRITICAL_THRESHOLD, 0.25);
+    }
+
+    #[test]
+    fn test_feature_drift_thresholds_per_metric() {
+        assert_eq!(feature_drift_thresholds(DriftMetric::L1), (0.10, 0.20));
+        assert_eq!(feature_drift_thresholds(DriftMetric::Psi), (0.10, 0.25));
+        assert_eq!(
+            feature_drift_thresholds(DriftMetric::JensenShannon),
+            (0.007, 0.03)
+        );
+        assert_eq!(
+            feature_drift_thresholds(DriftMetric::Hellinger),
+            (0.07, 0.14)
+        );
+        assert_eq!(
+            feature_drift_thresholds(DriftMetric::Wasserstein),
+            (0.10, 0.20)
+        );
+    }
+
+    #[test]
+    fn test_feature_drift_thresholds_match_l1() {
+        use crate::models::{FeatureStatsData, NumericStats};
+        use crate::utils::compute_feature_drift;
+
+        let baseline = FeatureStats {
+            name: "income".to_string(),
+            feature_type: FeatureType::Numeric,
+            stats: FeatureStatsData::Numeric(NumericStats {
+                bins: vec![0.0, 1.0, 2.0],
+                probabilities: vec![0.5, 0.5],
+                ..Default::default()
+            }),
+        };
+        // 100 values split 60/40 and 70/30 shift the even split by 0.10 and 0.20
+        let shifted = |low: usize| {
+            let mut values = vec!["0.5".to_string(); low];
+            values.extend(vec!["1.5".to_string(); 100 - low]);
+            values
+        };
+        let (warn_shift, critical_shift) = (shifted(60), shifted(70));
+
+        for metric in [
+            DriftMetric::L1,
+            DriftMetric::JensenShannon,
+            DriftMetric::Hellinger,
+        ] {
+            let (warn, critical) = feature_drift_thresholds(metric);
+            let at_warn = compute_feature_drift(&baseline, &warn_shift, metric);
+            let at_critical = compute_feature_drift(&baseline, &critical_shift, metric);
+            assert!(at_warn >= warn - 1e-9 && at_warn < critical, "{:?}", metric);
+            assert!(at_critical >= critical - 1e-9, "{:?}", metric);
+        }
+    }
+
+    #[test]
+    fn test_resolve_feature_drift_thresholds_precedence() {
+        let mut project_settings = ProjectSettings::defaults("p1");
+        assert_eq!(
+            resolve_feature_drift_thresholds(DriftMetric::Psi, &project_settings, None),
+            (0.10, 0.25)
+        );
+
+        project_settings.feature_drift_warn_threshold = Some(0.15);
+        assert_eq!(
+            resolve_feature_drift_thresholds(DriftMetric::Psi, &project_settings, None),
+            (0.15, 0.25)
+        );
+
+        let location = FeatureSettings {
+            project_id: "p1".to_string(),
+            feature_name: "location".to_string(),
+            drift_warn_threshold: None,
+            drift_critical_threshold: Some(0.5),
+            updated_at: None,
+        };
+        assert_eq!(
+            resolve_feature_drift_thresholds(DriftMetric::Psi, &project_settings, Some(&location)),
+            (0.15, 0.5)
+        );
+    }
+
+    #[test]
+    fn test_prediction_shift_thresholds_override() {
+        let mut project_settings = ProjectSettings::defaults("p1");
+        assert_eq!(prediction_shift_thresholds(&project_settings), (0.10, 0.20));
+
+        project_settings.prediction_shift_critical_threshold = Some(0.3);
+        assert_eq!(prediction_shift_thresholds(&project_settings), (0.10, 0.3));
+    }
+
+    #[test]
+    fn test_has_recovered_applies_hysteresis() {
+        assert!(has_recovered(0.05, 0.10, 0.1));
+        assert!(!has_recovered(0.095, 0.10, 0.1));
+        assert!(has_recovered(0.095, 0.10, 0.0));
+        assert!(!has_recovered(0.10, 0.10, 0.0));
     }
 
     #[test]
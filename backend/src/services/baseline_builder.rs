@@ -1,6 +1,9 @@
-use crate::models::FeatureStats;
+use crate::models::{BinningConfig, FeatureStats};
 use crate::storage::baselines;
-use crate::utils::{build_baseline_stats, compute_prediction_rate, infer_feature_types, parse_csv};
+use crate::utils::{
+    build_baseline_stats, build_confidence_histogram, compute_calibration, compute_prediction_rate,
+    infer_feature_types, parse_csv,
+};
 use sqlx::SqlitePool;
 use std::io::Read;
 
@@ -8,6 +11,7 @@ pub async fn build_baseline_from_csv<R: Read>(
     pool: &SqlitePool,
     project_id: &str,
     csv_reader: R,
+    binning: &BinningConfig,
 ) -> anyhow::Result<(i64, Vec<FeatureStats>)> {
     let (records, headers) = parse_csv(csv_reader)?;
 
@@ -19,39 +23,37 @@ pub async fn build_baseline_from_csv<R: Read>(
     let feature_types = infer_feature_types(&records, &headers);
 
     // Build baseline statistics
-    let feature_stats = build_baseline_stats(&records, &headers, &feature_types);
+    let feature_stats = build_baseline_stats(&records, &headers, &feature_types, binning);
 
     // Compute prediction rate
     let prediction_rate = compute_prediction_rate(&records);
 
+    // Confidence distribution, and calibration when labels are present
+    let confidence_stats = build_confidence_histogram(&records)
+        .map(|stats| serde_json::to_string(&stats))
+        .transpose()?;
+    let calibration = compute_calibration(&records)
+        .map(|stats| serde_json::to_string(&stats))
+        .transpose()?;
+
     // Create baseline record
-    let baseline = baselines::create_baseline(pool, project_id, prediction_rate).await?;
+    let baseline = baselines::create_baseline(
+        pool,
+        project_id,
+        prediction_rate,
+        confidence_stats.as_deref(),
+        calibration.as_deref(),
+    )
+    .await?;
 
     // Store feature statistics
     for feature_stat in &feature_stats {
-        let metadata_json = match &feature_stat.stats {
-            crate::models::FeatureStatsData::Numeric(numeric) => {
-                serde_json::json!({
-                    "bins": numeric.bins,
-                    "probabilities": numeric.probabilities
-                })
-            }
-            crate::models::FeatureStatsData::Categorical(categorical) => {
-                serde_json::json!({
-                    "frequencies": categorical.frequencies
-                })
-            }
-        };
-
         baselines::create_baseline_feature(
             pool,
             &baseline.baseline_id,
             &feature_stat.name,
-            match feature_stat.feature_type {
-                crate::models::FeatureType::Numeric => "numeric",
-                crate::models::FeatureType::Categorical => "categorical",
-            },
-            &metadata_json.to_string(),
+            feature_stat.feature_type.as_str(),
+            &feature_stat.metadata_json().to_string(),
         )
         .await?;
     }
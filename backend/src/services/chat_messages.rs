@@ -0,0 +1,403 @@
+use crate::models::{
+    severity_rank, Alert, EvaluationFeature, Project, UploadIncomingResponse, WebhookFormat,
+};
+use serde_json::{json, Value};
+use std::env;
+
+const DEFAULT_APP_URL: &str = "http://localhost:4200";
+const TOP_FEATURES: usize = 5;
+const MAX_ALERTS: usize = 10;
+
+/// What a chat message reports about one upload: its results, the drift of each
+/// feature and the alerts it created or escalated.
+pub struct UploadSummary<'a> {
+    pub project: &'a Project,
+    pub upload: &'a UploadIncomingResponse,
+    pub features: &'a [EvaluationFeature],
+    pub alerts: &'a [Alert],
+}
+
+/// Link to a project's alerts page in the frontend, which is served from
+/// `DRIFTGUARD_APP_URL` (default `http://localhost:4200`).
+pub fn project_alerts_url(project_id: &str) -> String {
+    let base = env::var("DRIFTGUARD_APP_URL").unwrap_or_else(|_| DEFAULT_APP_URL.to_string());
+    format!(
+        "{}/projects/{}/alerts",
+        base.trim_end_matches('/'),
+        project_id
+    )
+}
+
+/// The message summarising an upload for a chat format, or None for `JSON`
+/// webhooks, which are sent one raw payload per alert instead.
+pub fn summary_message(format: WebhookFormat, summary: &UploadSummary, url: &str) -> Option<Value> {
+    match format {
+        WebhookFormat::Json => None,
+        WebhookFormat::Slack => Some(slack_summary(summary, url)),
+        WebhookFormat::Teams => Some(teams_summary(summary, url)),
+    }
+}
+
+/// A short message confirming a chat webhook works, or None for `JSON` webhooks.
+pub fn test_message(format: WebhookFormat, project: &Project, url: &str) -> Option<Value> {
+    let text = format!("Test message from DriftGuard for project {}", project.name);
+    match format {
+        WebhookFormat::Json => None,
+        WebhookFormat::Slack => Some(json!({
+            "text": escape_mrkdwn(&text),
+            "blocks": [
+                {"type": "section", "text": {"type": "mrkdwn", "text": escape_mrkdwn(&text)}},
+                slack_button(url),
+            ],
+        })),
+        WebhookFormat::Teams => Some(teams_card(
+            vec![json!({"type": "TextBlock", "text": text, "wrap": true})],
+            url,
+        )),
+    }
+}
+
+fn title(summary: &UploadSummary) -> String {
+    format!(
+        "DriftGuard: {} is {}",
+        summary.project.name, summary.upload.health
+    )
+}
+
+/// The features with the highest drift scores, highest first.
+fn top_features(features: &[EvaluationFeature]) -> Vec<&EvaluationFeature> {
+    let mut top: Vec<&EvaluationFeature> = features.iter().collect();
+    top.sort_by(|a, b| b.drift_score.total_cmp(&a.drift_score));
+    top.truncate(TOP_FEATURES);
+    top
+}
+
+/// Alerts to list, most severe first, and how many were left out.
+fn listed_alerts(alerts: &[Alert]) -> (Vec<&Alert>, usize) {
+    let mut listed: Vec<&Alert> = alerts.iter().collect();
+    listed.sort_by_key(|alert| std::cmp::Reverse(severity_rank(&alert.severity)));
+    let omitted = listed.len().saturating_sub(MAX_ALERTS);
+    listed.truncate(MAX_ALERTS);
+    (listed, omitted)
+}
+
+fn alert_line(alert: &Alert) -> String {
+    format!("{} {}: {}", alert.severity, alert.alert_type, alert.message)
+}
+
+fn facts(summary: &UploadSummary) -> Vec<(&'static str, String)> {
+    vec![
+        ("Health", summary.upload.health.clone()),
+        ("Rows", summary.upload.rows_processed.to_string()),
+        ("Alerts created", summary.upload.alerts_created.to_string()),
+        (
+            "Alerts resolved",
+            summary.upload.alerts_resolved.to_string(),
+        ),
+        (
+            "Drift metric",
+            summary.upload.drift_metric.as_str().to_string(),
+        ),
+    ]
+}
+
+/// Slack treats `&`, `<` and `>` as control characters in mrkdwn text.
+fn escape_mrkdwn(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+fn slack_button(url: &str) -> Value {
+    json!({
+        "type": "actions",
+        "elements": [{
+            "type": "button",
+            "text": {"type": "plain_text", "text": "View alerts"},
+            "url": url,
+        }],
+    })
+}
+
+/// A Slack Block Kit message, with `text` as the notification fallback.
+fn slack_summary(summary: &UploadSummary, url: &str) -> Value {
+    let title = title(summary);
+    let fields: Vec<Value> = facts(summary)
+        .into_iter()
+        .map(|(name, value)| json!({"type": "mrkdwn", "text": format!("*{}*\n{}", name, value)}))
+        .collect();
+
+    let mut blocks = vec![
+        json!({"type": "header", "text": {"type": "plain_text", "text": title}}),
+        json!({"type": "section", "fields": fields}),
+    ];
+
+    let features = top_features(summary.features);
+    if !features.is_empty() {
+        let lines: Vec<String> = features
+            .iter()
+            .map(|f| {
+                format!(
+                    "• `{}`: {:.3}",
+                    escape_mrkdwn(&f.feature_name),
+                    f.drift_score
+                )
+            })
+            .collect();
+        blocks.push(json!({
+            "type": "section",
+            "text": {"type": "mrkdwn", "text": format!("*Top drifted features*\n{}", lines.join("\n"))},
+        }));
+    }
+
+    let (alerts, omitted) = listed_alerts(summary.alerts);
+    if !alerts.is_empty() {
+        let mut lines: Vec<String> = alerts
+            .iter()
+            .map(|alert| {
+                let icon = if alert.severity == "CRITICAL" {
+                    ":red_circle:"
+                } else {
+                    ":warning:"
+                };
+                format!("{} {}", icon, escape_mrkdwn(&alert_line(alert)))
+            })
+            .collect();
+        if omitted > 0 {
+            lines.push(format!("…and {} more", omitted));
+        }
+        blocks.push(json!({
+            "type": "section",
+            "text": {"type": "mrkdwn", "text": format!("*Alerts*\n{}", lines.join("\n"))},
+        }));
+    }
+
+    blocks.push(slack_button(url));
+
+    json!({"text": escape_mrkdwn(&title), "blocks": blocks})
+}
+
+/// Wraps Adaptive Card body elements in the message envelope Teams webhooks accept.
+fn teams_card(body: Vec<Value>, url: &str) -> Value {
+    json!({
+        "type": "message",
+        "attachments": [{
+            "contentType": "application/vnd.microsoft.card.adaptive",
+            "content": {
+                "$schema": "http://adaptivecards.io/schemas/adaptive-card.json",
+                "type": "AdaptiveCard",
+                "version": "1.4",
+                "body": body,
+                "actions": [{"type": "Action.OpenUrl", "title": "View alerts", "url": url}],
+            },
+        }],
+    })
+}
+
+fn teams_summary(summary: &UploadSummary, url: &str) -> Value {
+    let color = match summary.upload.health.as_str() {
+        "CRITICAL" => "attention",
+        "WARN" => "warning",
+        _ => "good",
+    };
+    let facts: Vec<Value> = facts(summary)
+        .into_iter()
+        .map(|(name, value)| json!({"title": name, "value": value}))
+        .collect();
+
+    let mut body = vec![
+        json!({
+            "type": "TextBlock",
+            "text": title(summary),
+            "size": "Large",
+            "weight": "Bolder",
+            "color": color,
+            "wrap": true,
+        }),
+        json!({"type": "FactSet", "facts": facts}),
+    ];
+
+    let features = top_features(summary.features);
+    if !features.is_empty() {
+        let facts: Vec<Value> = features
+            .iter()
+            .map(|f| json!({"title": f.feature_name, "value": format!("{:.3}", f.drift_score)}))
+            .collect();
+        body.push(json!({"type": "TextBlock", "text": "Top drifted features", "weight": "Bolder"}));
+        body.push(json!({"type": "FactSet", "facts": facts}));
+    }
+
+    let (alerts, omitted) = listed_alerts(summary.alerts);
+    if !alerts.is_empty() {
+        body.push(json!({"type": "TextBlock", "text": "Alerts", "weight": "Bolder"}));
+        for alert in alerts {
+            body.push(json!({
+                "type": "TextBlock",
+                "text": alert_line(alert),
+                "color": if alert.severity == "CRITICAL" { "attention" } else { "warning" },
+                "wrap": true,
+                "spacing": "Small",
+            }));
+        }
+        if omitted > 0 {
+            body.push(json!({
+                "type": "TextBlock",
+                "text": format!("…and {} more", omitted),
+                "isSubtle": true,
+                "spacing": "Small",
+            }));
+        }
+    }
+
+    teams_card(body, url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::DriftMetric;
+    use chrono::Utc;
+
+    fn project() -> Project {
+        Project {
+            project_id: "p1".to_string(),
+            name: "Credit <model>".to_string(),
+            created_at: Utc::now(),
+        }
+    }
+
+    fn upload() -> UploadIncomingResponse {
+        UploadIncomingResponse {
+            evaluation_id: "e1".to_string(),
+            rows_processed: 20,
+            alerts_created: 12,
+            alerts_resolved: 1,
+            incident_id: None,
+            health: "CRITICAL".to_string(),
+            drift_metric: DriftMetric::L1,
+            window: None,
+            windows: Vec::new(),
+            skipped_timestamps: 0,
+        }
+    }
+
+    fn feature(name: &str, drift_score: f64) -> EvaluationFeature {
+        EvaluationFeature {
+            feature_name: name.to_string(),
+            drift_score,
+            sample_size: 20,
+            p_value: None,
+            null_rate: None,
+            unexpected_fraction: None,
+        }
+    }
+
+    fn alert(severity: &str, feature_name: &str) -> Alert {
+        Alert {
+            alert_id: format!("a-{}", feature_name),
+            project_id: "p1".to_string(),
+            baseline_version: 1,
+            created_at: Utc::now(),
+            severity: severity.to_string(),
+            alert_type: "FEATURE_DRIFT".to_string(),
+            feature_name: Some(feature_name.to_string()),
+            metric_value: Some(0.3),
+            message: format!("Feature '{}' drifted", feature_name),
+            status: "OPEN".to_string(),
+            drift_metric: None,
+            rule_name: None,
+            occurrence_count: 1,
+            last_seen_at: Utc::now(),
+            acked_at: None,
+            resolved_at: None,
+            resolved_by: None,
+            resolution_note: None,
+            snoozed_until: None,
+            assignee: None,
+            incident_id: None,
+        }
+    }
+
+    #[test]
+    fn test_json_webhooks_get_no_chat_message() {
+        let (project, upload) = (project(), upload());
+        let summary = UploadSummary {
+            project: &project,
+            upload: &upload,
+            features: &[],
+            alerts: &[],
+        };
+        assert!(summary_message(WebhookFormat::Json, &summary, "http://x").is_none());
+        assert!(test_message(WebhookFormat::Json, &project, "http://x").is_none());
+    }
+
+    #[test]
+    fn test_slack_summary_lists_top_features_and_most_severe_alerts() {
+        let (project, upload) = (project(), upload());
+        let features: Vec<EvaluationFeature> = (0..7)
+            .map(|i| feature(&format!("f{}", i), i as f64 / 10.0))
+            .collect();
+        let mut alerts: Vec<Alert> = (0..11).map(|i| alert("WARN", &format!("w{}", i))).collect();
+        alerts.push(alert("CRITICAL", "income"));
+        let summary = UploadSummary {
+            project: &project,
+            upload: &upload,
+            features: &features,
+            alerts: &alerts,
+        };
+
+        let message = summary_message(WebhookFormat::Slack, &summary, "http://app/p1").unwrap();
+        assert_eq!(
+            message["text"],
+            "DriftGuard: Credit &lt;model&gt; is CRITICAL"
+        );
+        let blocks = message["blocks"].as_array().unwrap();
+        assert_eq!(blocks[0]["type"], "header");
+        assert_eq!(blocks[1]["fields"][0]["text"], "*Health*\nCRITICAL");
+
+        let features_text = blocks[2]["text"]["text"].as_str().unwrap();
+        assert!(features_text.starts_with("*Top drifted features*\n• `f6`: 0.600"));
+        assert_eq!(features_text.lines().count(), 1 + TOP_FEATURES);
+
+        let alerts_text = blocks[3]["text"]["text"].as_str().unwrap();
+        let lines: Vec<&str> = alerts_text.lines().collect();
+        assert_eq!(
+            lines[1],
+            ":red_circle: CRITICAL FEATURE_DRIFT: Feature 'income' drifted"
+        );
+        assert_eq!(lines.last().unwrap(), &"…and 2 more");
+        assert_eq!(blocks[4]["elements"][0]["url"], "http://app/p1");
+    }
+
+    #[test]
+    fn test_teams_summary_is_an_adaptive_card() {
+        let (project, upload) = (project(), upload());
+        let features = vec![feature("income", 0.8)];
+        let alerts = vec![alert("CRITICAL", "income")];
+        let summary = UploadSummary {
+            project: &project,
+            upload: &upload,
+            features: &features,
+            alerts: &alerts,
+        };
+
+        let message = summary_message(WebhookFormat::Teams, &summary, "http://app/p1").unwrap();
+        assert_eq!(message["type"], "message");
+        let card = &message["attachments"][0]["content"];
+        assert_eq!(card["type"], "AdaptiveCard");
+        assert_eq!(card["body"][0]["color"], "attention");
+        assert_eq!(card["body"][1]["facts"][2]["value"], "12");
+        assert_eq!(card["body"][3]["facts"][0]["title"], "income");
+        assert_eq!(card["body"][3]["facts"][0]["value"], "0.800");
+        assert_eq!(card["actions"][0]["url"], "http://app/p1");
+    }
+
+    #[test]
+    fn test_slack_text_is_escaped() {
+        let message = test_message(WebhookFormat::Slack, &project(), "http://x").unwrap();
+        assert_eq!(
+            message["blocks"][0]["text"]["text"],
+            "Test message from DriftGuard for project Credit &lt;model&gt;"
+        );
+    }
+}
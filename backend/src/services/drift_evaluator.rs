@@ -1,22 +1,52 @@
-use crate::models::{FeatureStats, FeatureStatsData, FeatureType};
-use crate::storage::baselines;
+use crate::models::{
+    CalibrationStats, DriftMetric, FeatureDrift, FeatureStats, FeatureStatsData, FeatureType,
+    NumericStats, TimeWindow, WindowDrift,
+};
+use crate::storage::{baselines, settings};
 use crate::utils::{
-    compute_feature_drift, compute_prediction_rate, compute_prediction_shift, parse_csv,
+    bucket_by_window, compute_calibration, compute_prediction_rate, compute_prediction_shift,
+    evaluate_feature_drift, parse_csv,
 };
 use sqlx::SqlitePool;
 use std::collections::HashMap;
 use std::io::Read;
 
 pub struct DriftResult {
-    pub feature_drifts: HashMap<String, f64>,
+    pub feature_drifts: HashMap<String, FeatureDrift>,
     pub prediction_shift: f64,
     pub incoming_prediction_rate: f64,
+    pub row_count: usize,
+    pub drift_metric: DriftMetric,
+    pub confidence_drift: Option<FeatureDrift>, // None without a baseline or incoming confidence column
+    pub calibration: Option<CalibrationStats>,  // None unless the incoming data is labelled
+    pub baseline_calibration: Option<CalibrationStats>,
+    pub windows: Vec<WindowDrift>, // empty unless a window was requested
+    pub skipped_timestamps: usize,
+}
+
+fn compute_feature_drifts(
+    baseline_stats_map: &HashMap<String, FeatureStats>,
+    records: &[HashMap<String, String>],
+    drift_metric: DriftMetric,
+) -> HashMap<String, FeatureDrift> {
+    let mut feature_drifts = HashMap::new();
+    for (feature_name, baseline_stat) in baseline_stats_map {
+        let incoming_values: Vec<String> = records
+            .iter()
+            .filter_map(|r| r.get(feature_name).cloned())
+            .collect();
+
+        let drift = evaluate_feature_drift(baseline_stat, &incoming_values, drift_metric);
+        feature_drifts.insert(feature_name.clone(), drift);
+    }
+    feature_drifts
 }
 
 pub async fn evaluate_drift<R: Read>(
     pool: &SqlitePool,
     project_id: &str,
     csv_reader: R,
+    window: Option<TimeWindow>,
 ) -> anyhow::Result<DriftResult> {
     // Get latest baseline
     let baseline = baselines::get_latest_baseline(pool, project_id)
@@ -24,9 +54,12 @@ pub async fn evaluate_drift<R: Read>(
         .ok_or_else(|| anyhow::anyhow!("No baseline found for project"))?;
 
     let baseline_features = baselines::get_baseline_features(pool, &baseline.baseline_id).await?;
+    let drift_metric = settings::get_project_settings(pool, project_id)
+        .await?
+        .drift_metric;
 
     // Parse incoming CSV
-    let (incoming_records, _incoming_headers) = parse_csv(csv_reader)?;
+    let (incoming_records, incoming_headers) = parse_csv(csv_reader)?;
 
     if incoming_records.is_empty() {
         return Err(anyhow::anyhow!("Incoming CSV is empty"));
@@ -35,70 +68,94 @@ pub async fn evaluate_drift<R: Read>(
     // Reconstruct baseline feature stats
     let baseline_stats_map: HashMap<String, FeatureStats> = baseline_features
         .iter()
-        .filter_map(|bf| {
-            let metadata: serde_json::Value = serde_json::from_str(&bf.metadata).ok()?;
-            let stats = match bf.feature_type.as_str() {
-                "numeric" => {
-                    let bins = metadata["bins"]
-                        .as_array()?
-                        .iter()
-                        .filter_map(|v| v.as_f64())
-                        .collect();
-                    let probabilities = metadata["probabilities"]
-                        .as_array()?
-                        .iter()
-                        .filter_map(|v| v.as_f64())
-                        .collect();
-                    FeatureStatsData::Numeric(crate::models::NumericStats {
-                        bins,
-                        probabilities,
-                    })
-                }
-                "categorical" => {
-                    let frequencies: HashMap<String, f64> = metadata["frequencies"]
-                        .as_object()?
-                        .iter()
-                        .filter_map(|(k, v)| Some((k.clone(), v.as_f64()?)))
-                        .collect();
-                    FeatureStatsData::Categorical(crate::models::CategoricalStats { frequencies })
-                }
-                _ => return None,
-            };
-
-            Some((
-                bf.feature_name.clone(),
-                FeatureStats {
-                    name: bf.feature_name.clone(),
-                    feature_type: match bf.feature_type.as_str() {
-                        "numeric" => FeatureType::Numeric,
-                        _ => FeatureType::Categorical,
-                    },
-                    stats,
-                },
-            ))
-        })
+        .filter_map(FeatureStats::from_baseline_feature)
+        .map(|fs| (fs.name.clone(), fs))
         .collect();
 
     // Compute feature drifts
-    let mut feature_drifts = HashMap::new();
-    for (feature_name, baseline_stat) in &baseline_stats_map {
-        let incoming_values: Vec<String> = incoming_records
-            .iter()
-            .filter_map(|r| r.get(feature_name).cloned())
-            .collect();
-
-        let drift = compute_feature_drift(baseline_stat, &incoming_values);
-        feature_drifts.insert(feature_name.clone(), drift);
-    }
+    let feature_drifts =
+        compute_feature_drifts(&baseline_stats_map, &incoming_records, drift_metric);
 
     // Compute prediction shift
     let incoming_prediction_rate = compute_prediction_rate(&incoming_records);
     let prediction_shift =
         compute_prediction_shift(baseline.prediction_rate, incoming_prediction_rate);
 
+    // Confidence drift, treated like a numeric feature on fixed [0, 1] bins
+    let baseline_confidence: Option<NumericStats> = baseline
+        .confidence_stats
+        .as_deref()
+        .and_then(|json| serde_json::from_str(json).ok());
+    let confidence_drift = match baseline_confidence {
+        Some(stats) if incoming_headers.iter().any(|h| h == "confidence") => {
+            let confidence_stats = FeatureStats {
+                name: "confidence".to_string(),
+                feature_type: FeatureType::Numeric,
+                stats: FeatureStatsData::Numeric(stats),
+            };
+            let incoming_values: Vec<String> = incoming_records
+                .iter()
+                .filter_map(|r| r.get("confidence").cloned())
+                .collect();
+            Some(evaluate_feature_drift(
+                &confidence_stats,
+                &incoming_values,
+                drift_metric,
+            ))
+        }
+        _ => None,
+    };
+
+    let baseline_calibration: Option<CalibrationStats> = baseline
+        .calibration
+        .as_deref()
+        .and_then(|json| serde_json::from_str(json).ok());
+
+    // Per-window drift, so drift inside a long backfill can be located in time
+    let (windows, skipped_timestamps) = match window {
+        Some(window) => {
+            if !incoming_headers.iter().any(|h| h == "timestamp") {
+                return Err(anyhow::anyhow!(
+                    "Windowed evaluation requires a timestamp column"
+                ));
+            }
+            let (buckets, skipped) = bucket_by_window(&incoming_records, window);
+            let windows = buckets
+                .into_iter()
+                .map(|(window_start, records)| {
+                    let prediction_rate = compute_prediction_rate(&records);
+                    let feature_scores =
+                        compute_feature_drifts(&baseline_stats_map, &records, drift_metric)
+                            .into_iter()
+                            .map(|(name, drift)| (name, drift.score))
+                            .collect();
+                    WindowDrift {
+                        window_start,
+                        row_count: records.len(),
+                        prediction_rate,
+                        prediction_shift: compute_prediction_shift(
+                            baseline.prediction_rate,
+                            prediction_rate,
+                        ),
+                        feature_scores,
+                    }
+                })
+                .collect();
+            (windows, skipped)
+        }
+        None => (Vec::new(), 0),
+    };
+
     Ok(DriftResult {
         feature_drifts,
         prediction_shift,
         incoming_prediction_rate,
+        row_count: incoming_records.len(),
+        drift_metric,
+        confidence_drift,
+        calibration: compute_calibration(&incoming_records),
+        baseline_calibration,
+        windows,
+        skipped_timestamps,
     })
 }
@@ -0,0 +1,453 @@
+use crate::models::{
+    severity_rank, Alert, AlertFilter, AlertNotification, DigestResponse, NotificationEvent,
+    NotificationSettings, Project,
+};
+use crate::services::chat_messages;
+use crate::storage::{alerts, notifications, projects};
+use chrono::{DateTime, Timelike, Utc};
+use lettre::message::header::ContentType;
+use lettre::message::Mailbox;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use sqlx::SqlitePool;
+use std::env;
+use std::time::Duration;
+
+const SMTP_TIMEOUT: Duration = Duration::from_secs(10);
+const DIGEST_CHECK_INTERVAL: Duration = Duration::from_secs(300);
+
+/// How the SMTP connection is secured.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SmtpSecurity {
+    None,     // plain text, e.g. a local SMTP sink
+    StartTls, // upgrade a plain connection, usually port 587
+    Tls,      // implicit TLS, usually port 465
+}
+
+impl SmtpSecurity {
+    fn default_port(&self) -> u16 {
+        match self {
+            SmtpSecurity::None => 25,
+            SmtpSecurity::StartTls => 587,
+            SmtpSecurity::Tls => 465,
+        }
+    }
+}
+
+impl std::str::FromStr for SmtpSecurity {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "none" => Ok(SmtpSecurity::None),
+            "starttls" => Ok(SmtpSecurity::StartTls),
+            "tls" => Ok(SmtpSecurity::Tls),
+            other => Err(anyhow::anyhow!("Unknown SMTP security: {}", other)),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SmtpConfig {
+    pub host: String,
+    pub port: u16,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub from: String,
+    pub security: SmtpSecurity,
+}
+
+impl SmtpConfig {
+    /// Reads `SMTP_HOST`, `SMTP_PORT`, `SMTP_SECURITY`, `SMTP_USERNAME`,
+    /// `SMTP_PASSWORD` and `SMTP_FROM`. Email is off (None) unless `SMTP_HOST` is set.
+    pub fn from_env() -> anyhow::Result<Option<Self>> {
+        let Ok(host) = env::var("SMTP_HOST") else {
+            return Ok(None);
+        };
+        let security: SmtpSecurity = env::var("SMTP_SECURITY")
+            .unwrap_or_else(|_| "starttls".to_string())
+            .parse()?;
+        let port = match env::var("SMTP_PORT") {
+            Ok(port) => port
+                .parse()
+                .map_err(|e| anyhow::anyhow!("Invalid SMTP_PORT: {}", e))?,
+            Err(_) => security.default_port(),
+        };
+
+        Ok(Some(SmtpConfig {
+            host,
+            port,
+            username: env::var("SMTP_USERNAME").ok(),
+            password: env::var("SMTP_PASSWORD").ok(),
+            from: env::var("SMTP_FROM").unwrap_or_else(|_| "driftguard@localhost".to_string()),
+            security,
+        }))
+    }
+}
+
+#[derive(Clone)]
+pub struct Mailer {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: Mailbox,
+}
+
+impl Mailer {
+    pub fn new(config: &SmtpConfig) -> anyhow::Result<Self> {
+        let builder = match config.security {
+            SmtpSecurity::None => {
+                AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&config.host)
+            }
+            SmtpSecurity::StartTls => {
+                AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&config.host)?
+            }
+            SmtpSecurity::Tls => AsyncSmtpTransport::<Tokio1Executor>::relay(&config.host)?,
+        };
+        let mut builder = builder.port(config.port).timeout(Some(SMTP_TIMEOUT));
+        if let Some(username) = &config.username {
+            builder = builder.credentials(Credentials::new(
+                username.clone(),
+                config.password.clone().unwrap_or_default(),
+            ));
+        }
+
+        Ok(Mailer {
+            transport: builder.build(),
+            from: config
+                .from
+                .parse()
+                .map_err(|e| anyhow::anyhow!("Invalid SMTP_FROM: {}", e))?,
+        })
+    }
+
+    /// Mailer for the `SMTP_*` environment, or None when email is not configured.
+    pub fn from_env() -> anyhow::Result<Option<Self>> {
+        SmtpConfig::from_env()?
+            .map(|config| Mailer::new(&config))
+            .transpose()
+    }
+
+    /// Sends one plain-text mail addressed to all recipients.
+    pub async fn send(&self, to: &[String], subject: &str, body: String) -> anyhow::Result<()> {
+        let mut message = Message::builder().from(self.from.clone());
+        for recipient in to {
+            message = message.to(recipient.parse()?);
+        }
+        let message = message
+            .subject(subject)
+            .header(ContentType::TEXT_PLAIN)
+            .body(body)?;
+        self.transport.send(message).await?;
+        Ok(())
+    }
+}
+
+pub fn is_valid_address(address: &str) -> bool {
+    address.parse::<lettre::Address>().is_ok()
+}
+
+fn alert_details(alert: &Alert) -> String {
+    let mut details = format!("[{}] {}", alert.severity, alert.alert_type);
+    if let Some(feature_name) = &alert.feature_name {
+        details.push_str(&format!(" ({})", feature_name));
+    }
+    details
+}
+
+/// Subject and body of the mail sent as soon as an alert becomes CRITICAL.
+pub fn alert_email(
+    event: NotificationEvent,
+    alert: &Alert,
+    project: &Project,
+    url: &str,
+) -> (String, String) {
+    let what = match event {
+        NotificationEvent::AlertEscalated => "Alert escalated to CRITICAL",
+        _ => "New CRITICAL alert",
+    };
+    let subject = format!(
+        "[DriftGuard] {} in {}: {}",
+        what,
+        project.name,
+        alert_details(alert)
+    );
+
+    let mut body = format!("{}\n\n", alert.message);
+    body.push_str(&format!("Project: {}\n", project.name));
+    body.push_str(&format!("Alert: {}\n", alert_details(alert)));
+    if let Some(value) = alert.metric_value {
+        body.push_str(&format!("Value: {:.4}\n", value));
+    }
+    body.push_str(&format!("Raised: {}\n", alert.created_at.to_rfc3339()));
+    body.push_str(&format!("Occurrences: {}\n", alert.occurrence_count));
+    body.push_str(&format!("\nView alerts: {}\n", url));
+
+    (subject, body)
+}
+
+/// Subject and body of a project's daily digest of open alerts.
+pub fn digest_email(
+    project: &Project,
+    open_alerts: &[Alert],
+    url: &str,
+    now: DateTime<Utc>,
+) -> (String, String) {
+    let critical = open_alerts
+        .iter()
+        .filter(|alert| alert.severity == "CRITICAL")
+        .count();
+    let subject = format!(
+        "[DriftGuard] Daily digest for {}: {} open alert{}",
+        project.name,
+        open_alerts.len(),
+        if open_alerts.len() == 1 { "" } else { "s" }
+    );
+
+    let mut listed: Vec<&Alert> = open_alerts.iter().collect();
+    listed.sort_by(|a, b| {
+        severity_rank(&b.severity)
+            .cmp(&severity_rank(&a.severity))
+            .then(b.last_seen_at.cmp(&a.last_seen_at))
+    });
+
+    let mut body = format!(
+        "{} has {} open alert{} ({} CRITICAL, {} WARN) as of {}.\n\n",
+        project.name,
+        open_alerts.len(),
+        if open_alerts.len() == 1 { "" } else { "s" },
+        critical,
+        open_alerts.len() - critical,
+        now.format("%Y-%m-%d %H:%M UTC")
+    );
+    for alert in listed {
+        body.push_str(&format!("- {}: {}\n", alert_details(alert), alert.message));
+        body.push_str(&format!(
+            "  seen {} time{}, last at {}\n",
+            alert.occurrence_count,
+            if alert.occurrence_count == 1 { "" } else { "s" },
+            alert.last_seen_at.format("%Y-%m-%d %H:%M UTC")
+        ));
+    }
+    body.push_str(&format!("\nView alerts: {}\n", url));
+
+    (subject, body)
+}
+
+/// A digest is due once a day, after the project's digest hour.
+pub fn digest_due(settings: &NotificationSettings, now: DateTime<Utc>) -> bool {
+    settings.email_digest
+        && !settings.email_recipients.is_empty()
+        && i64::from(now.hour()) >= settings.digest_hour
+        && settings
+            .last_digest_at
+            .is_none_or(|last| last.date_naive() < now.date_naive())
+}
+
+/// Mails new and escalated CRITICAL alerts to the project's recipients, unless
+/// immediate mails are switched off.
+pub async fn notify_alerts(
+    pool: &SqlitePool,
+    mailer: &Mailer,
+    project_id: &str,
+    notifications: &[AlertNotification],
+) -> anyhow::Result<()> {
+    let settings = notifications::get_notification_settings(pool, project_id).await?;
+    if !settings.email_immediate || settings.email_recipients.is_empty() {
+        return Ok(());
+    }
+    let project = projects::get_project(pool, project_id)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("Project not found: {}", project_id))?;
+    let url = chat_messages::project_alerts_url(project_id);
+
+    for notification in notifications {
+        let Some(alert) = alerts::get_alert(pool, project_id, &notification.alert_id).await? else {
+            continue;
+        };
+        if alert.severity != "CRITICAL" {
+            continue;
+        }
+        let (subject, body) = alert_email(notification.event, &alert, &project, &url);
+        mailer
+            .send(&settings.email_recipients, &subject, body)
+            .await?;
+    }
+
+    Ok(())
+}
+
+/// Mails the project's open alerts to its recipients and records the digest as
+/// sent. Nothing is mailed when no alerts are open.
+pub async fn send_digest(
+    pool: &SqlitePool,
+    mailer: &Mailer,
+    project_id: &str,
+    now: DateTime<Utc>,
+) -> anyhow::Result<DigestResponse> {
+    let settings = notifications::get_notification_settings(pool, project_id).await?;
+    let project = projects::get_project(pool, project_id)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("Project not found: {}", project_id))?;
+    let open_alerts = alerts::list_alerts(
+        pool,
+        project_id,
+        &AlertFilter {
+            status: Some("OPEN".to_string()),
+            ..AlertFilter::default()
+        },
+    )
+    .await?;
+
+    let sent = !open_alerts.is_empty() && !settings.email_recipients.is_empty();
+    if sent {
+        let url = chat_messages::project_alerts_url(project_id);
+        let (subject, body) = digest_email(&project, &open_alerts, &url, now);
+        mailer
+            .send(&settings.email_recipients, &subject, body)
+            .await?;
+    }
+    notifications::set_last_digest_at(pool, project_id, now).await?;
+
+    Ok(DigestResponse {
+        sent,
+        recipients: settings.email_recipients.len(),
+        open_alerts: open_alerts.len(),
+    })
+}
+
+/// Sends every digest that is due. A failure is logged and retried on the next run.
+pub async fn send_due_digests(
+    pool: &SqlitePool,
+    mailer: &Mailer,
+    now: DateTime<Utc>,
+) -> anyhow::Result<usize> {
+    let mut sent = 0;
+    for project_id in notifications::list_digest_projects(pool).await? {
+        let settings = notifications::get_notification_settings(pool, &project_id).await?;
+        if !digest_due(&settings, now) {
+            continue;
+        }
+        match send_digest(pool, mailer, &project_id, now).await {
+            Ok(digest) if digest.sent => sent += 1,
+            Ok(_) => {}
+            Err(e) => log::error!("Failed to send digest for project {}: {}", project_id, e),
+        }
+    }
+    Ok(sent)
+}
+
+/// Checks for due digests every few minutes, for as long as the server runs.
+pub async fn run_digests(pool: SqlitePool, mailer: Mailer) {
+    let mut interval = tokio::time::interval(DIGEST_CHECK_INTERVAL);
+    loop {
+        interval.tick().await;
+        if let Err(e) = send_due_digests(&pool, &mailer, Utc::now()).await {
+            log::error!("Failed to send digests: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn alert(severity: &str, feature_name: Option<&str>, occurrence_count: i64) -> Alert {
+        Alert {
+            alert_id: "a1".to_string(),
+            project_id: "p1".to_string(),
+            baseline_version: 1,
+            created_at: Utc.with_ymd_and_hms(2024, 3, 1, 9, 0, 0).unwrap(),
+            severity: severity.to_string(),
+            alert_type: "FEATURE_DRIFT".to_string(),
+            feature_name: feature_name.map(str::to_string),
+            metric_value: Some(0.25),
+            message: "Drift detected".to_string(),
+            status: "OPEN".to_string(),
+            drift_metric: None,
+            rule_name: None,
+            occurrence_count,
+            last_seen_at: Utc.with_ymd_and_hms(2024, 3, 1, 10, 0, 0).unwrap(),
+            acked_at: None,
+            resolved_at: None,
+            resolved_by: None,
+            resolution_note: None,
+            snoozed_until: None,
+            assignee: None,
+            incident_id: None,
+        }
+    }
+
+    fn project() -> Project {
+        Project {
+            project_id: "p1".to_string(),
+            name: "Credit".to_string(),
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_alert_email_names_the_alert() {
+        let (subject, body) = alert_email(
+            NotificationEvent::AlertEscalated,
+            &alert("CRITICAL", Some("income"), 3),
+            &project(),
+            "http://app/p1",
+        );
+        assert_eq!(
+            subject,
+            "[DriftGuard] Alert escalated to CRITICAL in Credit: [CRITICAL] FEATURE_DRIFT (income)"
+        );
+        assert!(body.starts_with("Drift detected\n\n"));
+        assert!(body.contains("Value: 0.2500\n"));
+        assert!(body.contains("Occurrences: 3\n"));
+        assert!(body.ends_with("View alerts: http://app/p1\n"));
+    }
+
+    #[test]
+    fn test_digest_lists_most_severe_alerts_first() {
+        let alerts = vec![alert("WARN", Some("age"), 1), alert("CRITICAL", None, 2)];
+        let now = Utc.with_ymd_and_hms(2024, 3, 2, 8, 0, 0).unwrap();
+        let (subject, body) = digest_email(&project(), &alerts, "http://app/p1", now);
+        assert_eq!(
+            subject,
+            "[DriftGuard] Daily digest for Credit: 2 open alerts"
+        );
+        let lines: Vec<&str> = body.lines().collect();
+        assert_eq!(
+            lines[0],
+            "Credit has 2 open alerts (1 CRITICAL, 1 WARN) as of 2024-03-02 08:00 UTC."
+        );
+        assert_eq!(lines[2], "- [CRITICAL] FEATURE_DRIFT: Drift detected");
+        assert_eq!(lines[3], "  seen 2 times, last at 2024-03-01 10:00 UTC");
+        assert_eq!(lines[4], "- [WARN] FEATURE_DRIFT (age): Drift detected");
+    }
+
+    #[test]
+    fn test_digest_is_due_once_a_day_after_the_digest_hour() {
+        let mut settings = NotificationSettings::defaults("p1");
+        settings.email_recipients = vec!["ops@example.com".to_string()];
+        let morning = Utc.with_ymd_and_hms(2024, 3, 2, 7, 59, 0).unwrap();
+        let later = Utc.with_ymd_and_hms(2024, 3, 2, 9, 0, 0).unwrap();
+        assert!(!digest_due(&settings, morning));
+        assert!(digest_due(&settings, later));
+
+        settings.last_digest_at = Some(Utc.with_ymd_and_hms(2024, 3, 2, 8, 5, 0).unwrap());
+        assert!(!digest_due(&settings, later));
+        settings.last_digest_at = Some(Utc.with_ymd_and_hms(2024, 3, 1, 8, 5, 0).unwrap());
+        assert!(digest_due(&settings, later));
+
+        settings.email_digest = false;
+        assert!(!digest_due(&settings, later));
+    }
+
+    #[test]
+    fn test_smtp_security_defaults_its_port() {
+        assert_eq!("none".parse::<SmtpSecurity>().unwrap().default_port(), 25);
+        assert_eq!(
+            "starttls".parse::<SmtpSecurity>().unwrap().default_port(),
+            587
+        );
+        assert_eq!("tls".parse::<SmtpSecurity>().unwrap().default_port(), 465);
+        assert!("ssl".parse::<SmtpSecurity>().is_err());
+    }
+}
@@ -0,0 +1,238 @@
+use crate::storage::alerts;
+use actix_web::web::Bytes;
+use futures_util::Stream;
+use serde::Serialize;
+use sqlx::SqlitePool;
+use std::convert::Infallible;
+use std::time::Duration;
+use tokio::sync::broadcast::{self, error::RecvError};
+
+// Subscribers that fall further behind than this miss events and are told so
+const CHANNEL_CAPACITY: usize = 256;
+
+pub const KEEPALIVE_INTERVAL: Duration = Duration::from_secs(15);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LiveEventKind {
+    AlertCreated,
+    AlertUpdated,
+    EvaluationCompleted,
+}
+
+impl LiveEventKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LiveEventKind::AlertCreated => "alert.created",
+            LiveEventKind::AlertUpdated => "alert.updated",
+            LiveEventKind::EvaluationCompleted => "evaluation.completed",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LiveEvent {
+    pub project_id: String,
+    pub kind: LiveEventKind,
+    pub data: serde_json::Value,
+}
+
+impl LiveEvent {
+    /// The event as a Server-Sent Events message; `data` is JSON on a single line.
+    pub fn to_sse(&self) -> String {
+        format!("event: {}\ndata: {}\n\n", self.kind.as_str(), self.data)
+    }
+}
+
+/// In-process broadcast of alert and evaluation events, shared by the alert engine,
+/// the upload route and the alert streams.
+#[derive(Debug, Clone)]
+pub struct LiveEvents {
+    sender: broadcast::Sender<LiveEvent>,
+}
+
+impl Default for LiveEvents {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LiveEvents {
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(CHANNEL_CAPACITY);
+        LiveEvents { sender }
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<LiveEvent> {
+        self.sender.subscribe()
+    }
+
+    pub fn has_subscribers(&self) -> bool {
+        self.sender.receiver_count() > 0
+    }
+
+    /// Sends an event to the current subscribers; with none it is dropped.
+    pub fn publish<T: Serialize>(&self, project_id: &str, kind: LiveEventKind, data: &T) {
+        if !self.has_subscribers() {
+            return;
+        }
+        match serde_json::to_value(data) {
+            Ok(data) => {
+                let _ = self.sender.send(LiveEvent {
+                    project_id: project_id.to_string(),
+                    kind,
+                    data,
+                });
+            }
+            Err(e) => log::error!("Failed to serialize {} event: {}", kind.as_str(), e),
+        }
+    }
+
+    /// Loads the alerts and publishes each as it now stands. Skipped without subscribers.
+    pub async fn publish_alerts(
+        &self,
+        pool: &SqlitePool,
+        project_id: &str,
+        changes: &[(LiveEventKind, String)],
+    ) -> anyhow::Result<()> {
+        if !self.has_subscribers() {
+            return Ok(());
+        }
+        for (kind, alert_id) in changes {
+            if let Some(alert) = alerts::get_alert(pool, project_id, alert_id).await? {
+                self.publish(project_id, *kind, &alert);
+            }
+        }
+        Ok(())
+    }
+
+    /// Publishes alerts a request changed as `alert.updated`. The change is already
+    /// stored, so an alert that can't be loaded is logged rather than failing the request.
+    pub async fn publish_updated(&self, pool: &SqlitePool, project_id: &str, alert_ids: &[String]) {
+        let changes: Vec<(LiveEventKind, String)> = alert_ids
+            .iter()
+            .map(|alert_id| (LiveEventKind::AlertUpdated, alert_id.clone()))
+            .collect();
+        if let Err(e) = self.publish_alerts(pool, project_id, &changes).await {
+            log::error!("Failed to publish alerts for project {}: {}", project_id, e);
+        }
+    }
+}
+
+/// The project's events as a Server-Sent Events body. A comment is sent every
+/// `keepalive` without events so proxies keep the connection open, and a `lagged`
+/// event tells a client that fell behind how many events it missed.
+pub fn sse_stream(
+    receiver: broadcast::Receiver<LiveEvent>,
+    project_id: String,
+    keepalive: Duration,
+) -> impl Stream<Item = Result<Bytes, Infallible>> {
+    let connected =
+        futures_util::stream::once(async { Ok(Bytes::from_static(b": connected\n\n")) });
+    let events = futures_util::stream::unfold(receiver, move |mut receiver| {
+        let project_id = project_id.clone();
+        async move {
+            loop {
+                let message = match tokio::time::timeout(keepalive, receiver.recv()).await {
+                    Err(_) => ": keepalive\n\n".to_string(),
+                    Ok(Ok(event)) if event.project_id == project_id => event.to_sse(),
+                    Ok(Ok(_)) => continue,
+                    Ok(Err(RecvError::Lagged(skipped))) => {
+                        format!("event: lagged\ndata: {{\"skipped\":{}}}\n\n", skipped)
+                    }
+                    Ok(Err(RecvError::Closed)) => return None,
+                };
+                return Some((Ok(Bytes::from(message)), receiver));
+            }
+        }
+    });
+    futures_util::StreamExt::chain(connected, events)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::StreamExt;
+
+    fn next_message(bytes: Option<Result<Bytes, Infallible>>) -> String {
+        String::from_utf8(bytes.unwrap().unwrap().to_vec()).unwrap()
+    }
+
+    #[test]
+    fn test_to_sse() {
+        let event = LiveEvent {
+            project_id: "p1".to_string(),
+            kind: LiveEventKind::AlertCreated,
+            data: serde_json::json!({"alert_id": "a1", "message": "line\nbreak"}),
+        };
+        assert_eq!(
+            event.to_sse(),
+            "event: alert.created\ndata: {\"alert_id\":\"a1\",\"message\":\"line\\nbreak\"}\n\n"
+        );
+    }
+
+    #[test]
+    fn test_publish_without_subscribers_is_dropped() {
+        let live_events = LiveEvents::new();
+        assert!(!live_events.has_subscribers());
+        live_events.publish("p1", LiveEventKind::AlertUpdated, &serde_json::json!({}));
+
+        let mut receiver = live_events.subscribe();
+        assert!(live_events.has_subscribers());
+        assert!(receiver.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_sse_stream_filters_by_project() {
+        let live_events = LiveEvents::new();
+        let stream = sse_stream(
+            live_events.subscribe(),
+            "p1".to_string(),
+            Duration::from_secs(60),
+        );
+        futures_util::pin_mut!(stream);
+        assert_eq!(next_message(stream.next().await), ": connected\n\n");
+
+        live_events.publish(
+            "p2",
+            LiveEventKind::AlertCreated,
+            &serde_json::json!({"alert_id": "other"}),
+        );
+        live_events.publish(
+            "p1",
+            LiveEventKind::EvaluationCompleted,
+            &serde_json::json!({"evaluation_id": "e1"}),
+        );
+        assert_eq!(
+            next_message(stream.next().await),
+            "event: evaluation.completed\ndata: {\"evaluation_id\":\"e1\"}\n\n"
+        );
+
+        drop(live_events);
+        assert!(stream.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_sse_stream_keepalive_and_lag() {
+        let live_events = LiveEvents::new();
+        let stream = sse_stream(
+            live_events.subscribe(),
+            "p1".to_string(),
+            Duration::from_millis(20),
+        );
+        futures_util::pin_mut!(stream);
+        assert_eq!(next_message(stream.next().await), ": connected\n\n");
+        assert_eq!(next_message(stream.next().await), ": keepalive\n\n");
+
+        for i in 0..CHANNEL_CAPACITY + 3 {
+            live_events.publish("p1", LiveEventKind::AlertUpdated, &i);
+        }
+        assert_eq!(
+            next_message(stream.next().await),
+            "event: lagged\ndata: {\"skipped\":3}\n\n"
+        );
+        assert_eq!(
+            next_message(stream.next().await),
+            "event: alert.updated\ndata: 3\n\n"
+        );
+    }
+}
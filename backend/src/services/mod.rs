@@ -1,3 +1,8 @@
 pub mod alert_engine;
 pub mod baseline_builder;
+pub mod chat_messages;
 pub mod drift_evaluator;
+pub mod email;
+pub mod live_events;
+pub mod rule_engine;
+pub mod webhooks;
@@ -0,0 +1,739 @@
+use crate::models::{
+    severity_rank, AlertRule, Comparison, DriftMetric, EvaluationDetail, FeatureMatch,
+    FeatureSettings, NamedThreshold, ProjectSettings, RuleAlert, RuleCondition, RuleThreshold,
+};
+use crate::services::alert_engine::{
+    prediction_shift_thresholds, resolve_feature_drift_thresholds,
+};
+use crate::services::drift_evaluator::DriftResult;
+use std::collections::{BTreeMap, HashMap};
+
+// Upper bound on `consecutive.count`, which sets how much history is loaded
+const MAX_CONSECUTIVE_COUNT: usize = 100;
+
+pub struct FeatureScore {
+    pub score: f64,
+    pub p_value: Option<f64>,
+}
+
+/// What rules are evaluated against: a fresh `DriftResult` or a stored evaluation.
+pub struct RuleInput {
+    pub features: HashMap<String, FeatureScore>,
+    pub prediction_shift: f64,
+    pub prediction_rate: f64,
+    pub row_count: usize,
+}
+
+impl RuleInput {
+    pub fn from_drift_result(drift_result: &DriftResult) -> Self {
+        RuleInput {
+            features: drift_result
+                .feature_drifts
+                .iter()
+                .map(|(name, drift)| {
+                    (
+                        name.clone(),
+                        FeatureScore {
+                            score: drift.score,
+                            p_value: drift.significance.as_ref().map(|test| test.p_value),
+                        },
+                    )
+                })
+                .collect(),
+            prediction_shift: drift_result.prediction_shift,
+            prediction_rate: drift_result.incoming_prediction_rate,
+            row_count: drift_result.row_count,
+        }
+    }
+
+    pub fn from_evaluation(evaluation: &EvaluationDetail) -> Self {
+        RuleInput {
+            features: evaluation
+                .features
+                .iter()
+                .map(|f| {
+                    (
+                        f.feature_name.clone(),
+                        FeatureScore {
+                            score: f.drift_score,
+                            p_value: f.p_value,
+                        },
+                    )
+                })
+                .collect(),
+            prediction_shift: evaluation.evaluation.prediction_shift,
+            prediction_rate: evaluation.evaluation.prediction_rate,
+            row_count: evaluation.evaluation.row_count,
+        }
+    }
+}
+
+/// Project configuration the rules are resolved against.
+pub struct RuleContext<'a> {
+    pub drift_metric: DriftMetric,
+    pub project_settings: &'a ProjectSettings,
+    pub feature_settings: &'a HashMap<String, FeatureSettings>,
+}
+
+/// The rules used when a project has none of its own; they reproduce the
+/// threshold-based FEATURE_DRIFT and PREDICTION_SHIFT alerts.
+pub fn default_rules() -> Vec<AlertRule> {
+    let feature_drift = |severity: &str, threshold: NamedThreshold| AlertRule {
+        name: format!("Feature drift {}", severity),
+        severity: severity.to_string(),
+        alert_type: "FEATURE_DRIFT".to_string(),
+        per_feature: true,
+        condition: RuleCondition::FeatureDrift {
+            features: None,
+            match_mode: FeatureMatch::Any,
+            op: Comparison::Gte,
+            value: RuleThreshold::Named(threshold),
+            require_significance: true,
+        },
+        message: None,
+        cooldown_minutes: None,
+    };
+    let prediction_shift = |severity: &str, threshold: NamedThreshold| AlertRule {
+        name: format!("Prediction shift {}", severity),
+        severity: severity.to_string(),
+        alert_type: "PREDICTION_SHIFT".to_string(),
+        per_feature: false,
+        condition: RuleCondition::PredictionShift {
+            op: Comparison::Gte,
+            value: RuleThreshold::Named(threshold),
+        },
+        message: None,
+        cooldown_minutes: None,
+    };
+
+    vec![
+        feature_drift("CRITICAL", NamedThreshold::Critical),
+        feature_drift("WARN", NamedThreshold::Warn),
+        prediction_shift("CRITICAL", NamedThreshold::Critical),
+        prediction_shift("WARN", NamedThreshold::Warn),
+    ]
+}
+
+/// Returns every problem found in a rule set; empty if it is valid.
+pub fn validate_rules(rules: &[AlertRule]) -> Vec<String> {
+    let mut errors = Vec::new();
+    let mut names = std::collections::HashSet::new();
+
+    for rule in rules {
+        if rule.name.trim().is_empty() {
+            errors.push("Rule name must not be empty".to_string());
+        } else if !names.insert(rule.name.as_str()) {
+            errors.push(format!("Duplicate rule name '{}'", rule.name));
+        }
+        if rule.severity != "WARN" && rule.severity != "CRITICAL" {
+            errors.push(format!(
+                "Rule '{}': severity must be WARN or CRITICAL",
+                rule.name
+            ));
+        }
+        if rule.cooldown_minutes.is_some_and(|m| m < 0) {
+            errors.push(format!(
+                "Rule '{}': cooldown_minutes must not be negative",
+                rule.name
+            ));
+        }
+        if rule.alert_type.trim().is_empty() {
+            errors.push(format!(
+                "Rule '{}': alert_type must not be empty",
+                rule.name
+            ));
+        }
+        validate_condition(&rule.name, &rule.condition, false, &mut errors);
+    }
+
+    errors
+}
+
+fn validate_condition(
+    rule_name: &str,
+    condition: &RuleCondition,
+    in_consecutive: bool,
+    errors: &mut Vec<String>,
+) {
+    match condition {
+        RuleCondition::FeatureDrift {
+            features, value, ..
+        } => {
+            if features.as_ref().is_some_and(|f| f.is_empty()) {
+                errors.push(format!(
+                    "Rule '{}': feature_drift features must not be empty",
+                    rule_name
+                ));
+            }
+            validate_threshold(rule_name, value, errors);
+        }
+        RuleCondition::PredictionShift { value, .. } => {
+            validate_threshold(rule_name, value, errors)
+        }
+        RuleCondition::RowCount { value, .. } => {
+            if !value.is_finite() {
+                errors.push(format!(
+                    "Rule '{}': row_count value must be a number",
+                    rule_name
+                ));
+            }
+        }
+        RuleCondition::Consecutive { count, condition } => {
+            if in_consecutive {
+                errors.push(format!(
+                    "Rule '{}': consecutive conditions cannot be nested",
+                    rule_name
+                ));
+            }
+            if !(1..=MAX_CONSECUTIVE_COUNT).contains(count) {
+                errors.push(format!(
+                    "Rule '{}': consecutive count must be between 1 and {}",
+                    rule_name, MAX_CONSECUTIVE_COUNT
+                ));
+            }
+            validate_condition(rule_name, condition, true, errors);
+        }
+        RuleCondition::All { conditions } | RuleCondition::Any { conditions } => {
+            if conditions.is_empty() {
+                errors.push(format!(
+                    "Rule '{}': all/any need at least one condition",
+                    rule_name
+                ));
+            }
+            for condition in conditions {
+                validate_condition(rule_name, condition, in_consecutive, errors);
+            }
+        }
+    }
+}
+
+fn validate_threshold(rule_name: &str, value: &RuleThreshold, errors: &mut Vec<String>) {
+    if let RuleThreshold::Value(v) = value {
+        if !v.is_finite() {
+            errors.push(format!("Rule '{}': threshold must be a number", rule_name));
+        }
+    }
+}
+
+/// How many earlier evaluations the rules look back over.
+pub fn history_depth(rules: &[AlertRule]) -> usize {
+    fn depth(condition: &RuleCondition) -> usize {
+        match condition {
+            RuleCondition::Consecutive { count, condition } => {
+                count.saturating_sub(1).max(depth(condition))
+            }
+            RuleCondition::All { conditions } | RuleCondition::Any { conditions } => {
+                conditions.iter().map(depth).max().unwrap_or(0)
+            }
+            _ => 0,
+        }
+    }
+    rules.iter().map(|r| depth(&r.condition)).max().unwrap_or(0)
+}
+
+/// A leaf condition that held, for the alert message.
+struct ConditionMatch {
+    feature: Option<String>,
+    value: Option<f64>,
+    description: String,
+}
+
+/// Evaluates rules against an input and the evaluations before it (most recent
+/// first). When several rules fire for the same alert type and feature, only the
+/// most severe alert is kept.
+pub fn evaluate_rules(
+    rules: &[AlertRule],
+    input: &RuleInput,
+    history: &[RuleInput],
+    context: &RuleContext,
+) -> Vec<RuleAlert> {
+    let mut feature_names: Vec<&String> = input.features.keys().collect();
+    feature_names.sort();
+
+    let mut alerts: BTreeMap<(String, Option<String>), RuleAlert> = BTreeMap::new();
+    let mut order = Vec::new();
+
+    for rule in rules {
+        let scopes: Vec<Option<&str>> = if rule.per_feature {
+            feature_names
+                .iter()
+                .map(|name| Some(name.as_str()))
+                .collect()
+        } else {
+            vec![None]
+        };
+
+        for scope in scopes {
+            let mut matches = Vec::new();
+            if !evaluate_condition(
+                &rule.condition,
+                input,
+                history,
+                context,
+                scope,
+                &mut matches,
+            ) {
+                continue;
+            }
+
+            let feature_name = scope.map(str::to_string).or_else(|| {
+                let mut matched: Vec<&String> =
+                    matches.iter().filter_map(|m| m.feature.as_ref()).collect();
+                matched.sort();
+                matched.dedup();
+                (matched.len() == 1).then(|| matched[0].clone())
+            });
+            let details = matches
+                .iter()
+                .map(|m| m.description.as_str())
+                .collect::<Vec<_>>()
+                .join("; ");
+            let alert = RuleAlert {
+                rule_name: rule.name.clone(),
+                severity: rule.severity.clone(),
+                alert_type: rule.alert_type.clone(),
+                feature_name: feature_name.clone(),
+                metric_value: matches.iter().find_map(|m| m.value),
+                message: format!(
+                    "{}: {}",
+                    rule.message.as_deref().unwrap_or(&rule.name),
+                    details
+                ),
+                drift_metric: uses_feature_drift(&rule.condition).then_some(context.drift_metric),
+                cooldown_minutes: rule.cooldown_minutes,
+            };
+
+            let key = (rule.alert_type.clone(), feature_name);
+            match alerts.get(&key) {
+                Some(existing)
+                    if severity_rank(&existing.severity) >= severity_rank(&alert.severity) => {}
+                Some(_) => {
+                    alerts.insert(key, alert);
+                }
+                None => {
+                    order.push(key.clone());
+                    alerts.insert(key, alert);
+                }
+            }
+        }
+    }
+
+    order
+        .into_iter()
+        .filter_map(|key| alerts.remove(&key))
+        .collect()
+}
+
+fn uses_feature_drift(condition: &RuleCondition) -> bool {
+    match condition {
+        RuleCondition::FeatureDrift { .. } => true,
+        RuleCondition::Consecutive { condition, .. } => uses_feature_drift(condition),
+        RuleCondition::All { conditions } | RuleCondition::Any { conditions } => {
+            conditions.iter().any(uses_feature_drift)
+        }
+        _ => false,
+    }
+}
+
+fn evaluate_condition(
+    condition: &RuleCondition,
+    input: &RuleInput,
+    history: &[RuleInput],
+    context: &RuleContext,
+    scope: Option<&str>,
+    matches: &mut Vec<ConditionMatch>,
+) -> bool {
+    match condition {
+        RuleCondition::FeatureDrift {
+            features,
+            match_mode,
+            op,
+            value,
+            require_significance,
+        } => {
+            let candidates: Vec<String> = match (features, scope) {
+                (Some(features), _) => features.clone(),
+                (None, Some(feature)) => vec![feature.to_string()],
+                (None, None) => {
+                    let mut all: Vec<String> = input.features.keys().cloned().collect();
+                    all.sort();
+                    all
+                }
+            };
+
+            let mut matched = Vec::new();
+            for feature_name in &candidates {
+                let Some(feature) = input.features.get(feature_name) else {
+                    continue;
+                };
+                let threshold = match value {
+                    RuleThreshold::Value(v) => *v,
+                    RuleThreshold::Named(named) => {
+                        let (warn, critical) = resolve_feature_drift_thresholds(
+                            context.drift_metric,
+                            context.project_settings,
+                            context.feature_settings.get(feature_name),
+                        );
+                        match named {
+                            NamedThreshold::Warn => warn,
+                            NamedThreshold::Critical => critical,
+                        }
+                    }
+                };
+                if !op.holds(feature.score, threshold) {
+                    continue;
+                }
+                // Require significance as well as effect size, so small batches don't alert on noise
+                let significance_note = match feature.p_value {
+                    Some(p)
+                        if *require_significance
+                            && p >= context.project_settings.significance_level =>
+                    {
+                        continue
+                    }
+                    Some(p) => format!(" (p-value: {:.4})", p),
+                    None => String::new(),
+                };
+                matched.push(ConditionMatch {
+                    feature: Some(feature_name.clone()),
+                    value: Some(feature.score),
+                    description: format!(
+                        "'{}' {} drift {:.4} {} {:.4}{}",
+                        feature_name,
+                        context.drift_metric.as_str(),
+                        feature.score,
+                        op.symbol(),
+                        threshold,
+                        significance_note
+                    ),
+                });
+            }
+
+            let holds = match match_mode {
+                FeatureMatch::Any => !matched.is_empty(),
+                FeatureMatch::All => !candidates.is_empty() && matched.len() == candidates.len(),
+            };
+            if holds {
+                matches.extend(matched);
+            }
+            holds
+        }
+        RuleCondition::PredictionShift { op, value } => {
+            let threshold = match value {
+                RuleThreshold::Value(v) => *v,
+                RuleThreshold::Named(named) => {
+                    let (warn, critical) = prediction_shift_thresholds(context.project_settings);
+                    match named {
+                        NamedThreshold::Warn => warn,
+                        NamedThreshold::Critical => critical,
+                    }
+                }
+            };
+            let holds = op.holds(input.prediction_shift, threshold);
+            if holds {
+                matches.push(ConditionMatch {
+                    feature: None,
+                    value: Some(input.prediction_shift),
+                    description: format!(
+                        "prediction shift {:.4} {} {:.4} (incoming rate: {:.4})",
+                        input.prediction_shift,
+                        op.symbol(),
+                        threshold,
+                        input.prediction_rate
+                    ),
+                });
+            }
+            holds
+        }
+        RuleCondition::RowCount { op, value } => {
+            let holds = op.holds(input.row_count as f64, *value);
+            if holds {
+                matches.push(ConditionMatch {
+                    feature: None,
+                    value: Some(input.row_count as f64),
+                    description: format!("{} rows {} {}", input.row_count, op.symbol(), value),
+                });
+            }
+            holds
+        }
+        RuleCondition::Consecutive { count, condition } => {
+            let mut current = Vec::new();
+            if !evaluate_condition(condition, input, history, context, scope, &mut current) {
+                return false;
+            }
+            let previous_count = count.saturating_sub(1);
+            if history.len() < previous_count {
+                return false;
+            }
+            let sustained = (0..previous_count).all(|i| {
+                evaluate_condition(
+                    condition,
+                    &history[i],
+                    &history[i + 1..],
+                    context,
+                    scope,
+                    &mut Vec::new(),
+                )
+            });
+            if sustained {
+                matches.extend(current);
+                matches.push(ConditionMatch {
+                    feature: None,
+                    value: None,
+                    description: format!("held for {} consecutive evaluations", count),
+                });
+            }
+            sustained
+        }
+        RuleCondition::All { conditions } => {
+            let mut all_matches = Vec::new();
+            let holds = conditions
+                .iter()
+                .all(|c| evaluate_condition(c, input, history, context, scope, &mut all_matches));
+            if holds {
+                matches.extend(all_matches);
+            }
+            holds
+        }
+        RuleCondition::Any { conditions } => {
+            let mut holds = false;
+            for c in conditions {
+                holds |= evaluate_condition(c, input, history, context, scope, matches);
+            }
+            holds
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input(scores: &[(&str, f64)], prediction_shift: f64, row_count: usize) -> RuleInput {
+        RuleInput {
+            features: scores
+                .iter()
+                .map(|(name, score)| {
+                    (
+                        name.to_string(),
+                        FeatureScore {
+                            score: *score,
+                            p_value: Some(0.001),
+                        },
+                    )
+                })
+                .collect(),
+            prediction_shift,
+            prediction_rate: 0.5,
+            row_count,
+        }
+    }
+
+    fn parse_rule(json: &str) -> AlertRule {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn test_default_rules_match_thresholds() {
+        let settings = ProjectSettings::defaults("p1");
+        let feature_settings = HashMap::new();
+        let context = RuleContext {
+            drift_metric: DriftMetric::L1,
+            project_settings: &settings,
+            feature_settings: &feature_settings,
+        };
+
+        let alerts = evaluate_rules(
+            &default_rules(),
+            &input(
+                &[("income", 0.25), ("age", 0.15), ("location", 0.05)],
+                0.12,
+                100,
+            ),
+            &[],
+            &context,
+        );
+
+        let summary: Vec<(String, String, Option<String>)> = alerts
+            .iter()
+            .map(|a| {
+                (
+                    a.alert_type.clone(),
+                    a.severity.clone(),
+                    a.feature_name.clone(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    "FEATURE_DRIFT".to_string(),
+                    "CRITICAL".to_string(),
+                    Some("income".to_string())
+                ),
+                (
+                    "FEATURE_DRIFT".to_string(),
+                    "WARN".to_string(),
+                    Some("age".to_string())
+                ),
+                ("PREDICTION_SHIFT".to_string(), "WARN".to_string(), None),
+            ]
+        );
+        assert_eq!(alerts[0].metric_value, Some(0.25));
+        assert_eq!(alerts[0].drift_metric, Some(DriftMetric::L1));
+    }
+
+    #[test]
+    fn test_default_rules_respect_significance_and_overrides() {
+        let settings = ProjectSettings::defaults("p1");
+        let mut feature_settings = HashMap::new();
+        feature_settings.insert(
+            "location".to_string(),
+            FeatureSettings {
+                project_id: "p1".to_string(),
+                feature_name: "location".to_string(),
+                drift_warn_threshold: Some(0.5),
+                drift_critical_threshold: Some(0.8),
+                updated_at: None,
+            },
+        );
+        let context = RuleContext {
+            drift_metric: DriftMetric::L1,
+            project_settings: &settings,
+            feature_settings: &feature_settings,
+        };
+
+        let mut batch = input(&[("income", 0.3), ("location", 0.3)], 0.0, 100);
+        batch.features.get_mut("income").unwrap().p_value = Some(0.4);
+
+        assert!(evaluate_rules(&default_rules(), &batch, &[], &context).is_empty());
+    }
+
+    #[test]
+    fn test_all_and_row_count_rule() {
+        let settings = ProjectSettings::defaults("p1");
+        let feature_settings = HashMap::new();
+        let context = RuleContext {
+            drift_metric: DriftMetric::Psi,
+            project_settings: &settings,
+            feature_settings: &feature_settings,
+        };
+        let rule = parse_rule(
+            r#"{"name": "large batch drift", "severity": "CRITICAL",
+                "condition": {"type": "all", "conditions": [
+                    {"type": "feature_drift", "features": ["income", "age"], "op": "gt", "value": 0.2},
+                    {"type": "row_count", "op": "gt", "value": 500}
+                ]}}"#,
+        );
+
+        let small = input(&[("income", 0.3), ("age", 0.0)], 0.0, 100);
+        assert!(evaluate_rules(std::slice::from_ref(&rule), &small, &[], &context).is_empty());
+
+        let large = input(&[("income", 0.3), ("age", 0.0)], 0.0, 600);
+        let alerts = evaluate_rules(&[rule], &large, &[], &context);
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].alert_type, "RULE");
+        assert_eq!(alerts[0].feature_name.as_deref(), Some("income"));
+        assert!(alerts[0]
+            .message
+            .starts_with("large batch drift: 'income' PSI drift"));
+    }
+
+    #[test]
+    fn test_rule_matching_one_feature_twice_names_it() {
+        let settings = ProjectSettings::defaults("p1");
+        let feature_settings = HashMap::new();
+        let context = RuleContext {
+            drift_metric: DriftMetric::Psi,
+            project_settings: &settings,
+            feature_settings: &feature_settings,
+        };
+        let rule = parse_rule(
+            r#"{"name": "income drift", "severity": "WARN",
+                "condition": {"type": "all", "conditions": [
+                    {"type": "feature_drift", "features": ["income"], "op": "gt", "value": 0.2},
+                    {"type": "feature_drift", "features": ["age"], "op": "lt", "value": 0.1,
+                     "require_significance": false},
+                    {"type": "feature_drift", "features": ["income"], "op": "gt", "value": 0.25}
+                ]}}"#,
+        );
+
+        let alerts = evaluate_rules(
+            std::slice::from_ref(&rule),
+            &input(&[("income", 0.3), ("age", 0.0)], 0.0, 100),
+            &[],
+            &context,
+        );
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].feature_name, None);
+
+        let only_income = parse_rule(
+            r#"{"name": "income drift", "severity": "WARN",
+                "condition": {"type": "all", "conditions": [
+                    {"type": "feature_drift", "features": ["income"], "op": "gt", "value": 0.2},
+                    {"type": "row_count", "op": "gt", "value": 50},
+                    {"type": "feature_drift", "features": ["income"], "op": "gt", "value": 0.25}
+                ]}}"#,
+        );
+        let alerts = evaluate_rules(
+            &[only_income],
+            &input(&[("income", 0.3), ("age", 0.0)], 0.0, 100),
+            &[],
+            &context,
+        );
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].feature_name.as_deref(), Some("income"));
+    }
+
+    #[test]
+    fn test_consecutive_rule_uses_history() {
+        let settings = ProjectSettings::defaults("p1");
+        let feature_settings = HashMap::new();
+        let context = RuleContext {
+            drift_metric: DriftMetric::L1,
+            project_settings: &settings,
+            feature_settings: &feature_settings,
+        };
+        let rule = parse_rule(
+            r#"{"name": "sustained drift", "severity": "WARN", "per_feature": true,
+                "condition": {"type": "consecutive", "count": 3,
+                              "condition": {"type": "feature_drift", "value": 0.1}}}"#,
+        );
+        assert_eq!(history_depth(std::slice::from_ref(&rule)), 2);
+
+        let current = input(&[("income", 0.2), ("age", 0.2)], 0.0, 100);
+        let history = vec![
+            input(&[("income", 0.15), ("age", 0.05)], 0.0, 100),
+            input(&[("income", 0.12), ("age", 0.3)], 0.0, 100),
+        ];
+        let alerts = evaluate_rules(std::slice::from_ref(&rule), &current, &history, &context);
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].feature_name.as_deref(), Some("income"));
+
+        // Not enough history yet
+        assert!(evaluate_rules(&[rule], &current, &history[..1], &context).is_empty());
+    }
+
+    #[test]
+    fn test_validate_rules() {
+        assert!(validate_rules(&default_rules()).is_empty());
+
+        let invalid = vec![
+            parse_rule(
+                r#"{"name": "a", "severity": "INFO",
+                    "condition": {"type": "any", "conditions": []}}"#,
+            ),
+            parse_rule(
+                r#"{"name": "a", "severity": "WARN",
+                    "condition": {"type": "consecutive", "count": 2, "condition":
+                        {"type": "consecutive", "count": 0, "condition":
+                            {"type": "prediction_shift", "value": "warn"}}}}"#,
+            ),
+        ];
+        let errors = validate_rules(&invalid);
+        assert_eq!(errors.len(), 5);
+        assert!(errors.iter().any(|e| e.contains("Duplicate rule name")));
+        assert!(errors.iter().any(|e| e.contains("cannot be nested")));
+    }
+}
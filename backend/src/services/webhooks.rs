@@ -0,0 +1,280 @@
+use crate::models::{
+    severity_rank, Alert, AlertNotification, Evaluation, EvaluationDetail, NotificationEvent,
+    Project, UploadIncomingResponse, Webhook, WebhookDelivery,
+};
+use crate::services::chat_messages::{self, UploadSummary};
+use crate::storage::{alerts, projects, webhooks};
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use sqlx::SqlitePool;
+use std::time::Duration;
+use uuid::Uuid;
+
+pub const SIGNATURE_HEADER: &str = "X-DriftGuard-Signature";
+pub const EVENT_HEADER: &str = "X-DriftGuard-Event";
+pub const DELIVERY_HEADER: &str = "X-DriftGuard-Delivery";
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How failed deliveries are retried: up to `max_attempts` in total, waiting
+/// `initial_backoff` after the first failure and doubling the wait each time.
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    pub initial_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 4,
+            initial_backoff: Duration::from_secs(1),
+        }
+    }
+}
+
+impl RetryPolicy {
+    fn backoff(&self, attempt: u32) -> Duration {
+        self.initial_backoff * 2u32.saturating_pow(attempt.saturating_sub(1))
+    }
+}
+
+/// The `X-DriftGuard-Signature` value for a body: `sha256=` and the hex HMAC-SHA256
+/// of the raw body keyed with the webhook secret.
+pub fn sign_payload(secret: &str, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any size");
+    mac.update(body);
+    format!("sha256={:x}", mac.finalize().into_bytes())
+}
+
+/// Server errors and rate limiting are worth retrying; other client errors are not.
+fn is_retryable(status: reqwest::StatusCode) -> bool {
+    status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS
+}
+
+pub fn alert_payload(
+    event: NotificationEvent,
+    alert: &Alert,
+    project: &Project,
+    evaluation: Option<&Evaluation>,
+) -> serde_json::Value {
+    serde_json::json!({
+        "event": event,
+        "alert": alert,
+        "project": {
+            "project_id": project.project_id,
+            "name": project.name,
+        },
+        "evaluation": evaluation,
+        "sent_at": Utc::now(),
+    })
+}
+
+pub fn test_payload(project: &Project) -> serde_json::Value {
+    serde_json::json!({
+        "event": NotificationEvent::Test,
+        "message": "Test delivery from DriftGuard",
+        "project": {
+            "project_id": project.project_id,
+            "name": project.name,
+        },
+        "sent_at": Utc::now(),
+    })
+}
+
+pub fn client() -> anyhow::Result<reqwest::Client> {
+    Ok(reqwest::Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .build()?)
+}
+
+/// POSTs a signed payload to a webhook, retrying per `retry`, and logs the delivery.
+pub async fn deliver(
+    pool: &SqlitePool,
+    client: &reqwest::Client,
+    webhook: &Webhook,
+    alert_id: Option<&str>,
+    event: NotificationEvent,
+    payload: serde_json::Value,
+    retry: RetryPolicy,
+) -> anyhow::Result<WebhookDelivery> {
+    let delivery_id = Uuid::new_v4().to_string();
+    let body = serde_json::to_vec(&payload)?;
+    let signature = sign_payload(&webhook.secret, &body);
+
+    let mut attempts = 0;
+    let (response_status, error) = loop {
+        attempts += 1;
+        let result = client
+            .post(&webhook.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(SIGNATURE_HEADER, &signature)
+            .header(EVENT_HEADER, event.as_str())
+            .header(DELIVERY_HEADER, &delivery_id)
+            .body(body.clone())
+            .send()
+            .await;
+
+        let (response_status, error, retryable) = match result {
+            Ok(response) if response.status().is_success() => {
+                break (Some(response.status().as_u16() as i64), None);
+            }
+            Ok(response) => {
+                let status = response.status();
+                (
+                    Some(status.as_u16() as i64),
+                    Some(format!("Webhook responded with HTTP {}", status.as_u16())),
+                    is_retryable(status),
+                )
+            }
+            Err(e) => (None, Some(e.to_string()), true),
+        };
+
+        if !retryable || attempts >= retry.max_attempts {
+            break (response_status, error);
+        }
+        tokio::time::sleep(retry.backoff(attempts)).await;
+    };
+
+    let delivery = WebhookDelivery {
+        delivery_id,
+        webhook_id: webhook.webhook_id.clone(),
+        project_id: webhook.project_id.clone(),
+        alert_id: alert_id.map(str::to_string),
+        event,
+        payload,
+        status: if error.is_none() { "SUCCESS" } else { "FAILED" }.to_string(),
+        attempts: attempts as i64,
+        response_status,
+        error,
+        created_at: Utc::now(),
+    };
+    webhooks::create_delivery(pool, &delivery).await?;
+
+    Ok(delivery)
+}
+
+/// Sends new and escalated alerts from an upload to the project's webhooks whose
+/// `min_severity` they meet: one request per alert for `JSON` webhooks, and a
+/// single summary message for Slack and Teams webhooks. A failed delivery is logged
+/// and does not stop the rest.
+pub async fn notify_alerts(
+    pool: &SqlitePool,
+    project_id: &str,
+    evaluation: &EvaluationDetail,
+    upload: &UploadIncomingResponse,
+    notifications: &[AlertNotification],
+    retry: RetryPolicy,
+) -> anyhow::Result<()> {
+    let project_webhooks = webhooks::list_webhooks(pool, project_id).await?;
+    if project_webhooks.is_empty() || notifications.is_empty() {
+        return Ok(());
+    }
+    let project = projects::get_project(pool, project_id)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("Project not found: {}", project_id))?;
+    let client = client()?;
+    let url = chat_messages::project_alerts_url(project_id);
+
+    let mut notified = Vec::new();
+    for notification in notifications {
+        if let Some(alert) = alerts::get_alert(pool, project_id, &notification.alert_id).await? {
+            notified.push((notification.event, alert));
+        }
+    }
+
+    for webhook in &project_webhooks {
+        let matching: Vec<&(NotificationEvent, Alert)> = notified
+            .iter()
+            .filter(|(_, alert)| {
+                severity_rank(&alert.severity) >= severity_rank(&webhook.min_severity)
+            })
+            .collect();
+        if matching.is_empty() {
+            continue;
+        }
+
+        let alerts: Vec<Alert> = matching.iter().map(|(_, alert)| alert.clone()).collect();
+        let summary = UploadSummary {
+            project: &project,
+            upload,
+            features: &evaluation.features,
+            alerts: &alerts,
+        };
+        if let Some(message) = chat_messages::summary_message(webhook.format, &summary, &url) {
+            if let Err(e) = deliver(
+                pool,
+                &client,
+                webhook,
+                None,
+                NotificationEvent::AlertsSummary,
+                message,
+                retry,
+            )
+            .await
+            {
+                log::error!("Failed to deliver to webhook {}: {}", webhook.webhook_id, e);
+            }
+            continue;
+        }
+
+        for (event, alert) in matching {
+            let payload = alert_payload(*event, alert, &project, Some(&evaluation.evaluation));
+            if let Err(e) = deliver(
+                pool,
+                &client,
+                webhook,
+                Some(&alert.alert_id),
+                *event,
+                payload,
+                retry,
+            )
+            .await
+            {
+                log::error!(
+                    "Failed to deliver alert {} to webhook {}: {}",
+                    alert.alert_id,
+                    webhook.webhook_id,
+                    e
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sign_payload_matches_known_hmac() {
+        // RFC 4231 test case 2
+        assert_eq!(
+            sign_payload("Jefe", b"what do ya want for nothing?"),
+            "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    #[test]
+    fn test_retry_policy_backs_off_exponentially() {
+        let retry = RetryPolicy {
+            max_attempts: 4,
+            initial_backoff: Duration::from_millis(100),
+        };
+        assert_eq!(retry.backoff(1), Duration::from_millis(100));
+        assert_eq!(retry.backoff(2), Duration::from_millis(200));
+        assert_eq!(retry.backoff(3), Duration::from_millis(400));
+    }
+
+    #[test]
+    fn test_only_server_errors_and_rate_limits_are_retried() {
+        assert!(is_retryable(reqwest::StatusCode::INTERNAL_SERVER_ERROR));
+        assert!(is_retryable(reqwest::StatusCode::TOO_MANY_REQUESTS));
+        assert!(!is_retryable(reqwest::StatusCode::NOT_FOUND));
+        assert!(!is_retryable(reqwest::StatusCode::UNAUTHORIZED));
+    }
+}
@@ -0,0 +1,77 @@
+use crate::models::AlertEvent;
+use chrono::Utc;
+use sqlx::{SqliteConnection, SqlitePool};
+use uuid::Uuid;
+
+/// Appends an entry to an alert's audit trail. Does nothing if the alert doesn't exist.
+pub async fn insert_event(
+    conn: &mut SqliteConnection,
+    alert_id: &str,
+    event_type: &str,
+    actor: &str,
+    from_status: Option<&str>,
+    to_status: Option<&str>,
+    details: Option<&str>,
+) -> anyhow::Result<()> {
+    let event_id = Uuid::new_v4().to_string();
+    let created_at_str = Utc::now().to_rfc3339();
+
+    sqlx::query!(
+        r#"
+        INSERT INTO alert_events (event_id, alert_id, project_id, event_type, actor, from_status, to_status, details, created_at)
+        SELECT ?1, alert_id, project_id, ?3, ?4, ?5, ?6, ?7, ?8
+        FROM alerts
+        WHERE alert_id = ?2
+        "#,
+        event_id,
+        alert_id,
+        event_type,
+        actor,
+        from_status,
+        to_status,
+        details,
+        created_at_str
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn list_alert_events(
+    pool: &SqlitePool,
+    alert_id: &str,
+) -> anyhow::Result<Vec<AlertEvent>> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT event_id, alert_id, project_id, event_type, actor, from_status, to_status, details, created_at
+        FROM alert_events
+        WHERE alert_id = ?1
+        ORDER BY created_at ASC
+        "#,
+        alert_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut events = Vec::new();
+    for row in rows {
+        let created_at = chrono::DateTime::parse_from_rfc3339(&row.created_at)
+            .map_err(|e| anyhow::anyhow!("Failed to parse date: {}", e))?
+            .with_timezone(&chrono::Utc);
+
+        events.push(AlertEvent {
+            event_id: row.event_id.expect("event_id should not be null"),
+            alert_id: row.alert_id,
+            project_id: row.project_id,
+            event_type: row.event_type,
+            actor: row.actor,
+            from_status: row.from_status,
+            to_status: row.to_status,
+            details: row.details,
+            created_at,
+        });
+    }
+
+    Ok(events)
+}
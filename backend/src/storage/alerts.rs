@@ -1,21 +1,110 @@
-use crate::models::{Alert, AlertFilter};
-use chrono::Utc;
-use sqlx::{Row, SqlitePool};
+use crate::models::{
+    severity_rank, Alert, AlertAction, AlertCursor, AlertFilter, AlertOutcome, AlertPage,
+    AlertSort, AlertSummary, AlertSummaryParams, AlertTransition, BulkAlertAction,
+    CreateAlertParams, DailyAlertCount, FeatureAlertCount, SortOrder,
+};
+use crate::storage::alert_events;
+use chrono::{DateTime, Utc};
+use sqlx::sqlite::SqliteRow;
+use sqlx::{Acquire, Row, Sqlite, SqliteConnection, SqlitePool};
+use std::collections::BTreeMap;
 use uuid::Uuid;
 
+fn parse_optional_date(value: Option<String>) -> Option<DateTime<Utc>> {
+    value
+        .and_then(|t| DateTime::parse_from_rfc3339(&t).ok())
+        .map(|t| t.with_timezone(&Utc))
+}
+
+/// Current status of a project's alert, or None if the project has no such alert.
+async fn alert_status(
+    conn: &mut SqliteConnection,
+    project_id: &str,
+    alert_id: &str,
+) -> anyhow::Result<Option<String>> {
+    let row = sqlx::query!(
+        r#"
+        SELECT status FROM alerts WHERE alert_id = ?1 AND project_id = ?2
+        "#,
+        alert_id,
+        project_id
+    )
+    .fetch_optional(conn)
+    .await?;
+
+    Ok(row.map(|r| r.status))
+}
+
+/// The alert's current status if `action` can change it; otherwise the outcome to report.
+/// Single and bulk actions both go through `AlertAction::allows`.
+async fn transition_from(
+    conn: &mut SqliteConnection,
+    project_id: &str,
+    alert_id: &str,
+    action: AlertAction,
+) -> anyhow::Result<Result<String, AlertTransition>> {
+    Ok(match alert_status(conn, project_id, alert_id).await? {
+        None => Err(AlertTransition::NotFound),
+        Some(status) if !action.allows(&status) => Err(AlertTransition::NotAllowed(status)),
+        Some(status) => Ok(status),
+    })
+}
+
+/// Appends the `AND ...` conditions for `filter` to a query, binding values after `params`.
+/// Comma-separated values match any of them, e.g. `severity=WARN,CRITICAL`.
+fn push_filter_conditions(query: &mut String, params: &mut Vec<String>, filter: &AlertFilter) {
+    let any_of = [
+        ("status", &filter.status),
+        ("assignee", &filter.assignee),
+        ("severity", &filter.severity),
+        ("feature_name", &filter.feature_name),
+        ("alert_type", &filter.alert_type),
+        ("incident_id", &filter.incident_id),
+    ];
+    for (column, value) in any_of {
+        let Some(value) = value else {
+            continue;
+        };
+        let mut placeholders = Vec::new();
+        for value in value.split(',').map(str::trim).filter(|v| !v.is_empty()) {
+            params.push(value.to_string());
+            placeholders.push(format!("?{}", params.len()));
+        }
+        if !placeholders.is_empty() {
+            query.push_str(&format!(" AND {} IN ({})", column, placeholders.join(", ")));
+        }
+    }
+
+    let bounds = [
+        ("created_at >= ", &filter.start_time),
+        ("created_at <= ", &filter.end_time),
+    ];
+    for (condition, value) in bounds {
+        if let Some(value) = value {
+            params.push(value.clone());
+            query.push_str(&format!(" AND {}?{}", condition, params.len()));
+        }
+    }
+}
+
+/// Stores a new OPEN alert. `conn` is a pool, or a connection whose open transaction the
+/// alert joins.
 pub async fn create_alert(
-    pool: &SqlitePool,
-    params: &crate::models::CreateAlertParams,
+    conn: impl Acquire<'_, Database = Sqlite>,
+    params: &CreateAlertParams,
 ) -> anyhow::Result<Alert> {
     let alert_id = Uuid::new_v4().to_string();
     let created_at = Utc::now();
     let created_at_str = created_at.to_rfc3339();
     let feature_name_ref = params.feature_name.as_deref();
+    let drift_metric_ref = params.drift_metric.as_deref();
+    let rule_name_ref = params.rule_name.as_deref();
 
+    let mut tx = conn.begin().await?;
     sqlx::query!(
         r#"
-        INSERT INTO alerts (alert_id, project_id, baseline_version, created_at, severity, alert_type, feature_name, metric_value, message, status)
-        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, 'OPEN')
+        INSERT INTO alerts (alert_id, project_id, baseline_version, created_at, severity, alert_type, feature_name, metric_value, message, status, drift_metric, rule_name, occurrence_count, last_seen_at)
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, 'OPEN', ?10, ?11, 1, ?4)
         "#,
         alert_id,
         params.project_id,
@@ -25,10 +114,23 @@ pub async fn create_alert(
         params.alert_type,
         feature_name_ref,
         params.metric_value,
-        params.message
+        params.message,
+        drift_metric_ref,
+        rule_name_ref
+    )
+    .execute(&mut *tx)
+    .await?;
+    alert_events::insert_event(
+        &mut tx,
+        &alert_id,
+        "CREATED",
+        "auto",
+        None,
+        Some("OPEN"),
+        None,
     )
-    .execute(pool)
     .await?;
+    tx.commit().await?;
 
     Ok(Alert {
         alert_id,
@@ -41,103 +143,747 @@ pub async fn create_alert(
         metric_value: params.metric_value,
         message: params.message.clone(),
         status: "OPEN".to_string(),
+        drift_metric: params.drift_metric.clone(),
+        rule_name: params.rule_name.clone(),
+        occurrence_count: 1,
+        last_seen_at: created_at,
+        acked_at: None,
+        resolved_at: None,
+        resolved_by: None,
+        resolution_note: None,
+        snoozed_until: None,
+        assignee: None,
+        incident_id: None,
     })
 }
 
-pub async fn list_alerts(
-    pool: &SqlitePool,
-    project_id: &str,
-    filter: &AlertFilter,
-) -> anyhow::Result<Vec<Alert>> {
-    let mut query = String::from(
+/// Raises an alert, folding it into the project's OPEN alert of the same type and
+/// feature if there is one. A snoozed alert, or one acknowledged less than
+/// `cooldown_minutes` ago, is updated in place but keeps its status; a snoozed
+/// alert whose snooze is over is reopened.
+pub async fn record_alert(
+    conn: impl Acquire<'_, Database = Sqlite>,
+    params: &CreateAlertParams,
+    cooldown_minutes: i64,
+) -> anyhow::Result<AlertOutcome> {
+    let mut conn = conn.acquire().await?;
+    let feature_name_ref = params.feature_name.as_deref();
+    let existing = sqlx::query!(
         r#"
-        SELECT alert_id, project_id, baseline_version, created_at, severity, alert_type, feature_name, metric_value, message, status
+        SELECT alert_id AS "alert_id!", status, severity, acked_at, snoozed_until
         FROM alerts
-        WHERE project_id = ?1
+        WHERE project_id = ?1 AND alert_type = ?2 AND feature_name IS ?3 AND status IN ('OPEN', 'ACK', 'SNOOZED')
+        ORDER BY created_at DESC
+        LIMIT 1
+        "#,
+        params.project_id,
+        params.alert_type,
+        feature_name_ref
+    )
+    .fetch_optional(&mut *conn)
+    .await?;
+
+    let now = Utc::now();
+    let outcome = match existing {
+        Some(row) if row.status == "OPEN" => {
+            if severity_rank(&params.severity) > severity_rank(&row.severity) {
+                AlertOutcome::Escalated(row.alert_id)
+            } else {
+                AlertOutcome::Updated(row.alert_id)
+            }
+        }
+        Some(row) if row.status == "SNOOZED" => {
+            if parse_optional_date(row.snoozed_until).is_some_and(|until| now < until) {
+                AlertOutcome::Suppressed(row.alert_id)
+            } else {
+                reopen_alert(&mut conn, &row.alert_id).await?;
+                AlertOutcome::Updated(row.alert_id)
+            }
+        }
+        Some(row) => match parse_optional_date(row.acked_at) {
+            Some(acked_at) if now < acked_at + chrono::Duration::minutes(cooldown_minutes) => {
+                AlertOutcome::Suppressed(row.alert_id)
+            }
+            _ => AlertOutcome::Created(create_alert(&mut *conn, params).await?.alert_id),
+        },
+        None => AlertOutcome::Created(create_alert(&mut *conn, params).await?.alert_id),
+    };
+
+    if let AlertOutcome::Escalated(alert_id)
+    | AlertOutcome::Updated(alert_id)
+    | AlertOutcome::Suppressed(alert_id) = &outcome
+    {
+        let now_str = now.to_rfc3339();
+        let drift_metric_ref = params.drift_metric.as_deref();
+        let rule_name_ref = params.rule_name.as_deref();
+        sqlx::query!(
+            r#"
+            UPDATE alerts
+            SET occurrence_count = occurrence_count + 1,
+                last_seen_at = ?2,
+                severity = ?3,
+                metric_value = ?4,
+                message = ?5,
+                baseline_version = ?6,
+                drift_metric = ?7,
+                rule_name = ?8
+            WHERE alert_id = ?1
+            "#,
+            alert_id,
+            now_str,
+            params.severity,
+            params.metric_value,
+            params.message,
+            params.baseline_version,
+            drift_metric_ref,
+            rule_name_ref
+        )
+        .execute(&mut *conn)
+        .await?;
+    }
+
+    Ok(outcome)
+}
+
+async fn reopen_alert(conn: &mut SqliteConnection, alert_id: &str) -> anyhow::Result<()> {
+    let mut tx = conn.begin().await?;
+    sqlx::query!(
+        r#"
+        UPDATE alerts SET status = 'OPEN', snoozed_until = NULL WHERE alert_id = ?1
         "#,
+        alert_id
+    )
+    .execute(&mut *tx)
+    .await?;
+    alert_events::insert_event(
+        &mut tx,
+        alert_id,
+        "REOPENED",
+        "auto",
+        Some("SNOOZED"),
+        Some("OPEN"),
+        Some("Raised again after the snooze ended"),
+    )
+    .await?;
+    tx.commit().await?;
+
+    Ok(())
+}
+
+const ALERT_COLUMNS: &str = "alert_id, project_id, baseline_version, created_at, severity, alert_type, feature_name, metric_value, message, status, drift_metric, \
+     rule_name, occurrence_count, last_seen_at, acked_at, resolved_at, resolved_by, resolution_note, snoozed_until, assignee, incident_id";
+
+// Alerts are read with dynamic queries and mapped by hand to avoid DateTime issues
+fn alert_from_row(row: &SqliteRow) -> anyhow::Result<Alert> {
+    let created_at_str: String = row.get("created_at");
+    let created_at = chrono::DateTime::parse_from_rfc3339(&created_at_str)
+        .map_err(|e| anyhow::anyhow!("Failed to parse date: {}", e))?
+        .with_timezone(&chrono::Utc);
+    let last_seen_at = parse_optional_date(row.get("last_seen_at")).unwrap_or(created_at);
+
+    Ok(Alert {
+        alert_id: row.get("alert_id"),
+        project_id: row.get("project_id"),
+        baseline_version: row.get("baseline_version"),
+        created_at,
+        severity: row.get("severity"),
+        alert_type: row.get("alert_type"),
+        feature_name: row.get("feature_name"),
+        metric_value: row.get("metric_value"),
+        message: row.get("message"),
+        status: row.get("status"),
+        drift_metric: row.get("drift_metric"),
+        rule_name: row.get("rule_name"),
+        occurrence_count: row.get("occurrence_count"),
+        last_seen_at,
+        acked_at: parse_optional_date(row.get("acked_at")),
+        resolved_at: parse_optional_date(row.get("resolved_at")),
+        resolved_by: row.get("resolved_by"),
+        resolution_note: row.get("resolution_note"),
+        snoozed_until: parse_optional_date(row.get("snoozed_until")),
+        assignee: row.get("assignee"),
+        incident_id: row.get("incident_id"),
+    })
+}
+
+pub async fn get_alert(
+    pool: &SqlitePool,
+    project_id: &str,
+    alert_id: &str,
+) -> anyhow::Result<Option<Alert>> {
+    let query = format!(
+        "SELECT {} FROM alerts WHERE alert_id = ?1 AND project_id = ?2",
+        ALERT_COLUMNS
     );
+    let row = sqlx::query(&query)
+        .bind(alert_id)
+        .bind(project_id)
+        .fetch_optional(pool)
+        .await?;
+
+    row.as_ref().map(alert_from_row).transpose()
+}
 
+pub async fn list_alerts(
+    pool: &SqlitePool,
+    project_id: &str,
+    filter: &AlertFilter,
+) -> anyhow::Result<Vec<Alert>> {
+    let mut query = format!("SELECT {} FROM alerts WHERE project_id = ?1", ALERT_COLUMNS);
     let mut params: Vec<String> = vec![project_id.to_string()];
-    let mut param_index = 2;
+    push_filter_conditions(&mut query, &mut params, filter);
+    query.push_str(" ORDER BY created_at DESC");
 
-    if let Some(status) = &filter.status {
-        query.push_str(&format!(" AND status = ?{}", param_index));
-        params.push(status.clone());
-        param_index += 1;
+    let mut sql_query = sqlx::query(&query);
+    for param in params {
+        sql_query = sql_query.bind(param);
     }
 
-    if let Some(severity) = &filter.severity {
-        query.push_str(&format!(" AND severity = ?{}", param_index));
-        params.push(severity.clone());
-        param_index += 1;
-    }
+    let rows = sql_query.fetch_all(pool).await?;
+    rows.iter().map(alert_from_row).collect()
+}
 
-    if let Some(feature_name) = &filter.feature_name {
-        query.push_str(&format!(" AND feature_name = ?{}", param_index));
-        params.push(feature_name.clone());
-        param_index += 1;
+/// The expression alerts are ordered by for a sort. Alerts without a metric value sort
+/// below all others.
+fn sort_expression(sort: AlertSort) -> &'static str {
+    match sort {
+        AlertSort::CreatedAt => "created_at",
+        AlertSort::Severity => "CASE severity WHEN 'CRITICAL' THEN 2 WHEN 'WARN' THEN 1 ELSE 0 END",
+        AlertSort::MetricValue => "COALESCE(metric_value, -1.0e308)",
     }
+}
 
-    if let Some(alert_type) = &filter.alert_type {
-        query.push_str(&format!(" AND alert_type = ?{}", param_index));
-        params.push(alert_type.clone());
-        param_index += 1;
+/// One page of the alerts matching `filter`, in `sort` order with ties broken by alert
+/// id, starting after `after`. Pages stay consistent while alerts are being added.
+pub async fn list_alerts_page(
+    pool: &SqlitePool,
+    project_id: &str,
+    filter: &AlertFilter,
+    sort: AlertSort,
+    order: SortOrder,
+    limit: i64,
+    after: Option<&AlertCursor>,
+) -> anyhow::Result<AlertPage> {
+    let mut count_query = "SELECT COUNT(*) AS total FROM alerts WHERE project_id = ?1".to_string();
+    let mut count_params: Vec<String> = vec![project_id.to_string()];
+    push_filter_conditions(&mut count_query, &mut count_params, filter);
+    let mut sql_count = sqlx::query(&count_query);
+    for param in count_params {
+        sql_count = sql_count.bind(param);
     }
+    let total: i64 = sql_count.fetch_one(pool).await?.get("total");
 
-    if let Some(start_time) = &filter.start_time {
-        query.push_str(&format!(" AND created_at >= ?{}", param_index));
-        params.push(start_time.clone());
-        param_index += 1;
+    let expression = sort_expression(sort);
+    let (direction, comparison) = match order {
+        SortOrder::Asc => ("ASC", ">"),
+        SortOrder::Desc => ("DESC", "<"),
+    };
+    let mut query = format!(
+        "SELECT {}, {} AS sort_key FROM alerts WHERE project_id = ?1",
+        ALERT_COLUMNS, expression
+    );
+    let mut params: Vec<String> = vec![project_id.to_string()];
+    push_filter_conditions(&mut query, &mut params, filter);
+    if after.is_some() {
+        // The sort key is bound separately below, as it isn't always text
+        query.push_str(&format!(
+            " AND ({expr} {cmp} ?{key} OR ({expr} = ?{key} AND alert_id {cmp} ?{id}))",
+            expr = expression,
+            cmp = comparison,
+            key = params.len() + 1,
+            id = params.len() + 2
+        ));
     }
+    query.push_str(&format!(
+        " ORDER BY {} {dir}, alert_id {dir} LIMIT {}",
+        expression,
+        limit + 1,
+        dir = direction
+    ));
 
-    if let Some(end_time) = &filter.end_time {
-        query.push_str(&format!(" AND created_at <= ?{}", param_index));
-        params.push(end_time.clone());
+    let mut sql_query = sqlx::query(&query);
+    for param in params {
+        sql_query = sql_query.bind(param);
+    }
+    if let Some(after) = after {
+        sql_query = match sort {
+            AlertSort::CreatedAt => sql_query.bind(after.value.as_str().map(str::to_string)),
+            AlertSort::Severity => sql_query.bind(after.value.as_i64()),
+            AlertSort::MetricValue => sql_query.bind(after.value.as_f64()),
+        };
+        sql_query = sql_query.bind(after.alert_id.clone());
     }
+    let mut rows = sql_query.fetch_all(pool).await?;
 
-    query.push_str(" ORDER BY created_at DESC");
+    // The extra row only tells whether there is another page
+    let has_more = rows.len() as i64 > limit;
+    rows.truncate(limit as usize);
+    let next_cursor = match rows.last() {
+        Some(row) if has_more => {
+            let value = match sort {
+                AlertSort::CreatedAt => serde_json::json!(row.get::<String, _>("sort_key")),
+                AlertSort::Severity => serde_json::json!(row.get::<i64, _>("sort_key")),
+                AlertSort::MetricValue => serde_json::json!(row.get::<f64, _>("sort_key")),
+            };
+            let cursor = AlertCursor {
+                sort,
+                order,
+                value,
+                alert_id: row.get("alert_id"),
+            };
+            Some(cursor.encode())
+        }
+        _ => None,
+    };
+
+    Ok(AlertPage {
+        alerts: rows
+            .iter()
+            .map(alert_from_row)
+            .collect::<anyhow::Result<_>>()?,
+        total,
+        next_cursor,
+    })
+}
+
+// Features with the most alert occurrences listed in a summary
+const SUMMARY_TOP_FEATURES: i64 = 10;
+
+/// Runs `select` over the project's alerts that match `filter`, followed by `rest`
+/// (grouping and ordering).
+async fn fetch_filtered(
+    pool: &SqlitePool,
+    select: &str,
+    project_id: &str,
+    filter: &AlertFilter,
+    rest: &str,
+) -> anyhow::Result<Vec<SqliteRow>> {
+    let mut query = format!("{} FROM alerts WHERE project_id = ?1", select);
+    let mut params: Vec<String> = vec![project_id.to_string()];
+    push_filter_conditions(&mut query, &mut params, filter);
+    query.push(' ');
+    query.push_str(rest);
 
-    // Use query and manually map rows to avoid DateTime issues with dynamic queries
     let mut sql_query = sqlx::query(&query);
     for param in params {
         sql_query = sql_query.bind(param);
     }
+    Ok(sql_query.fetch_all(pool).await?)
+}
 
-    let rows = sql_query.fetch_all(pool).await?;
-    let mut alerts = Vec::new();
+/// Open alert counts, the features with the most drift alert occurrences, the mean time
+/// to acknowledge and the number of alerts created per UTC day, over the alerts created
+/// within `params`. Days without alerts between the first and last are reported as zero.
+pub async fn alert_summary(
+    pool: &SqlitePool,
+    project_id: &str,
+    params: &AlertSummaryParams,
+) -> anyhow::Result<AlertSummary> {
+    let filter = &AlertFilter {
+        start_time: params.start_time.clone(),
+        end_time: params.end_time.clone(),
+        ..AlertFilter::default()
+    };
+    let open_rows = fetch_filtered(
+        pool,
+        "SELECT severity, alert_type, COUNT(*) AS alert_count",
+        project_id,
+        filter,
+        "AND status = 'OPEN' GROUP BY severity, alert_type",
+    )
+    .await?;
+    let mut open_by_severity = BTreeMap::new();
+    let mut open_by_type = BTreeMap::new();
+    for row in &open_rows {
+        let count: i64 = row.get("alert_count");
+        *open_by_severity.entry(row.get("severity")).or_insert(0) += count;
+        *open_by_type.entry(row.get("alert_type")).or_insert(0) += count;
+    }
 
-    for row in rows {
-        let created_at_str: String = row.get("created_at");
-        let created_at = chrono::DateTime::parse_from_rfc3339(&created_at_str)
+    let feature_rows = fetch_filtered(
+        pool,
+        "SELECT feature_name, COUNT(*) AS alert_count, SUM(occurrence_count) AS occurrences, \
+         MAX(last_seen_at) AS last_seen_at",
+        project_id,
+        filter,
+        &format!(
+            "AND alert_type = 'FEATURE_DRIFT' AND feature_name IS NOT NULL GROUP BY feature_name \
+             ORDER BY occurrences DESC, alert_count DESC, feature_name LIMIT {}",
+            SUMMARY_TOP_FEATURES
+        ),
+    )
+    .await?;
+    let mut top_features = Vec::new();
+    for row in &feature_rows {
+        let last_seen_at_str: String = row.get("last_seen_at");
+        let last_seen_at = chrono::DateTime::parse_from_rfc3339(&last_seen_at_str)
             .map_err(|e| anyhow::anyhow!("Failed to parse date: {}", e))?
             .with_timezone(&chrono::Utc);
-
-        alerts.push(Alert {
-            alert_id: row.get("alert_id"),
-            project_id: row.get("project_id"),
-            baseline_version: row.get("baseline_version"),
-            created_at,
-            severity: row.get("severity"),
-            alert_type: row.get("alert_type"),
+        top_features.push(FeatureAlertCount {
             feature_name: row.get("feature_name"),
-            metric_value: row.get("metric_value"),
-            message: row.get("message"),
-            status: row.get("status"),
+            alert_count: row.get("alert_count"),
+            occurrences: row.get("occurrences"),
+            last_seen_at,
         });
     }
 
-    Ok(alerts)
+    let ack_rows = fetch_filtered(
+        pool,
+        "SELECT AVG((julianday(acked_at) - julianday(created_at)) * 86400.0) AS mean_seconds, \
+         COUNT(*) AS acked_alerts",
+        project_id,
+        filter,
+        "AND acked_at IS NOT NULL",
+    )
+    .await?;
+    let (mean_time_to_ack_seconds, acked_alerts) = match ack_rows.first() {
+        Some(row) => (row.get("mean_seconds"), row.get("acked_alerts")),
+        None => (None, 0),
+    };
+
+    let day_rows = fetch_filtered(
+        pool,
+        "SELECT substr(created_at, 1, 10) AS day, COUNT(*) AS alert_count, \
+         SUM(severity = 'CRITICAL') AS critical, SUM(severity = 'WARN') AS warn",
+        project_id,
+        filter,
+        "GROUP BY day ORDER BY day",
+    )
+    .await?;
+    let mut alerts_per_day: Vec<DailyAlertCount> = Vec::new();
+    for row in &day_rows {
+        let day: String = row.get("day");
+        let date = chrono::NaiveDate::parse_from_str(&day, "%Y-%m-%d")
+            .map_err(|e| anyhow::anyhow!("Failed to parse date: {}", e))?;
+        if let Some(last) = alerts_per_day.last() {
+            let mut gap = last.date.succ_opt();
+            while let Some(missing) = gap.filter(|d| *d < date) {
+                alerts_per_day.push(DailyAlertCount {
+                    date: missing,
+                    alerts: 0,
+                    critical: 0,
+                    warn: 0,
+                });
+                gap = missing.succ_opt();
+            }
+        }
+        alerts_per_day.push(DailyAlertCount {
+            date,
+            alerts: row.get("alert_count"),
+            critical: row.get("critical"),
+            warn: row.get("warn"),
+        });
+    }
+
+    Ok(AlertSummary {
+        open_by_severity,
+        open_by_type,
+        top_features,
+        mean_time_to_ack_seconds,
+        acked_alerts,
+        alerts_per_day,
+    })
+}
+
+pub async fn alert_exists(
+    pool: &SqlitePool,
+    project_id: &str,
+    alert_id: &str,
+) -> anyhow::Result<bool> {
+    let mut conn = pool.acquire().await?;
+    Ok(alert_status(&mut conn, project_id, alert_id)
+        .await?
+        .is_some())
+}
+
+/// Acknowledges a project's alert if it is open or snoozed.
+pub async fn ack_alert(
+    pool: &SqlitePool,
+    project_id: &str,
+    alert_id: &str,
+    actor: &str,
+) -> anyhow::Result<AlertTransition> {
+    let mut tx = pool.begin().await?;
+    let from_status = match transition_from(&mut tx, project_id, alert_id, AlertAction::Ack).await?
+    {
+        Ok(from_status) => from_status,
+        Err(outcome) => return Ok(outcome),
+    };
+    mark_acked(&mut tx, alert_id, &from_status, actor).await?;
+    tx.commit().await?;
+
+    Ok(AlertTransition::Applied)
 }
 
-pub async fn ack_alert(pool: &SqlitePool, alert_id: &str) -> anyhow::Result<()> {
+async fn mark_acked(
+    conn: &mut SqliteConnection,
+    alert_id: &str,
+    from_status: &str,
+    actor: &str,
+) -> anyhow::Result<()> {
+    let acked_at_str = Utc::now().to_rfc3339();
     sqlx::query!(
         r#"
-        UPDATE alerts SET status = 'ACK' WHERE alert_id = ?1
+        UPDATE alerts SET status = 'ACK', acked_at = ?2, snoozed_until = NULL WHERE alert_id = ?1
         "#,
-        alert_id
+        alert_id,
+        acked_at_str
     )
-    .execute(pool)
+    .execute(&mut *conn)
     .await?;
+    alert_events::insert_event(
+        conn,
+        alert_id,
+        "ACK",
+        actor,
+        Some(from_status),
+        Some("ACK"),
+        None,
+    )
+    .await
+}
 
-    Ok(())
+/// Resolves a project's alert unless it is already resolved.
+pub async fn resolve_alert(
+    pool: &SqlitePool,
+    project_id: &str,
+    alert_id: &str,
+    resolved_by: &str,
+    note: Option<&str>,
+) -> anyhow::Result<AlertTransition> {
+    let mut tx = pool.begin().await?;
+    let from_status =
+        match transition_from(&mut tx, project_id, alert_id, AlertAction::Resolve).await? {
+            Ok(from_status) => from_status,
+            Err(outcome) => return Ok(outcome),
+        };
+    mark_resolved(&mut tx, alert_id, &from_status, resolved_by, note).await?;
+    tx.commit().await?;
+
+    Ok(AlertTransition::Applied)
+}
+
+async fn mark_resolved(
+    conn: &mut SqliteConnection,
+    alert_id: &str,
+    from_status: &str,
+    resolved_by: &str,
+    note: Option<&str>,
+) -> anyhow::Result<()> {
+    let resolved_at_str = Utc::now().to_rfc3339();
+    sqlx::query!(
+        r#"
+        UPDATE alerts
+        SET status = 'RESOLVED', resolved_at = ?2, resolved_by = ?3, resolution_note = ?4, snoozed_until = NULL
+        WHERE alert_id = ?1
+        "#,
+        alert_id,
+        resolved_at_str,
+        resolved_by,
+        note
+    )
+    .execute(&mut *conn)
+    .await?;
+    alert_events::insert_event(
+        conn,
+        alert_id,
+        "RESOLVED",
+        resolved_by,
+        Some(from_status),
+        Some("RESOLVED"),
+        note,
+    )
+    .await
+}
+
+/// Resolves the project's unresolved alerts of one type and feature, returning their ids.
+pub async fn resolve_matching_alerts(
+    conn: impl Acquire<'_, Database = Sqlite>,
+    project_id: &str,
+    alert_type: &str,
+    feature_name: Option<&str>,
+    resolved_by: &str,
+    note: &str,
+) -> anyhow::Result<Vec<String>> {
+    let mut tx = conn.begin().await?;
+    let rows = sqlx::query!(
+        r#"
+        SELECT alert_id AS "alert_id!", status
+        FROM alerts
+        WHERE project_id = ?1 AND alert_type = ?2 AND feature_name IS ?3 AND status IN ('OPEN', 'ACK', 'SNOOZED')
+        "#,
+        project_id,
+        alert_type,
+        feature_name
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+
+    for row in &rows {
+        mark_resolved(&mut tx, &row.alert_id, &row.status, resolved_by, Some(note)).await?;
+    }
+    tx.commit().await?;
+
+    Ok(rows.into_iter().map(|row| row.alert_id).collect())
+}
+
+/// Acks or resolves every alert of the project that matches `filter` and can make that
+/// transition, in one transaction. Returns the ids of the alerts that changed.
+pub async fn bulk_update_alerts(
+    pool: &SqlitePool,
+    project_id: &str,
+    filter: &AlertFilter,
+    action: BulkAlertAction,
+    actor: &str,
+    note: Option<&str>,
+) -> anyhow::Result<Vec<String>> {
+    let statuses = AlertAction::from(action)
+        .allowed_statuses()
+        .iter()
+        .map(|s| format!("'{}'", s))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let mut query = format!(
+        "SELECT alert_id, status FROM alerts WHERE project_id = ?1 AND status IN ({})",
+        statuses
+    );
+    let mut params: Vec<String> = vec![project_id.to_string()];
+    push_filter_conditions(&mut query, &mut params, filter);
+
+    let mut tx = pool.begin().await?;
+    let mut sql_query = sqlx::query(&query);
+    for param in params {
+        sql_query = sql_query.bind(param);
+    }
+    let rows = sql_query.fetch_all(&mut *tx).await?;
+
+    let mut alert_ids = Vec::with_capacity(rows.len());
+    for row in &rows {
+        let alert_id: String = row.get("alert_id");
+        let from_status: String = row.get("status");
+        match action {
+            BulkAlertAction::Ack => mark_acked(&mut tx, &alert_id, &from_status, actor).await?,
+            BulkAlertAction::Resolve => {
+                mark_resolved(&mut tx, &alert_id, &from_status, actor, note).await?
+            }
+        }
+        alert_ids.push(alert_id);
+    }
+    tx.commit().await?;
+
+    Ok(alert_ids)
+}
+
+/// Snoozes a project's alert until `until` unless it is resolved.
+pub async fn snooze_alert(
+    pool: &SqlitePool,
+    project_id: &str,
+    alert_id: &str,
+    until: DateTime<Utc>,
+    actor: &str,
+    note: Option<&str>,
+) -> anyhow::Result<AlertTransition> {
+    let mut tx = pool.begin().await?;
+    let from_status =
+        match transition_from(&mut tx, project_id, alert_id, AlertAction::Snooze).await? {
+            Ok(from_status) => from_status,
+            Err(outcome) => return Ok(outcome),
+        };
+
+    let until_str = until.to_rfc3339();
+    sqlx::query!(
+        r#"
+        UPDATE alerts SET status = 'SNOOZED', snoozed_until = ?2 WHERE alert_id = ?1
+        "#,
+        alert_id,
+        until_str
+    )
+    .execute(&mut *tx)
+    .await?;
+    let details = match note {
+        Some(note) => format!("Until {}: {}", until_str, note),
+        None => format!("Until {}", until_str),
+    };
+    alert_events::insert_event(
+        &mut tx,
+        alert_id,
+        "SNOOZED",
+        actor,
+        Some(&from_status),
+        Some("SNOOZED"),
+        Some(&details),
+    )
+    .await?;
+    tx.commit().await?;
+
+    Ok(AlertTransition::Applied)
+}
+
+/// Assigns a project's alert to someone, or unassigns it when `assignee` is None.
+/// Returns false if the project has no such alert.
+pub async fn assign_alert(
+    pool: &SqlitePool,
+    project_id: &str,
+    alert_id: &str,
+    assignee: Option<&str>,
+    actor: &str,
+) -> anyhow::Result<bool> {
+    let mut tx = pool.begin().await?;
+    if alert_status(&mut tx, project_id, alert_id).await?.is_none() {
+        return Ok(false);
+    }
+
+    sqlx::query!(
+        r#"
+        UPDATE alerts SET assignee = ?2 WHERE alert_id = ?1
+        "#,
+        alert_id,
+        assignee
+    )
+    .execute(&mut *tx)
+    .await?;
+    let details = match assignee {
+        Some(assignee) => format!("Assigned to {}", assignee),
+        None => "Unassigned".to_string(),
+    };
+    alert_events::insert_event(
+        &mut tx,
+        alert_id,
+        "ASSIGNED",
+        actor,
+        None,
+        None,
+        Some(&details),
+    )
+    .await?;
+    tx.commit().await?;
+
+    Ok(true)
+}
+
+/// Comments on a project's alert. Returns false if the project has no such alert.
+pub async fn add_comment(
+    pool: &SqlitePool,
+    project_id: &str,
+    alert_id: &str,
+    actor: &str,
+    comment: &str,
+) -> anyhow::Result<bool> {
+    let mut tx = pool.begin().await?;
+    if alert_status(&mut tx, project_id, alert_id).await?.is_none() {
+        return Ok(false);
+    }
+    alert_events::insert_event(
+        &mut tx,
+        alert_id,
+        "COMMENT",
+        actor,
+        None,
+        None,
+        Some(comment),
+    )
+    .await?;
+    tx.commit().await?;
+
+    Ok(true)
 }
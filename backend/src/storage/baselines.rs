@@ -7,6 +7,8 @@ pub async fn create_baseline(
     pool: &SqlitePool,
     project_id: &str,
     prediction_rate: f64,
+    confidence_stats: Option<&str>,
+    calibration: Option<&str>,
 ) -> anyhow::Result<Baseline> {
     // Get current max version for this project
     let max_version: Option<i64> = sqlx::query_scalar!(
@@ -26,14 +28,16 @@ pub async fn create_baseline(
 
     sqlx::query!(
         r#"
-        INSERT INTO baselines (baseline_id, project_id, baseline_version, prediction_rate, created_at)
-        VALUES (?1, ?2, ?3, ?4, ?5)
+        INSERT INTO baselines (baseline_id, project_id, baseline_version, prediction_rate, created_at, confidence_stats, calibration)
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
         "#,
         baseline_id,
         project_id,
         baseline_version,
         prediction_rate,
-        created_at_str
+        created_at_str,
+        confidence_stats,
+        calibration
     )
     .execute(pool)
     .await?;
@@ -44,6 +48,8 @@ pub async fn create_baseline(
         baseline_version,
         prediction_rate,
         created_at,
+        confidence_stats: confidence_stats.map(str::to_string),
+        calibration: calibration.map(str::to_string),
     })
 }
 
@@ -53,7 +59,7 @@ pub async fn get_latest_baseline(
 ) -> anyhow::Result<Option<Baseline>> {
     let row = sqlx::query!(
         r#"
-        SELECT baseline_id, project_id, baseline_version, prediction_rate, created_at
+        SELECT baseline_id, project_id, baseline_version, prediction_rate, created_at, confidence_stats, calibration
         FROM baselines
         WHERE project_id = ?1
         ORDER BY baseline_version DESC
@@ -76,6 +82,8 @@ pub async fn get_latest_baseline(
             baseline_version: row.baseline_version,
             prediction_rate: row.prediction_rate,
             created_at,
+            confidence_stats: row.confidence_stats,
+            calibration: row.calibration,
         }))
     } else {
         Ok(None)
@@ -0,0 +1,215 @@
+use crate::models::{CreateEvaluationParams, Evaluation, EvaluationDetail, EvaluationFeature};
+use chrono::{DateTime, Utc};
+use sqlx::{Acquire, Sqlite, SqlitePool};
+use uuid::Uuid;
+
+pub async fn create_evaluation(
+    conn: impl Acquire<'_, Database = Sqlite>,
+    params: &CreateEvaluationParams,
+) -> anyhow::Result<EvaluationDetail> {
+    let evaluation_id = Uuid::new_v4().to_string();
+    let created_at = Utc::now();
+    let created_at_str = created_at.to_rfc3339();
+    let row_count = params.row_count as i64;
+    let alerts_created = params.alerts_created as i64;
+    let drift_metric = params.drift_metric.as_str();
+
+    let mut tx = conn.begin().await?;
+    sqlx::query!(
+        r#"
+        INSERT INTO evaluations (evaluation_id, project_id, baseline_version, created_at, row_count, prediction_rate, prediction_shift, drift_metric, health, alerts_created, content_hash)
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)
+        "#,
+        evaluation_id,
+        params.project_id,
+        params.baseline_version,
+        created_at_str,
+        row_count,
+        params.prediction_rate,
+        params.prediction_shift,
+        drift_metric,
+        params.health,
+        alerts_created,
+        params.content_hash
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    for feature in &params.features {
+        let sample_size = feature.sample_size as i64;
+        sqlx::query!(
+            r#"
+            INSERT INTO evaluation_features (evaluation_id, feature_name, drift_score, sample_size, p_value, null_rate, unexpected_fraction)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+            "#,
+            evaluation_id,
+            feature.feature_name,
+            feature.drift_score,
+            sample_size,
+            feature.p_value,
+            feature.null_rate,
+            feature.unexpected_fraction
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+    tx.commit().await?;
+
+    Ok(EvaluationDetail {
+        evaluation: Evaluation {
+            evaluation_id,
+            project_id: params.project_id.clone(),
+            baseline_version: params.baseline_version,
+            created_at,
+            row_count: params.row_count,
+            prediction_rate: params.prediction_rate,
+            prediction_shift: params.prediction_shift,
+            drift_metric: params.drift_metric,
+            health: params.health.clone(),
+            alerts_created: params.alerts_created,
+            content_hash: params.content_hash.clone(),
+        },
+        features: params.features.clone(),
+    })
+}
+
+pub async fn list_evaluations(
+    pool: &SqlitePool,
+    project_id: &str,
+) -> anyhow::Result<Vec<Evaluation>> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT evaluation_id, project_id, baseline_version, created_at, row_count, prediction_rate, prediction_shift, drift_metric, health, alerts_created, content_hash
+        FROM evaluations
+        WHERE project_id = ?1
+        ORDER BY created_at DESC
+        "#,
+        project_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut evaluations = Vec::new();
+    for row in rows {
+        let created_at = chrono::DateTime::parse_from_rfc3339(&row.created_at)
+            .map_err(|e| anyhow::anyhow!("Failed to parse date: {}", e))?
+            .with_timezone(&chrono::Utc);
+
+        evaluations.push(Evaluation {
+            evaluation_id: row.evaluation_id.expect("evaluation_id should not be null"),
+            project_id: row.project_id,
+            baseline_version: row.baseline_version,
+            created_at,
+            row_count: row.row_count as usize,
+            prediction_rate: row.prediction_rate,
+            prediction_shift: row.prediction_shift,
+            drift_metric: row.drift_metric.parse()?,
+            health: row.health,
+            alerts_created: row.alerts_created as usize,
+            content_hash: row.content_hash,
+        });
+    }
+
+    Ok(evaluations)
+}
+
+pub async fn get_evaluation(
+    pool: &SqlitePool,
+    project_id: &str,
+    evaluation_id: &str,
+) -> anyhow::Result<Option<EvaluationDetail>> {
+    let row = sqlx::query!(
+        r#"
+        SELECT evaluation_id, project_id, baseline_version, created_at, row_count, prediction_rate, prediction_shift, drift_metric, health, alerts_created, content_hash
+        FROM evaluations
+        WHERE project_id = ?1 AND evaluation_id = ?2
+        "#,
+        project_id,
+        evaluation_id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    let Some(row) = row else {
+        return Ok(None);
+    };
+
+    let created_at = chrono::DateTime::parse_from_rfc3339(&row.created_at)
+        .map_err(|e| anyhow::anyhow!("Failed to parse date: {}", e))?
+        .with_timezone(&chrono::Utc);
+
+    let feature_rows = sqlx::query!(
+        r#"
+        SELECT feature_name, drift_score, sample_size, p_value, null_rate, unexpected_fraction
+        FROM evaluation_features
+        WHERE evaluation_id = ?1
+        ORDER BY feature_name
+        "#,
+        evaluation_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let features = feature_rows
+        .into_iter()
+        .map(|f| EvaluationFeature {
+            feature_name: f.feature_name,
+            drift_score: f.drift_score,
+            sample_size: f.sample_size as usize,
+            p_value: f.p_value,
+            null_rate: f.null_rate,
+            unexpected_fraction: f.unexpected_fraction,
+        })
+        .collect();
+
+    Ok(Some(EvaluationDetail {
+        evaluation: Evaluation {
+            evaluation_id: row.evaluation_id.expect("evaluation_id should not be null"),
+            project_id: row.project_id,
+            baseline_version: row.baseline_version,
+            created_at,
+            row_count: row.row_count as usize,
+            prediction_rate: row.prediction_rate,
+            prediction_shift: row.prediction_shift,
+            drift_metric: row.drift_metric.parse()?,
+            health: row.health,
+            alerts_created: row.alerts_created as usize,
+            content_hash: row.content_hash,
+        },
+        features,
+    }))
+}
+
+/// Up to `limit` evaluations created before `before` (or the latest ones), most recent first.
+pub async fn list_recent_evaluation_details(
+    pool: &SqlitePool,
+    project_id: &str,
+    before: Option<DateTime<Utc>>,
+    limit: usize,
+) -> anyhow::Result<Vec<EvaluationDetail>> {
+    let before_str = before.map(|t| t.to_rfc3339());
+    let limit = limit as i64;
+    let evaluation_ids = sqlx::query_scalar!(
+        r#"
+        SELECT evaluation_id AS "evaluation_id!"
+        FROM evaluations
+        WHERE project_id = ?1 AND (?2 IS NULL OR created_at < ?2)
+        ORDER BY created_at DESC
+        LIMIT ?3
+        "#,
+        project_id,
+        before_str,
+        limit
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut details = Vec::new();
+    for evaluation_id in evaluation_ids {
+        if let Some(detail) = get_evaluation(pool, project_id, &evaluation_id).await? {
+            details.push(detail);
+        }
+    }
+
+    Ok(details)
+}
@@ -0,0 +1,81 @@
+use crate::models::FeatureHistoryPoint;
+use chrono::{DateTime, Utc};
+use sqlx::SqlitePool;
+
+/// Drift of one feature across a project's evaluations, oldest first.
+pub async fn get_feature_history(
+    pool: &SqlitePool,
+    project_id: &str,
+    feature_name: &str,
+    start_time: Option<DateTime<Utc>>,
+    end_time: Option<DateTime<Utc>>,
+) -> anyhow::Result<Vec<FeatureHistoryPoint>> {
+    let start_time_str = start_time.map(|t| t.to_rfc3339());
+    let end_time_str = end_time.map(|t| t.to_rfc3339());
+
+    let rows = sqlx::query!(
+        r#"
+        SELECT e.evaluation_id, e.created_at, e.drift_metric, e.baseline_version, f.drift_score, f.sample_size
+        FROM evaluation_features f
+        JOIN evaluations e ON e.evaluation_id = f.evaluation_id
+        WHERE e.project_id = ?1
+          AND f.feature_name = ?2
+          AND (?3 IS NULL OR e.created_at >= ?3)
+          AND (?4 IS NULL OR e.created_at <= ?4)
+        ORDER BY e.created_at ASC
+        "#,
+        project_id,
+        feature_name,
+        start_time_str,
+        end_time_str
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut points = Vec::new();
+    for row in rows {
+        let created_at = chrono::DateTime::parse_from_rfc3339(&row.created_at)
+            .map_err(|e| anyhow::anyhow!("Failed to parse date: {}", e))?
+            .with_timezone(&chrono::Utc);
+
+        points.push(FeatureHistoryPoint {
+            evaluation_id: row.evaluation_id,
+            created_at,
+            drift_score: row.drift_score,
+            max_drift_score: row.drift_score,
+            drift_metric: row.drift_metric.parse()?,
+            sample_size: row.sample_size as usize,
+            baseline_version: row.baseline_version,
+            evaluations: 1,
+        });
+    }
+
+    Ok(points)
+}
+
+/// Whether a feature appears in any of the project's baselines or evaluations.
+pub async fn feature_exists(
+    pool: &SqlitePool,
+    project_id: &str,
+    feature_name: &str,
+) -> anyhow::Result<bool> {
+    let exists = sqlx::query_scalar!(
+        r#"
+        SELECT EXISTS (
+            SELECT 1 FROM baseline_features f
+            JOIN baselines b ON b.baseline_id = f.baseline_id
+            WHERE b.project_id = ?1 AND f.feature_name = ?2
+            UNION ALL
+            SELECT 1 FROM evaluation_features f
+            JOIN evaluations e ON e.evaluation_id = f.evaluation_id
+            WHERE e.project_id = ?1 AND f.feature_name = ?2
+        ) AS "exists!: bool"
+        "#,
+        project_id,
+        feature_name
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(exists)
+}
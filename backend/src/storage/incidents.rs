@@ -0,0 +1,164 @@
+use crate::models::Incident;
+use chrono::{Duration, Utc};
+use sqlx::sqlite::SqliteRow;
+use sqlx::{Acquire, Row, Sqlite, SqlitePool};
+use uuid::Uuid;
+
+/// Puts newly raised alerts into an incident: the project's latest unresolved incident
+/// if it opened less than `window_minutes` ago, otherwise a new one. Returns its id.
+pub async fn group_alerts(
+    conn: impl Acquire<'_, Database = Sqlite>,
+    project_id: &str,
+    alert_ids: &[String],
+    window_minutes: i64,
+) -> anyhow::Result<String> {
+    let now = Utc::now();
+    let mut tx = conn.begin().await?;
+
+    let mut existing = None;
+    if window_minutes > 0 {
+        let since = (now - Duration::minutes(window_minutes)).to_rfc3339();
+        existing = sqlx::query_scalar!(
+            r#"
+            SELECT i.incident_id as "incident_id!"
+            FROM incidents i
+            WHERE i.project_id = ?1 AND i.created_at >= ?2
+              AND EXISTS (SELECT 1 FROM alerts a WHERE a.incident_id = i.incident_id AND a.status != 'RESOLVED')
+            ORDER BY i.created_at DESC
+            LIMIT 1
+            "#,
+            project_id,
+            since
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+    }
+
+    let incident_id = match existing {
+        Some(incident_id) => incident_id,
+        None => {
+            let incident_id = Uuid::new_v4().to_string();
+            let created_at_str = now.to_rfc3339();
+            sqlx::query!(
+                r#"
+                INSERT INTO incidents (incident_id, project_id, created_at)
+                VALUES (?1, ?2, ?3)
+                "#,
+                incident_id,
+                project_id,
+                created_at_str
+            )
+            .execute(&mut *tx)
+            .await?;
+            incident_id
+        }
+    };
+
+    for alert_id in alert_ids {
+        sqlx::query!(
+            r#"
+            UPDATE alerts SET incident_id = ?1 WHERE alert_id = ?2 AND project_id = ?3
+            "#,
+            incident_id,
+            alert_id,
+            project_id
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+    tx.commit().await?;
+
+    Ok(incident_id)
+}
+
+// Severity, status and counts are aggregated from the incident's alerts
+const INCIDENT_QUERY: &str = "SELECT i.incident_id, i.project_id, i.created_at, \
+     COUNT(a.alert_id) AS alert_count, \
+     MAX(CASE a.severity WHEN 'CRITICAL' THEN 2 WHEN 'WARN' THEN 1 ELSE 0 END) AS severity_rank, \
+     SUM(a.status = 'OPEN') AS open_alerts, \
+     SUM(a.status != 'RESOLVED') AS unresolved_alerts, \
+     GROUP_CONCAT(DISTINCT a.alert_type) AS alert_types, \
+     MAX(a.last_seen_at) AS last_seen_at \
+     FROM incidents i JOIN alerts a ON a.incident_id = i.incident_id \
+     WHERE i.project_id = ?1";
+
+fn incident_from_row(row: &SqliteRow) -> anyhow::Result<Incident> {
+    let created_at_str: String = row.get("created_at");
+    let created_at = chrono::DateTime::parse_from_rfc3339(&created_at_str)
+        .map_err(|e| anyhow::anyhow!("Failed to parse date: {}", e))?
+        .with_timezone(&chrono::Utc);
+    let last_seen_at_str: String = row.get("last_seen_at");
+    let last_seen_at = chrono::DateTime::parse_from_rfc3339(&last_seen_at_str)
+        .map_err(|e| anyhow::anyhow!("Failed to parse date: {}", e))?
+        .with_timezone(&chrono::Utc);
+
+    let severity_rank: i64 = row.get("severity_rank");
+    let open_alerts: i64 = row.get("open_alerts");
+    let unresolved_alerts: i64 = row.get("unresolved_alerts");
+    let status = if unresolved_alerts == 0 {
+        "RESOLVED"
+    } else if open_alerts > 0 {
+        "OPEN"
+    } else {
+        "ACK"
+    };
+    let severity = if severity_rank >= 2 {
+        "CRITICAL"
+    } else {
+        "WARN"
+    };
+    let alert_types: String = row.get("alert_types");
+
+    Ok(Incident {
+        incident_id: row.get("incident_id"),
+        project_id: row.get("project_id"),
+        severity: severity.to_string(),
+        status: status.to_string(),
+        alert_count: row.get("alert_count"),
+        open_alerts,
+        alert_types: alert_types.split(',').map(str::to_string).collect(),
+        created_at,
+        last_seen_at,
+    })
+}
+
+/// The project's incidents, newest first, optionally only those with `status`.
+pub async fn list_incidents(
+    pool: &SqlitePool,
+    project_id: &str,
+    status: Option<&str>,
+) -> anyhow::Result<Vec<Incident>> {
+    let query = format!(
+        "{} GROUP BY i.incident_id ORDER BY i.created_at DESC",
+        INCIDENT_QUERY
+    );
+    let rows = sqlx::query(&query).bind(project_id).fetch_all(pool).await?;
+
+    let mut incidents = Vec::new();
+    for row in &rows {
+        let incident = incident_from_row(row)?;
+        if status.is_none_or(|status| incident.status == status) {
+            incidents.push(incident);
+        }
+    }
+
+    Ok(incidents)
+}
+
+pub async fn get_incident(
+    pool: &SqlitePool,
+    project_id: &str,
+    incident_id: &str,
+) -> anyhow::Result<Option<Incident>> {
+    let query = format!(
+        "{} AND i.incident_id = ?2 GROUP BY i.incident_id",
+        INCIDENT_QUERY
+    );
+    let row = sqlx::query(&query)
+        .bind(project_id)
+        .bind(incident_id)
+        .fetch_optional(pool)
+        .await?;
+
+    row.as_ref().map(incident_from_row).transpose()
+}
@@ -1,3 +1,11 @@
+pub mod alert_events;
 pub mod alerts;
 pub mod baselines;
+pub mod evaluations;
+pub mod history;
+pub mod incidents;
+pub mod notifications;
 pub mod projects;
+pub mod settings;
+pub mod webhooks;
+pub mod windows;
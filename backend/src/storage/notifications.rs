@@ -0,0 +1,118 @@
+use crate::models::NotificationSettings;
+use chrono::{DateTime, Utc};
+use sqlx::SqlitePool;
+
+pub async fn get_notification_settings(
+    pool: &SqlitePool,
+    project_id: &str,
+) -> anyhow::Result<NotificationSettings> {
+    let row = sqlx::query!(
+        r#"
+        SELECT project_id, email_recipients, email_immediate, email_digest, digest_hour,
+               last_digest_at, updated_at
+        FROM notification_settings
+        WHERE project_id = ?1
+        "#,
+        project_id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    let Some(row) = row else {
+        // Projects without a settings row use the defaults
+        return Ok(NotificationSettings::defaults(project_id));
+    };
+    let updated_at = chrono::DateTime::parse_from_rfc3339(&row.updated_at)
+        .map_err(|e| anyhow::anyhow!("Failed to parse date: {}", e))?
+        .with_timezone(&chrono::Utc);
+    let last_digest_at = row
+        .last_digest_at
+        .map(|date| {
+            chrono::DateTime::parse_from_rfc3339(&date)
+                .map(|date| date.with_timezone(&chrono::Utc))
+                .map_err(|e| anyhow::anyhow!("Failed to parse date: {}", e))
+        })
+        .transpose()?;
+
+    Ok(NotificationSettings {
+        project_id: project_id.to_string(),
+        email_recipients: serde_json::from_str(&row.email_recipients)?,
+        email_immediate: row.email_immediate,
+        email_digest: row.email_digest,
+        digest_hour: row.digest_hour,
+        last_digest_at,
+        updated_at: Some(updated_at),
+    })
+}
+
+/// Stores a project's notification settings, leaving the time of its last digest as is.
+pub async fn upsert_notification_settings(
+    pool: &SqlitePool,
+    settings: &NotificationSettings,
+) -> anyhow::Result<NotificationSettings> {
+    let updated_at = Utc::now();
+    let updated_at_str = updated_at.to_rfc3339();
+    let email_recipients = serde_json::to_string(&settings.email_recipients)?;
+
+    sqlx::query!(
+        r#"
+        INSERT INTO notification_settings (project_id, email_recipients, email_immediate, email_digest,
+                                           digest_hour, updated_at)
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+        ON CONFLICT(project_id) DO UPDATE SET
+            email_recipients = excluded.email_recipients,
+            email_immediate = excluded.email_immediate,
+            email_digest = excluded.email_digest,
+            digest_hour = excluded.digest_hour,
+            updated_at = excluded.updated_at
+        "#,
+        settings.project_id,
+        email_recipients,
+        settings.email_immediate,
+        settings.email_digest,
+        settings.digest_hour,
+        updated_at_str
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(NotificationSettings {
+        updated_at: Some(updated_at),
+        ..settings.clone()
+    })
+}
+
+pub async fn set_last_digest_at(
+    pool: &SqlitePool,
+    project_id: &str,
+    sent_at: DateTime<Utc>,
+) -> anyhow::Result<()> {
+    let sent_at_str = sent_at.to_rfc3339();
+
+    sqlx::query!(
+        r#"
+        UPDATE notification_settings SET last_digest_at = ?1 WHERE project_id = ?2
+        "#,
+        sent_at_str,
+        project_id
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Projects with the daily digest switched on, whether or not one is due.
+pub async fn list_digest_projects(pool: &SqlitePool) -> anyhow::Result<Vec<String>> {
+    let project_ids = sqlx::query_scalar!(
+        r#"
+        SELECT project_id as "project_id!" FROM notification_settings
+        WHERE email_digest = 1 AND email_recipients != '[]'
+        ORDER BY project_id
+        "#
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(project_ids)
+}
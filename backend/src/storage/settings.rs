@@ -0,0 +1,229 @@
+use crate::models::{AlertRule, FeatureSettings, ProjectSettings};
+use chrono::Utc;
+use sqlx::SqlitePool;
+
+pub async fn get_project_settings(
+    pool: &SqlitePool,
+    project_id: &str,
+) -> anyhow::Result<ProjectSettings> {
+    let row = sqlx::query!(
+        r#"
+        SELECT project_id, drift_metric, significance_level, null_rate_tolerance,
+               feature_drift_warn_threshold, feature_drift_critical_threshold,
+               prediction_shift_warn_threshold, prediction_shift_critical_threshold,
+               alert_cooldown_minutes, alert_resolve_hysteresis, incident_window_minutes, updated_at
+        FROM project_settings
+        WHERE project_id = ?1
+        "#,
+        project_id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    if let Some(row) = row {
+        let updated_at = chrono::DateTime::parse_from_rfc3339(&row.updated_at)
+            .map_err(|e| anyhow::anyhow!("Failed to parse date: {}", e))?
+            .with_timezone(&chrono::Utc);
+
+        Ok(ProjectSettings {
+            project_id: project_id.to_string(),
+            drift_metric: row.drift_metric.parse()?,
+            significance_level: row.significance_level,
+            null_rate_tolerance: row.null_rate_tolerance,
+            feature_drift_warn_threshold: row.feature_drift_warn_threshold,
+            feature_drift_critical_threshold: row.feature_drift_critical_threshold,
+            prediction_shift_warn_threshold: row.prediction_shift_warn_threshold,
+            prediction_shift_critical_threshold: row.prediction_shift_critical_threshold,
+            alert_cooldown_minutes: row.alert_cooldown_minutes,
+            alert_resolve_hysteresis: row.alert_resolve_hysteresis,
+            incident_window_minutes: row.incident_window_minutes,
+            updated_at: Some(updated_at),
+        })
+    } else {
+        // Projects without a settings row use the defaults
+        Ok(ProjectSettings::defaults(project_id))
+    }
+}
+
+pub async fn upsert_project_settings(
+    pool: &SqlitePool,
+    settings: &ProjectSettings,
+) -> anyhow::Result<ProjectSettings> {
+    let updated_at = Utc::now();
+    let updated_at_str = updated_at.to_rfc3339();
+    let drift_metric = settings.drift_metric.as_str();
+
+    sqlx::query!(
+        r#"
+        INSERT INTO project_settings (project_id, drift_metric, significance_level, null_rate_tolerance,
+                                      feature_drift_warn_threshold, feature_drift_critical_threshold,
+                                      prediction_shift_warn_threshold, prediction_shift_critical_threshold,
+                                      alert_cooldown_minutes, alert_resolve_hysteresis, incident_window_minutes,
+                                      updated_at)
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)
+        ON CONFLICT(project_id) DO UPDATE SET
+            drift_metric = excluded.drift_metric,
+            significance_level = excluded.significance_level,
+            null_rate_tolerance = excluded.null_rate_tolerance,
+            feature_drift_warn_threshold = excluded.feature_drift_warn_threshold,
+            feature_drift_critical_threshold = excluded.feature_drift_critical_threshold,
+            prediction_shift_warn_threshold = excluded.prediction_shift_warn_threshold,
+            prediction_shift_critical_threshold = excluded.prediction_shift_critical_threshold,
+            alert_cooldown_minutes = excluded.alert_cooldown_minutes,
+            alert_resolve_hysteresis = excluded.alert_resolve_hysteresis,
+            incident_window_minutes = excluded.incident_window_minutes,
+            updated_at = excluded.updated_at
+        "#,
+        settings.project_id,
+        drift_metric,
+        settings.significance_level,
+        settings.null_rate_tolerance,
+        settings.feature_drift_warn_threshold,
+        settings.feature_drift_critical_threshold,
+        settings.prediction_shift_warn_threshold,
+        settings.prediction_shift_critical_threshold,
+        settings.alert_cooldown_minutes,
+        settings.alert_resolve_hysteresis,
+        settings.incident_window_minutes,
+        updated_at_str
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(ProjectSettings {
+        updated_at: Some(updated_at),
+        ..settings.clone()
+    })
+}
+
+pub async fn list_feature_settings(
+    pool: &SqlitePool,
+    project_id: &str,
+) -> anyhow::Result<Vec<FeatureSettings>> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT project_id, feature_name, drift_warn_threshold, drift_critical_threshold, updated_at
+        FROM feature_settings
+        WHERE project_id = ?1
+        ORDER BY feature_name
+        "#,
+        project_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut feature_settings = Vec::new();
+    for row in rows {
+        let updated_at = chrono::DateTime::parse_from_rfc3339(&row.updated_at)
+            .map_err(|e| anyhow::anyhow!("Failed to parse date: {}", e))?
+            .with_timezone(&chrono::Utc);
+
+        feature_settings.push(FeatureSettings {
+            project_id: row.project_id,
+            feature_name: row.feature_name,
+            drift_warn_threshold: row.drift_warn_threshold,
+            drift_critical_threshold: row.drift_critical_threshold,
+            updated_at: Some(updated_at),
+        });
+    }
+
+    Ok(feature_settings)
+}
+
+pub async fn upsert_feature_settings(
+    pool: &SqlitePool,
+    settings: &FeatureSettings,
+) -> anyhow::Result<FeatureSettings> {
+    let updated_at = Utc::now();
+    let updated_at_str = updated_at.to_rfc3339();
+
+    sqlx::query!(
+        r#"
+        INSERT INTO feature_settings (project_id, feature_name, drift_warn_threshold, drift_critical_threshold, updated_at)
+        VALUES (?1, ?2, ?3, ?4, ?5)
+        ON CONFLICT(project_id, feature_name) DO UPDATE SET
+            drift_warn_threshold = excluded.drift_warn_threshold,
+            drift_critical_threshold = excluded.drift_critical_threshold,
+            updated_at = excluded.updated_at
+        "#,
+        settings.project_id,
+        settings.feature_name,
+        settings.drift_warn_threshold,
+        settings.drift_critical_threshold,
+        updated_at_str
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(FeatureSettings {
+        updated_at: Some(updated_at),
+        ..settings.clone()
+    })
+}
+
+/// Returns false if the feature had no override.
+pub async fn delete_feature_settings(
+    pool: &SqlitePool,
+    project_id: &str,
+    feature_name: &str,
+) -> anyhow::Result<bool> {
+    let result = sqlx::query!(
+        r#"
+        DELETE FROM feature_settings WHERE project_id = ?1 AND feature_name = ?2
+        "#,
+        project_id,
+        feature_name
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Returns None when the project uses the default rules.
+pub async fn get_alert_rules(
+    pool: &SqlitePool,
+    project_id: &str,
+) -> anyhow::Result<Option<Vec<AlertRule>>> {
+    let rules_json = sqlx::query_scalar!(
+        r#"
+        SELECT alert_rules FROM project_settings WHERE project_id = ?1
+        "#,
+        project_id
+    )
+    .fetch_optional(pool)
+    .await?
+    .flatten();
+
+    match rules_json {
+        Some(json) => Ok(Some(serde_json::from_str(&json)?)),
+        None => Ok(None),
+    }
+}
+
+/// Stores a project's rules; None goes back to the default rules.
+pub async fn set_alert_rules(
+    pool: &SqlitePool,
+    project_id: &str,
+    rules: Option<&[AlertRule]>,
+) -> anyhow::Result<()> {
+    let updated_at_str = Utc::now().to_rfc3339();
+    let rules_json = rules.map(serde_json::to_string).transpose()?;
+
+    sqlx::query!(
+        r#"
+        INSERT INTO project_settings (project_id, alert_rules, updated_at)
+        VALUES (?1, ?2, ?3)
+        ON CONFLICT(project_id) DO UPDATE SET
+            alert_rules = excluded.alert_rules,
+            updated_at = excluded.updated_at
+        "#,
+        project_id,
+        rules_json,
+        updated_at_str
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
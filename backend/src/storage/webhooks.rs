@@ -0,0 +1,215 @@
+use crate::models::{NotificationEvent, Webhook, WebhookDelivery, WebhookFormat};
+use chrono::Utc;
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+pub async fn create_webhook(
+    pool: &SqlitePool,
+    project_id: &str,
+    url: &str,
+    secret: &str,
+    min_severity: &str,
+    format: WebhookFormat,
+) -> anyhow::Result<Webhook> {
+    let webhook_id = Uuid::new_v4().to_string();
+    let format_str = format.as_str();
+    let created_at = Utc::now();
+    let created_at_str = created_at.to_rfc3339();
+
+    sqlx::query!(
+        r#"
+        INSERT INTO webhooks (webhook_id, project_id, url, secret, min_severity, format, created_at)
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+        "#,
+        webhook_id,
+        project_id,
+        url,
+        secret,
+        min_severity,
+        format_str,
+        created_at_str
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(Webhook {
+        webhook_id,
+        project_id: project_id.to_string(),
+        url: url.to_string(),
+        secret: secret.to_string(),
+        min_severity: min_severity.to_string(),
+        format,
+        created_at,
+    })
+}
+
+pub async fn list_webhooks(pool: &SqlitePool, project_id: &str) -> anyhow::Result<Vec<Webhook>> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT webhook_id, project_id, url, secret, min_severity, format, created_at
+        FROM webhooks
+        WHERE project_id = ?1
+        ORDER BY created_at ASC
+        "#,
+        project_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut webhooks = Vec::new();
+    for row in rows {
+        let created_at = chrono::DateTime::parse_from_rfc3339(&row.created_at)
+            .map_err(|e| anyhow::anyhow!("Failed to parse date: {}", e))?
+            .with_timezone(&chrono::Utc);
+
+        webhooks.push(Webhook {
+            webhook_id: row.webhook_id.expect("webhook_id should not be null"),
+            project_id: row.project_id,
+            url: row.url,
+            secret: row.secret,
+            min_severity: row.min_severity,
+            format: row.format.parse()?,
+            created_at,
+        });
+    }
+
+    Ok(webhooks)
+}
+
+pub async fn get_webhook(
+    pool: &SqlitePool,
+    project_id: &str,
+    webhook_id: &str,
+) -> anyhow::Result<Option<Webhook>> {
+    let row = sqlx::query!(
+        r#"
+        SELECT webhook_id, project_id, url, secret, min_severity, format, created_at
+        FROM webhooks
+        WHERE webhook_id = ?1 AND project_id = ?2
+        "#,
+        webhook_id,
+        project_id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    let Some(row) = row else {
+        return Ok(None);
+    };
+    let created_at = chrono::DateTime::parse_from_rfc3339(&row.created_at)
+        .map_err(|e| anyhow::anyhow!("Failed to parse date: {}", e))?
+        .with_timezone(&chrono::Utc);
+
+    Ok(Some(Webhook {
+        webhook_id: row.webhook_id.expect("webhook_id should not be null"),
+        project_id: row.project_id,
+        url: row.url,
+        secret: row.secret,
+        min_severity: row.min_severity,
+        format: row.format.parse()?,
+        created_at,
+    }))
+}
+
+/// Deletes a webhook and its delivery log. Returns false if the project has no such webhook.
+pub async fn delete_webhook(
+    pool: &SqlitePool,
+    project_id: &str,
+    webhook_id: &str,
+) -> anyhow::Result<bool> {
+    let mut tx = pool.begin().await?;
+    sqlx::query!(
+        r#"
+        DELETE FROM webhook_deliveries WHERE webhook_id = ?1 AND project_id = ?2
+        "#,
+        webhook_id,
+        project_id
+    )
+    .execute(&mut *tx)
+    .await?;
+    let result = sqlx::query!(
+        r#"
+        DELETE FROM webhooks WHERE webhook_id = ?1 AND project_id = ?2
+        "#,
+        webhook_id,
+        project_id
+    )
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+pub async fn create_delivery(pool: &SqlitePool, delivery: &WebhookDelivery) -> anyhow::Result<()> {
+    let event = delivery.event.as_str();
+    let payload = serde_json::to_string(&delivery.payload)?;
+    let created_at_str = delivery.created_at.to_rfc3339();
+
+    sqlx::query!(
+        r#"
+        INSERT INTO webhook_deliveries (delivery_id, webhook_id, project_id, alert_id, event, payload, status, attempts, response_status, error, created_at)
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)
+        "#,
+        delivery.delivery_id,
+        delivery.webhook_id,
+        delivery.project_id,
+        delivery.alert_id,
+        event,
+        payload,
+        delivery.status,
+        delivery.attempts,
+        delivery.response_status,
+        delivery.error,
+        created_at_str
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// The most recent deliveries of a webhook, newest first.
+pub async fn list_deliveries(
+    pool: &SqlitePool,
+    webhook_id: &str,
+    limit: i64,
+) -> anyhow::Result<Vec<WebhookDelivery>> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT delivery_id, webhook_id, project_id, alert_id, event, payload, status, attempts, response_status, error, created_at
+        FROM webhook_deliveries
+        WHERE webhook_id = ?1
+        ORDER BY created_at DESC
+        LIMIT ?2
+        "#,
+        webhook_id,
+        limit
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut deliveries = Vec::new();
+    for row in rows {
+        let created_at = chrono::DateTime::parse_from_rfc3339(&row.created_at)
+            .map_err(|e| anyhow::anyhow!("Failed to parse date: {}", e))?
+            .with_timezone(&chrono::Utc);
+        let event: NotificationEvent = row.event.parse()?;
+
+        deliveries.push(WebhookDelivery {
+            delivery_id: row.delivery_id.expect("delivery_id should not be null"),
+            webhook_id: row.webhook_id,
+            project_id: row.project_id,
+            alert_id: row.alert_id,
+            event,
+            payload: serde_json::from_str(&row.payload)?,
+            status: row.status,
+            attempts: row.attempts,
+            response_status: row.response_status,
+            error: row.error,
+            created_at,
+        });
+    }
+
+    Ok(deliveries)
+}
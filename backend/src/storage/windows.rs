@@ -0,0 +1,93 @@
+use crate::models::{DriftWindow, TimeWindow, WindowDrift};
+use chrono::Utc;
+use sqlx::{Acquire, Sqlite, SqlitePool};
+use uuid::Uuid;
+
+pub async fn create_drift_windows(
+    conn: impl Acquire<'_, Database = Sqlite>,
+    project_id: &str,
+    evaluation_id: &str,
+    baseline_version: i64,
+    window: TimeWindow,
+    windows: &[WindowDrift],
+) -> anyhow::Result<()> {
+    let created_at_str = Utc::now().to_rfc3339();
+    let window_str = window.as_str();
+
+    let mut tx = conn.begin().await?;
+    for window_drift in windows {
+        let window_id = Uuid::new_v4().to_string();
+        let window_start_str = window_drift.window_start.to_rfc3339();
+        let row_count = window_drift.row_count as i64;
+        let feature_scores = serde_json::to_string(&window_drift.feature_scores)?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO drift_windows (window_id, project_id, baseline_version, window, window_start, row_count, prediction_rate, prediction_shift, feature_scores, created_at, evaluation_id)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)
+            "#,
+            window_id,
+            project_id,
+            baseline_version,
+            window_str,
+            window_start_str,
+            row_count,
+            window_drift.prediction_rate,
+            window_drift.prediction_shift,
+            feature_scores,
+            created_at_str,
+            evaluation_id
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+    tx.commit().await?;
+
+    Ok(())
+}
+
+pub async fn list_drift_windows(
+    pool: &SqlitePool,
+    project_id: &str,
+    window: Option<TimeWindow>,
+) -> anyhow::Result<Vec<DriftWindow>> {
+    let window_str = window.map(|w| w.as_str());
+    let rows = sqlx::query!(
+        r#"
+        SELECT window_id, project_id, baseline_version, window, window_start, row_count, prediction_rate, prediction_shift, feature_scores, created_at, evaluation_id
+        FROM drift_windows
+        WHERE project_id = ?1 AND (?2 IS NULL OR window = ?2)
+        ORDER BY window_start ASC, created_at ASC
+        "#,
+        project_id,
+        window_str
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut windows = Vec::new();
+    for row in rows {
+        let window_start = chrono::DateTime::parse_from_rfc3339(&row.window_start)
+            .map_err(|e| anyhow::anyhow!("Failed to parse date: {}", e))?
+            .with_timezone(&chrono::Utc);
+        let created_at = chrono::DateTime::parse_from_rfc3339(&row.created_at)
+            .map_err(|e| anyhow::anyhow!("Failed to parse date: {}", e))?
+            .with_timezone(&chrono::Utc);
+
+        windows.push(DriftWindow {
+            window_id: row.window_id.expect("window_id should not be null"),
+            project_id: row.project_id,
+            evaluation_id: row.evaluation_id,
+            baseline_version: row.baseline_version,
+            window: row.window.parse()?,
+            window_start,
+            row_count: row.row_count as usize,
+            prediction_rate: row.prediction_rate,
+            prediction_shift: row.prediction_shift,
+            feature_scores: serde_json::from_str(&row.feature_scores)?,
+            created_at,
+        });
+    }
+
+    Ok(windows)
+}
@@ -0,0 +1,157 @@
+use crate::models::{BinningStrategy, CalibrationStats, NumericStats, ReliabilityBin};
+use crate::utils::csv_parser::{column_null_rate, histogram_counts, LABEL_COLUMN};
+use crate::utils::drift::parse_numeric;
+use std::collections::HashMap;
+
+/// Confidence is a probability, so it is always binned on fixed edges over [0, 1].
+const CONFIDENCE_BINS: usize = 10;
+
+fn confidence_edges() -> Vec<f64> {
+    (0..=CONFIDENCE_BINS)
+        .map(|i| i as f64 / CONFIDENCE_BINS as f64)
+        .collect()
+}
+
+fn parse_column(record: &HashMap<String, String>, column: &str) -> Option<f64> {
+    record.get(column).and_then(|v| parse_numeric(v))
+}
+
+/// Histogram of the `confidence` column, or None if the column has no values.
+pub fn build_confidence_histogram(records: &[HashMap<String, String>]) -> Option<NumericStats> {
+    let values: Vec<f64> = records
+        .iter()
+        .filter_map(|r| parse_column(r, "confidence"))
+        .collect();
+    if values.is_empty() {
+        return None;
+    }
+
+    let bins = confidence_edges();
+    let (underflow, bin_counts, overflow) = histogram_counts(&bins, &values);
+    let total = values.len() as f64;
+
+    Some(NumericStats {
+        bins,
+        probabilities: bin_counts.iter().map(|&c| c as f64 / total).collect(),
+        count: values.len(),
+        binning: BinningStrategy::EqualWidth,
+        underflow: underflow as f64 / total,
+        overflow: overflow as f64 / total,
+        null_rate: column_null_rate(records, "confidence"),
+    })
+}
+
+/// Reliability bins and expected calibration error (ECE) over rows that have both
+/// a `confidence` and a `label`. Returns None when no row is labelled.
+pub fn compute_calibration(records: &[HashMap<String, String>]) -> Option<CalibrationStats> {
+    let pairs: Vec<(f64, f64)> = records
+        .iter()
+        .filter_map(|r| {
+            Some((
+                parse_column(r, "confidence")?,
+                parse_column(r, LABEL_COLUMN)?,
+            ))
+        })
+        .collect();
+    if pairs.is_empty() {
+        return None;
+    }
+
+    let edges = confidence_edges();
+    let mut counts = [0usize; CONFIDENCE_BINS];
+    let mut confidence_sums = [0.0; CONFIDENCE_BINS];
+    let mut label_sums = [0.0; CONFIDENCE_BINS];
+    for (confidence, label) in &pairs {
+        let clamped = confidence.clamp(0.0, 1.0);
+        let bin = ((clamped * CONFIDENCE_BINS as f64).floor() as usize).min(CONFIDENCE_BINS - 1);
+        counts[bin] += 1;
+        confidence_sums[bin] += clamped;
+        label_sums[bin] += if *label > 0.0 { 1.0 } else { 0.0 };
+    }
+
+    let total = pairs.len() as f64;
+    let mut expected_calibration_error = 0.0;
+    let mut bins = Vec::new();
+    for i in 0..CONFIDENCE_BINS {
+        if counts[i] == 0 {
+            continue;
+        }
+        let count = counts[i] as f64;
+        let mean_confidence = confidence_sums[i] / count;
+        let observed_rate = label_sums[i] / count;
+        expected_calibration_error += count / total * (mean_confidence - observed_rate).abs();
+        bins.push(ReliabilityBin {
+            lower: edges[i],
+            upper: edges[i + 1],
+            count: counts[i],
+            mean_confidence,
+            observed_rate,
+        });
+    }
+
+    Some(CalibrationStats {
+        count: pairs.len(),
+        expected_calibration_error,
+        mean_confidence: confidence_sums.iter().sum::<f64>() / total,
+        observed_rate: label_sums.iter().sum::<f64>() / total,
+        bins,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(confidence: &str, label: Option<&str>) -> HashMap<String, String> {
+        let mut record = HashMap::new();
+        record.insert("confidence".to_string(), confidence.to_string());
+        if let Some(label) = label {
+            record.insert("label".to_string(), label.to_string());
+        }
+        record
+    }
+
+    #[test]
+    fn test_build_confidence_histogram() {
+        let records = vec![record("0.05", None), record("0.95", None), record("", None)];
+        let stats = build_confidence_histogram(&records).unwrap();
+
+        assert_eq!(stats.bins.len(), 11);
+        assert_eq!(stats.count, 2);
+        assert!((stats.probabilities[0] - 0.5).abs() < 1e-9);
+        assert!((stats.probabilities[9] - 0.5).abs() < 1e-9);
+        assert!(build_confidence_histogram(&[]).is_none());
+    }
+
+    #[test]
+    fn test_compute_calibration_perfect() {
+        // 80% confidence, 4 of 5 positive
+        let records: Vec<_> = ["1", "1", "1", "1", "0"]
+            .iter()
+            .map(|label| record("0.8", Some(label)))
+            .collect();
+        let calibration = compute_calibration(&records).unwrap();
+
+        assert_eq!(calibration.count, 5);
+        assert!(calibration.expected_calibration_error.abs() < 1e-9);
+        assert_eq!(calibration.bins.len(), 1);
+    }
+
+    #[test]
+    fn test_compute_calibration_overconfident() {
+        let records: Vec<_> = ["1", "0", "0", "0"]
+            .iter()
+            .map(|label| record("0.9", Some(label)))
+            .collect();
+        let calibration = compute_calibration(&records).unwrap();
+
+        assert!((calibration.expected_calibration_error - 0.65).abs() < 1e-9);
+        assert!(calibration.mean_confidence > calibration.observed_rate);
+    }
+
+    #[test]
+    fn test_compute_calibration_requires_labels() {
+        let records = vec![record("0.9", None)];
+        assert!(compute_calibration(&records).is_none());
+    }
+}
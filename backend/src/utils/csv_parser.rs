@@ -1,13 +1,28 @@
-use crate::models::{CategoricalStats, FeatureStats, FeatureStatsData, FeatureType, NumericStats};
+use crate::models::{
+    BinningConfig, BinningStrategy, CategoricalStats, FeatureStats, FeatureStatsData, FeatureType,
+    NumericStats,
+};
+use crate::utils::drift::parse_numeric;
 use csv::ReaderBuilder;
 use std::collections::HashMap;
 use std::io::Read;
 
 pub type CsvParseResult = (Vec<HashMap<String, String>>, Vec<String>);
 
-pub fn parse_csv<R: Read>(
-    reader: R,
-) -> anyhow::Result<CsvParseResult> {
+/// Columns with a fixed meaning that are never treated as features.
+pub const SPECIAL_COLUMNS: [&str; 3] = ["prediction", "confidence", "timestamp"];
+
+/// Ground truth for calibration. Only reserved in files that also have `confidence`,
+/// so elsewhere a column named `label` is still an ordinary feature.
+pub const LABEL_COLUMN: &str = "label";
+
+/// Whether `name` is a special column in a file with these `headers`.
+pub fn is_special_column(name: &str, headers: &[String]) -> bool {
+    SPECIAL_COLUMNS.contains(&name)
+        || (name == LABEL_COLUMN && headers.iter().any(|h| h == "confidence"))
+}
+
+pub fn parse_csv<R: Read>(reader: R) -> anyhow::Result<CsvParseResult> {
     let mut rdr = ReaderBuilder::new().has_headers(true).from_reader(reader);
 
     let headers = rdr.headers()?.clone();
@@ -35,10 +50,7 @@ pub fn infer_feature_types(
     let mut types = HashMap::new();
 
     for feature_name in feature_names {
-        if feature_name == "prediction"
-            || feature_name == "confidence"
-            || feature_name == "timestamp"
-        {
+        if is_special_column(feature_name, feature_names) {
             continue; // Skip special columns
         }
 
@@ -73,21 +85,19 @@ pub fn build_baseline_stats(
     records: &[HashMap<String, String>],
     feature_names: &[String],
     feature_types: &HashMap<String, FeatureType>,
+    binning: &BinningConfig,
 ) -> Vec<FeatureStats> {
     let mut stats = Vec::new();
 
     for feature_name in feature_names {
-        if feature_name == "prediction"
-            || feature_name == "confidence"
-            || feature_name == "timestamp"
-        {
+        if is_special_column(feature_name, feature_names) {
             continue;
         }
 
         let feature_type = feature_types.get(feature_name).unwrap();
         let feature_stat = match feature_type {
             FeatureType::Numeric => {
-                let numeric_stats = build_numeric_histogram(records, feature_name);
+                let numeric_stats = build_numeric_histogram(records, feature_name, binning);
                 FeatureStats {
                     name: feature_name.clone(),
                     feature_type: FeatureType::Numeric,
@@ -113,29 +123,60 @@ pub fn build_baseline_stats(
 fn build_numeric_histogram(
     records: &[HashMap<String, String>],
     feature_name: &str,
+    binning: &BinningConfig,
 ) -> NumericStats {
     let mut values: Vec<f64> = records
         .iter()
-        .filter_map(|r| {
-            r.get(feature_name)
-                .and_then(|v| v.trim().parse::<f64>().ok())
-        })
+        .filter_map(|r| r.get(feature_name).and_then(|v| parse_numeric(v)))
         .collect();
 
+    let null_rate = column_null_rate(records, feature_name);
+
     if values.is_empty() {
         return NumericStats {
             bins: vec![0.0, 1.0],
             probabilities: vec![1.0],
+            count: 0,
+            binning: binning.strategy,
+            underflow: 0.0,
+            overflow: 0.0,
+            null_rate,
         };
     }
 
-    values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    values.sort_by(f64::total_cmp);
 
+    let num_bins = binning.num_bins.max(1);
+    let bins = match binning.strategy {
+        BinningStrategy::EqualWidth => equal_width_edges(&values, num_bins),
+        BinningStrategy::Quantile => quantile_edges(&values, num_bins),
+    };
+
+    // Count values in each bin
+    let (underflow, bin_counts, overflow) = histogram_counts(&bins, &values);
+
+    // Convert to probabilities
+    let total = values.len() as f64;
+    let probabilities: Vec<f64> = bin_counts
+        .iter()
+        .map(|&count| count as f64 / total)
+        .collect();
+
+    NumericStats {
+        bins,
+        probabilities,
+        count: values.len(),
+        binning: binning.strategy,
+        underflow: underflow as f64 / total,
+        overflow: overflow as f64 / total,
+        null_rate,
+    }
+}
+
+/// Edges of `num_bins` equal-width bins between the min and max of sorted `values`.
+fn equal_width_edges(values: &[f64], num_bins: usize) -> Vec<f64> {
     let min = values[0];
     let max = values[values.len() - 1];
-
-    // Create 10 bins using min/max binning
-    let num_bins = 10;
     let bin_width = if max > min {
         (max - min) / num_bins as f64
     } else {
@@ -143,33 +184,80 @@ fn build_numeric_histogram(
     };
 
     let mut bins = vec![min];
-    for i in 1..=num_bins {
+    for i in 1..num_bins {
         bins.push(min + bin_width * i as f64);
     }
+    // Pin the last edge to the max so rounding can't push it into overflow
+    bins.push(if max > min {
+        max
+    } else {
+        min + bin_width * num_bins as f64
+    });
+    bins
+}
 
-    // Count values in each bin
-    let mut bin_counts = vec![0; num_bins];
-    for value in &values {
-        let bin_index = if bin_width > 0.0 {
-            ((value - min) / bin_width).floor() as usize
-        } else {
-            0
-        };
-        let bin_index = bin_index.min(num_bins - 1);
-        bin_counts[bin_index] += 1;
+/// Edges at the quantiles of sorted `values`, so each bin holds roughly the same
+/// number of baseline rows. Repeated values collapse duplicate edges, which can
+/// leave fewer than `num_bins` bins.
+fn quantile_edges(values: &[f64], num_bins: usize) -> Vec<f64> {
+    let last = values.len() - 1;
+    let mut bins: Vec<f64> = (0..=num_bins)
+        .map(|k| {
+            let position = k as f64 * last as f64 / num_bins as f64;
+            let lower = position.floor() as usize;
+            let upper = position.ceil() as usize;
+            let fraction = position - lower as f64;
+            values[lower] + (values[upper] - values[lower]) * fraction
+        })
+        .collect();
+    bins.dedup();
+
+    if bins.len() < 2 {
+        bins = vec![values[0], values[0] + 1.0];
     }
+    bins
+}
 
-    // Convert to probabilities
-    let total = values.len() as f64;
-    let probabilities: Vec<f64> = bin_counts
+/// Share of rows whose `column` is missing or empty, or None if there are no rows.
+/// Matches `drift::compute_null_rate` for incoming data; values that don't parse are
+/// type mismatches, not nulls.
+pub fn column_null_rate(records: &[HashMap<String, String>], column: &str) -> Option<f64> {
+    if records.is_empty() {
+        return None;
+    }
+    let nulls = records
         .iter()
-        .map(|&count| count as f64 / total)
-        .collect();
+        .filter(|r| r.get(column).is_none_or(|v| v.trim().is_empty()))
+        .count();
+    Some(nulls as f64 / records.len() as f64)
+}
 
-    NumericStats {
-        bins,
-        probabilities,
+/// Counts `values` against bin `edges`, returning (underflow, per-bin counts, overflow).
+/// Bins are half-open `[lower, upper)` except the last, which includes its upper edge.
+/// Non-finite values are not counted anywhere.
+pub fn histogram_counts(edges: &[f64], values: &[f64]) -> (usize, Vec<usize>, usize) {
+    let num_bins = edges.len().saturating_sub(1);
+    let mut underflow = 0;
+    let mut overflow = 0;
+    let mut bin_counts = vec![0; num_bins];
+    if num_bins == 0 {
+        return (underflow, bin_counts, overflow);
     }
+
+    let min = edges[0];
+    let max = edges[num_bins];
+    for value in values.iter().filter(|v| v.is_finite()) {
+        if *value < min {
+            underflow += 1;
+        } else if *value > max {
+            overflow += 1;
+        } else {
+            let bin_index = edges.partition_point(|edge| edge <= value) - 1;
+            bin_counts[bin_index.min(num_bins - 1)] += 1;
+        }
+    }
+
+    (underflow, bin_counts, overflow)
 }
 
 fn build_categorical_frequencies(
@@ -198,7 +286,13 @@ fn build_categorical_frequencies(
         HashMap::new()
     };
 
-    CategoricalStats { frequencies }
+    let null_rate = column_null_rate(records, feature_name);
+
+    CategoricalStats {
+        frequencies,
+        count: total,
+        null_rate,
+    }
 }
 
 pub fn compute_prediction_rate(records: &[HashMap<String, String>]) -> f64 {
@@ -247,6 +341,23 @@ mod tests {
         assert_eq!(types.get("location"), Some(&FeatureType::Categorical));
     }
 
+    #[test]
+    fn test_label_is_only_special_with_confidence() {
+        let headers = |names: &[&str]| names.iter().map(|n| n.to_string()).collect::<Vec<_>>();
+        let with_confidence = headers(&["prediction", "confidence", "label", "income"]);
+        let without_confidence = headers(&["prediction", "label", "income"]);
+
+        assert!(is_special_column("label", &with_confidence));
+        assert!(!is_special_column("label", &without_confidence));
+        assert!(is_special_column("prediction", &without_confidence));
+        assert!(!is_special_column("income", &with_confidence));
+
+        let mut record = HashMap::new();
+        record.insert("label".to_string(), "yes".to_string());
+        let types = infer_feature_types(&[record], &without_confidence);
+        assert_eq!(types.get("label"), Some(&FeatureType::Categorical));
+    }
+
     #[test]
     fn test_compute_prediction_rate() {
         let mut records = Vec::new();
@@ -276,8 +387,9 @@ mod tests {
             records.push(record);
         }
 
-        let stats = build_numeric_histogram(&records, "income");
+        let stats = build_numeric_histogram(&records, "income", &BinningConfig::default());
         assert_eq!(stats.bins.len(), 11); // 10 bins = 11 edges
+        assert_eq!(stats.count, 10);
         assert_eq!(stats.probabilities.len(), 10);
         // Probabilities should sum to ~1.0
         let sum: f64 = stats.probabilities.iter().sum();
@@ -298,5 +410,85 @@ mod tests {
         assert!((stats.frequencies.get("urban").unwrap() - 0.4).abs() < 0.001);
         assert!((stats.frequencies.get("suburban").unwrap() - 0.4).abs() < 0.001);
         assert!((stats.frequencies.get("rural").unwrap() - 0.2).abs() < 0.001);
+        assert_eq!(stats.null_rate, Some(0.0));
+    }
+
+    #[test]
+    fn test_baseline_null_rate() {
+        let mut records = Vec::new();
+        for value in ["25000", "", "  ", "30000", "n/a", "NaN"] {
+            let mut record = HashMap::new();
+            record.insert("income".to_string(), value.to_string());
+            record.insert(
+                "location".to_string(),
+                value.trim().replace("25000", "urban"),
+            );
+            records.push(record);
+        }
+
+        // Unparseable values are type mismatches, not nulls
+        let numeric = build_numeric_histogram(&records, "income", &BinningConfig::default());
+        assert_eq!(numeric.null_rate, Some(2.0 / 6.0));
+        assert_eq!(numeric.count, 2);
+
+        let categorical = build_categorical_frequencies(&records, "location");
+        assert_eq!(categorical.null_rate, Some(2.0 / 6.0));
+        assert_eq!(column_null_rate(&records, "missing"), Some(1.0));
+    }
+
+    #[test]
+    fn test_build_numeric_histogram_quantile_with_outlier() {
+        let mut records = Vec::new();
+        for i in 0..99 {
+            let mut record = HashMap::new();
+            record.insert("income".to_string(), (30000 + i * 100).to_string());
+            records.push(record);
+        }
+        let mut outlier = HashMap::new();
+        outlier.insert("income".to_string(), "10000000".to_string());
+        records.push(outlier);
+
+        let equal_width = build_numeric_histogram(&records, "income", &BinningConfig::default());
+        assert!(equal_width.probabilities[0] > 0.98);
+
+        let quantile = build_numeric_histogram(
+            &records,
+            "income",
+            &BinningConfig {
+                strategy: BinningStrategy::Quantile,
+                num_bins: 5,
+            },
+        );
+        assert_eq!(quantile.binning, BinningStrategy::Quantile);
+        assert_eq!(quantile.bins.len(), 6);
+        for probability in &quantile.probabilities {
+            assert!((probability - 0.2).abs() < 0.02);
+        }
+        assert_eq!(quantile.underflow, 0.0);
+        assert_eq!(quantile.overflow, 0.0);
+    }
+
+    #[test]
+    fn test_histogram_counts_out_of_range() {
+        let edges = vec![0.0, 10.0, 20.0];
+        let (underflow, counts, overflow) =
+            histogram_counts(&edges, &[-5.0, 0.0, 10.0, 20.0, 25.0, 30.0]);
+
+        assert_eq!(underflow, 1);
+        assert_eq!(counts, vec![1, 2]);
+        assert_eq!(overflow, 2);
+    }
+
+    #[test]
+    fn test_histogram_counts_skips_non_finite_values() {
+        let edges = vec![0.0, 10.0, 20.0];
+        let (underflow, counts, overflow) = histogram_counts(
+            &edges,
+            &[f64::NAN, 5.0, f64::INFINITY, f64::NEG_INFINITY, 15.0],
+        );
+
+        assert_eq!(underflow, 0);
+        assert_eq!(counts, vec![1, 1]);
+        assert_eq!(overflow, 0);
     }
 }
@@ -1,78 +1,228 @@
-use crate::models::{CategoricalStats, FeatureStats, FeatureStatsData, NumericStats};
+use crate::models::{
+    CategoricalStats, DriftMetric, FeatureDrift, FeatureStats, FeatureStatsData, NumericStats,
+    SignificanceTest, UnexpectedValues, ValueCount,
+};
+use crate::utils::csv_parser::histogram_counts;
+use crate::utils::stats::{chi_squared_survival, ks_p_value};
 use std::collections::HashMap;
 
-pub fn compute_feature_drift(baseline_stats: &FeatureStats, incoming_values: &[String]) -> f64 {
+/// Floor applied to empty bins and unseen categories so PSI stays finite.
+const PSI_EPSILON: f64 = 1e-4;
+/// Number of offending values reported for out-of-range/unseen values.
+const TOP_UNEXPECTED_VALUES: usize = 5;
+
+pub fn compute_feature_drift(
+    baseline_stats: &FeatureStats,
+    incoming_values: &[String],
+    metric: DriftMetric,
+) -> f64 {
     match &baseline_stats.stats {
         FeatureStatsData::Numeric(baseline_numeric) => {
-            compute_numeric_drift(baseline_numeric, incoming_values)
+            compute_numeric_drift(baseline_numeric, incoming_values, metric)
         }
         FeatureStatsData::Categorical(baseline_categorical) => {
-            compute_categorical_drift(baseline_categorical, incoming_values)
+            compute_categorical_drift(baseline_categorical, incoming_values, metric)
         }
     }
 }
 
-fn compute_numeric_drift(baseline: &NumericStats, incoming_values: &[String]) -> f64 {
-    // Parse incoming values
-    let values: Vec<f64> = incoming_values
-        .iter()
-        .filter_map(|v| v.trim().parse::<f64>().ok())
-        .collect();
+/// Scores drift for one feature and, when the baseline sample size is known,
+/// tests whether the difference is statistically significant.
+pub fn evaluate_feature_drift(
+    baseline_stats: &FeatureStats,
+    incoming_values: &[String],
+    metric: DriftMetric,
+) -> FeatureDrift {
+    let (sample_size, significance, unexpected_values, type_mismatches) =
+        match &baseline_stats.stats {
+            FeatureStatsData::Numeric(baseline_numeric) => (
+                parse_numeric_values(incoming_values).len(),
+                kolmogorov_smirnov_test(baseline_numeric, incoming_values),
+                find_out_of_range_values(baseline_numeric, incoming_values),
+                find_non_numeric_values(incoming_values),
+            ),
+            FeatureStatsData::Categorical(baseline_categorical) => (
+                count_categories(incoming_values).1,
+                chi_squared_test(baseline_categorical, incoming_values),
+                find_unseen_categories(baseline_categorical, incoming_values),
+                None,
+            ),
+        };
 
-    if values.is_empty() || baseline.bins.len() < 2 {
-        return 0.0;
+    FeatureDrift {
+        score: compute_feature_drift(baseline_stats, incoming_values, metric),
+        sample_size,
+        significance,
+        unexpected_values,
+        null_rate: compute_null_rate(incoming_values),
+        type_mismatches,
+    }
+}
+
+/// Share of incoming values that are empty, or None if there are no rows. Baselines
+/// use the same definition (`csv_parser::column_null_rate`).
+pub fn compute_null_rate(incoming_values: &[String]) -> Option<f64> {
+    if incoming_values.is_empty() {
+        return None;
     }
+    let nulls = incoming_values
+        .iter()
+        .filter(|v| v.trim().is_empty())
+        .count();
+    Some(nulls as f64 / incoming_values.len() as f64)
+}
 
-    // Build incoming histogram using same bin edges
+/// Numeric values below the first or above the last baseline bin edge.
+fn find_out_of_range_values(
+    baseline: &NumericStats,
+    incoming_values: &[String],
+) -> Option<UnexpectedValues> {
+    if baseline.bins.len() < 2 {
+        return None;
+    }
     let min = baseline.bins[0];
     let max = baseline.bins[baseline.bins.len() - 1];
-    let num_bins = baseline.probabilities.len();
-    let bin_width = if max > min {
-        (max - min) / num_bins as f64
-    } else {
-        1.0
-    };
 
-    let mut bin_counts = vec![0; num_bins];
-    for value in &values {
-        let bin_index = if bin_width > 0.0 {
-            ((value - min) / bin_width).floor() as usize
-        } else {
-            0
-        };
-        let bin_index = bin_index.min(num_bins - 1);
-        bin_counts[bin_index] += 1;
+    let mut offending: HashMap<String, usize> = HashMap::new();
+    let mut total = 0;
+    for value in incoming_values {
+        let trimmed = value.trim();
+        if let Some(parsed) = parse_numeric(trimmed) {
+            total += 1;
+            if parsed < min || parsed > max {
+                *offending.entry(trimmed.to_string()).or_insert(0) += 1;
+            }
+        }
     }
 
-    let total = values.len() as f64;
-    let incoming_probs: Vec<f64> = bin_counts
-        .iter()
-        .map(|&count| count as f64 / total)
+    summarize_unexpected_values(offending, total)
+}
+
+/// Non-empty values of a numeric feature that don't parse as finite numbers (`NaN`
+/// and `inf` included). These are left out of the drift score, so they are reported
+/// separately.
+fn find_non_numeric_values(incoming_values: &[String]) -> Option<UnexpectedValues> {
+    let mut offending: HashMap<String, usize> = HashMap::new();
+    let mut total = 0;
+    for value in incoming_values {
+        let trimmed = value.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        total += 1;
+        if parse_numeric(trimmed).is_none() {
+            *offending.entry(trimmed.to_string()).or_insert(0) += 1;
+        }
+    }
+
+    summarize_unexpected_values(offending, total)
+}
+
+/// Categories that never appeared in the baseline.
+fn find_unseen_categories(
+    baseline: &CategoricalStats,
+    incoming_values: &[String],
+) -> Option<UnexpectedValues> {
+    let (incoming_counts, total) = count_categories(incoming_values);
+    let offending: HashMap<String, usize> = incoming_counts
+        .into_iter()
+        .filter(|(category, _)| !baseline.frequencies.contains_key(category))
         .collect();
 
-    // Compute L1 distance (simpler than Jensen-Shannon for v1)
-    let mut l1_distance = 0.0;
-    for (prob_baseline, prob_incoming) in baseline.probabilities.iter().zip(incoming_probs.iter()) {
-        l1_distance += (prob_baseline - prob_incoming).abs();
+    summarize_unexpected_values(offending, total)
+}
+
+fn summarize_unexpected_values(
+    offending: HashMap<String, usize>,
+    total: usize,
+) -> Option<UnexpectedValues> {
+    let count: usize = offending.values().sum();
+    if count == 0 || total == 0 {
+        return None;
     }
 
-    // Normalize to 0-1 range (L1 distance for distributions is 0-2, so divide by 2)
-    l1_distance / 2.0
+    let mut top_values: Vec<ValueCount> = offending
+        .into_iter()
+        .map(|(value, count)| ValueCount { value, count })
+        .collect();
+    top_values.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.value.cmp(&b.value)));
+    top_values.truncate(TOP_UNEXPECTED_VALUES);
+
+    Some(UnexpectedValues {
+        count,
+        fraction: count as f64 / total as f64,
+        top_values,
+    })
 }
 
-fn compute_categorical_drift(baseline: &CategoricalStats, incoming_values: &[String]) -> f64 {
-    // Count incoming frequencies
-    let mut incoming_counts: HashMap<String, usize> = HashMap::new();
+/// A cell's value as a finite number, or None if it is empty, not a number, `NaN` or
+/// infinite.
+pub fn parse_numeric(value: &str) -> Option<f64> {
+    value.trim().parse::<f64>().ok().filter(|v| v.is_finite())
+}
+
+fn parse_numeric_values(incoming_values: &[String]) -> Vec<f64> {
+    incoming_values
+        .iter()
+        .filter_map(|v| parse_numeric(v))
+        .collect()
+}
+
+fn count_categories(incoming_values: &[String]) -> (HashMap<String, usize>, usize) {
+    let mut counts: HashMap<String, usize> = HashMap::new();
     let mut total = 0;
 
     for value in incoming_values {
         let trimmed = value.trim();
         if !trimmed.is_empty() {
-            *incoming_counts.entry(trimmed.to_string()).or_insert(0) += 1;
+            *counts.entry(trimmed.to_string()).or_insert(0) += 1;
             total += 1;
         }
     }
 
+    (counts, total)
+}
+
+fn compute_numeric_drift(
+    baseline: &NumericStats,
+    incoming_values: &[String],
+    metric: DriftMetric,
+) -> f64 {
+    let values = parse_numeric_values(incoming_values);
+
+    if values.is_empty() || baseline.bins.len() < 2 {
+        return 0.0;
+    }
+
+    if metric == DriftMetric::Wasserstein {
+        return normalized_wasserstein_distance(baseline, &values);
+    }
+
+    // Build incoming histogram on the baseline edges, keeping values outside the
+    // baseline range in their own underflow/overflow buckets rather than clamping
+    let (underflow, bin_counts, overflow) = histogram_counts(&baseline.bins, &values);
+
+    let total = values.len() as f64;
+    let incoming_probs: Vec<f64> = std::iter::once(underflow)
+        .chain(bin_counts)
+        .chain(std::iter::once(overflow))
+        .map(|count| count as f64 / total)
+        .collect();
+    let baseline_probs: Vec<f64> = std::iter::once(baseline.underflow)
+        .chain(baseline.probabilities.iter().copied())
+        .chain(std::iter::once(baseline.overflow))
+        .collect();
+
+    distribution_distance(&baseline_probs, &incoming_probs, metric)
+}
+
+fn compute_categorical_drift(
+    baseline: &CategoricalStats,
+    incoming_values: &[String],
+    metric: DriftMetric,
+) -> f64 {
+    let (incoming_counts, total) = count_categories(incoming_values);
+
     if total == 0 {
         return 0.0;
     }
@@ -91,18 +241,248 @@ fn compute_categorical_drift(baseline: &CategoricalStats, incoming_values: &[Str
         }
     }
 
-    // Compute L1 distance
+    // Align both distributions on the same category order
+    let baseline_probs: Vec<f64> = all_categories
+        .iter()
+        .map(|c| baseline.frequencies.get(c).copied().unwrap_or(0.0))
+        .collect();
+    let incoming_probs: Vec<f64> = all_categories
+        .iter()
+        .map(|c| incoming_freqs.get(c).copied().unwrap_or(0.0))
+        .collect();
+
+    distribution_distance(&baseline_probs, &incoming_probs, metric)
+}
+
+fn distribution_distance(baseline: &[f64], incoming: &[f64], metric: DriftMetric) -> f64 {
+    match metric {
+        DriftMetric::L1 => l1_distance(baseline, incoming),
+        DriftMetric::Psi => population_stability_index(baseline, incoming),
+        DriftMetric::JensenShannon => jensen_shannon_divergence(baseline, incoming),
+        DriftMetric::Hellinger => hellinger_distance(baseline, incoming),
+        // Categories have no ordering to move mass along, so fall back to L1
+        DriftMetric::Wasserstein => l1_distance(baseline, incoming),
+    }
+}
+
+fn l1_distance(baseline: &[f64], incoming: &[f64]) -> f64 {
     let mut l1_distance = 0.0;
-    for category in &all_categories {
-        let baseline_freq = baseline.frequencies.get(category).copied().unwrap_or(0.0);
-        let incoming_freq = incoming_freqs.get(category).copied().unwrap_or(0.0);
-        l1_distance += (baseline_freq - incoming_freq).abs();
+    for (prob_baseline, prob_incoming) in baseline.iter().zip(incoming.iter()) {
+        l1_distance += (prob_baseline - prob_incoming).abs();
     }
 
-    // Normalize to 0-1 range
+    // Normalize to 0-1 range (L1 distance for distributions is 0-2, so divide by 2)
     l1_distance / 2.0
 }
 
+/// PSI = sum((incoming - baseline) * ln(incoming / baseline)), with empty bins
+/// floored at `PSI_EPSILON`. Unbounded above; 0.1 / 0.25 are the usual cut-offs.
+fn population_stability_index(baseline: &[f64], incoming: &[f64]) -> f64 {
+    let mut psi = 0.0;
+    for (prob_baseline, prob_incoming) in baseline.iter().zip(incoming.iter()) {
+        let b = prob_baseline.max(PSI_EPSILON);
+        let i = prob_incoming.max(PSI_EPSILON);
+        psi += (i - b) * (i / b).ln();
+    }
+    psi
+}
+
+/// JS divergence in bits: the mean KL divergence of each distribution from their
+/// midpoint. Zero-probability terms contribute nothing, so no smoothing is needed.
+fn jensen_shannon_divergence(baseline: &[f64], incoming: &[f64]) -> f64 {
+    let mut divergence = 0.0;
+    for (prob_baseline, prob_incoming) in baseline.iter().zip(incoming.iter()) {
+        let midpoint = (prob_baseline + prob_incoming) / 2.0;
+        if *prob_baseline > 0.0 {
+            divergence += 0.5 * prob_baseline * (prob_baseline / midpoint).log2();
+        }
+        if *prob_incoming > 0.0 {
+            divergence += 0.5 * prob_incoming * (prob_incoming / midpoint).log2();
+        }
+    }
+    // Guard against tiny negative values from floating point error
+    divergence.clamp(0.0, 1.0)
+}
+
+/// Hellinger distance: sqrt(1 - sum(sqrt(baseline[i] * incoming[i]))).
+fn hellinger_distance(baseline: &[f64], incoming: &[f64]) -> f64 {
+    let bhattacharyya: f64 = baseline
+        .iter()
+        .zip(incoming.iter())
+        .map(|(prob_baseline, prob_incoming)| (prob_baseline * prob_incoming).sqrt())
+        .sum();
+    (1.0 - bhattacharyya).max(0.0).sqrt()
+}
+
+/// Baseline CDF at `x`, treating values as uniformly spread within each bin.
+fn baseline_cdf(baseline: &NumericStats, x: f64) -> f64 {
+    let edges = &baseline.bins;
+    if x <= edges[0] {
+        return 0.0;
+    }
+    if x >= edges[edges.len() - 1] {
+        return 1.0;
+    }
+
+    let bin = edges.partition_point(|edge| *edge <= x) - 1;
+    let below: f64 = baseline.probabilities.iter().take(bin).sum();
+    let width = edges[bin + 1] - edges[bin];
+    let within = if width > 0.0 {
+        (x - edges[bin]) / width
+    } else {
+        1.0
+    };
+    below + baseline.probabilities.get(bin).copied().unwrap_or(0.0) * within
+}
+
+/// Standard deviation of the baseline histogram, with values uniform within bins.
+fn baseline_std_dev(baseline: &NumericStats) -> f64 {
+    let mut mean = 0.0;
+    let mut second_moment = 0.0;
+    for (i, prob) in baseline.probabilities.iter().enumerate() {
+        let (lower, upper) = (baseline.bins[i], baseline.bins[i + 1]);
+        let midpoint = (lower + upper) / 2.0;
+        let width = upper - lower;
+        mean += prob * midpoint;
+        second_moment += prob * (midpoint * midpoint + width * width / 12.0);
+    }
+    (second_moment - mean * mean).max(0.0).sqrt()
+}
+
+/// 1-D Wasserstein (earth mover's) distance between the baseline histogram and the
+/// incoming values, i.e. the area between the two CDFs, divided by the baseline
+/// standard deviation so the score reads as "standard deviations of mass moved".
+fn normalized_wasserstein_distance(baseline: &NumericStats, values: &[f64]) -> f64 {
+    // Non-finite values have no place on either CDF
+    let mut sorted: Vec<f64> = values.iter().copied().filter(|v| v.is_finite()).collect();
+    if sorted.is_empty() || baseline.bins.iter().any(|edge| !edge.is_finite()) {
+        return 0.0;
+    }
+    sorted.sort_by(f64::total_cmp);
+    let total = sorted.len() as f64;
+
+    // Between consecutive breakpoints the incoming CDF is constant and the
+    // baseline CDF is linear, so each segment integrates exactly
+    let mut breakpoints: Vec<f64> = baseline.bins.iter().chain(sorted.iter()).copied().collect();
+    breakpoints.sort_by(f64::total_cmp);
+    breakpoints.dedup();
+
+    let mut distance = 0.0;
+    for segment in breakpoints.windows(2) {
+        let (start, end) = (segment[0], segment[1]);
+        let incoming_cdf = sorted.partition_point(|v| *v <= start) as f64 / total;
+        let gap_start = baseline_cdf(baseline, start) - incoming_cdf;
+        let gap_end = baseline_cdf(baseline, end) - incoming_cdf;
+        let length = end - start;
+
+        distance += if gap_start * gap_end >= 0.0 {
+            length * (gap_start.abs() + gap_end.abs()) / 2.0
+        } else {
+            // The gap changes sign inside the segment
+            length * (gap_start * gap_start + gap_end * gap_end)
+                / (2.0 * (gap_start.abs() + gap_end.abs()))
+        };
+    }
+
+    let spread = baseline_std_dev(baseline);
+    if spread > 0.0 {
+        distance / spread
+    } else {
+        distance
+    }
+}
+
+/// Two-sample Kolmogorov-Smirnov test. The baseline CDF is only known at its
+/// bin edges, so the statistic is the largest CDF gap over those edges.
+fn kolmogorov_smirnov_test(
+    baseline: &NumericStats,
+    incoming_values: &[String],
+) -> Option<SignificanceTest> {
+    let mut values = parse_numeric_values(incoming_values);
+    if values.is_empty() || baseline.count == 0 || baseline.bins.len() < 2 {
+        return None;
+    }
+    values.sort_by(f64::total_cmp);
+
+    let total = values.len() as f64;
+    let last_edge = baseline.bins.len() - 1;
+    let mut baseline_cdf = 0.0;
+    let mut statistic: f64 = 0.0;
+    for (i, edge) in baseline.bins.iter().enumerate() {
+        if i > 0 {
+            baseline_cdf += baseline.probabilities.get(i - 1).copied().unwrap_or(0.0);
+        }
+        // Bins are half-open except the last, which includes the maximum
+        let below = if i == last_edge {
+            values.partition_point(|v| v <= edge)
+        } else {
+            values.partition_point(|v| v < edge)
+        };
+        let incoming_cdf = below as f64 / total;
+        statistic = statistic.max((baseline_cdf - incoming_cdf).abs());
+    }
+
+    Some(SignificanceTest {
+        test: "KS".to_string(),
+        statistic,
+        p_value: ks_p_value(statistic, baseline.count, values.len()),
+    })
+}
+
+/// Chi-squared test of homogeneity between baseline and incoming category counts.
+fn chi_squared_test(
+    baseline: &CategoricalStats,
+    incoming_values: &[String],
+) -> Option<SignificanceTest> {
+    let (incoming_counts, total) = count_categories(incoming_values);
+    if total == 0 || baseline.count == 0 {
+        return None;
+    }
+
+    let baseline_total = baseline.count as f64;
+    let incoming_total = total as f64;
+    let grand_total = baseline_total + incoming_total;
+
+    let mut all_categories: Vec<&String> = baseline.frequencies.keys().collect();
+    for cat in incoming_counts.keys() {
+        if !baseline.frequencies.contains_key(cat) {
+            all_categories.push(cat);
+        }
+    }
+
+    let mut statistic = 0.0;
+    let mut columns = 0;
+    for category in all_categories {
+        let observed_baseline =
+            baseline.frequencies.get(category).copied().unwrap_or(0.0) * baseline_total;
+        let observed_incoming = incoming_counts.get(category).copied().unwrap_or(0) as f64;
+        let column_total = observed_baseline + observed_incoming;
+        if column_total <= 0.0 {
+            continue;
+        }
+        columns += 1;
+
+        let expected_baseline = column_total * baseline_total / grand_total;
+        let expected_incoming = column_total * incoming_total / grand_total;
+        statistic += (observed_baseline - expected_baseline).powi(2) / expected_baseline;
+        statistic += (observed_incoming - expected_incoming).powi(2) / expected_incoming;
+    }
+
+    if columns < 2 {
+        return Some(SignificanceTest {
+            test: "CHI_SQUARED".to_string(),
+            statistic: 0.0,
+            p_value: 1.0,
+        });
+    }
+
+    Some(SignificanceTest {
+        test: "CHI_SQUARED".to_string(),
+        statistic,
+        p_value: chi_squared_survival(statistic, columns - 1),
+    })
+}
+
 pub fn compute_prediction_shift(baseline_rate: f64, incoming_rate: f64) -> f64 {
     (incoming_rate - baseline_rate).abs()
 }
@@ -111,7 +491,7 @@ pub fn compute_prediction_shift(baseline_rate: f64, incoming_rate: f64) -> f64 {
 mod tests {
     use super::*;
     use crate::models::{
-        CategoricalStats, FeatureStats, FeatureStatsData, FeatureType, NumericStats,
+        CategoricalStats, DriftMetric, FeatureStats, FeatureStatsData, FeatureType, NumericStats,
     };
 
     #[test]
@@ -125,6 +505,7 @@ mod tests {
         let baseline = NumericStats {
             bins: vec![0.0, 10.0, 20.0, 30.0],
             probabilities: vec![0.5, 0.3, 0.2],
+            ..Default::default()
         };
 
         // Same distribution
@@ -136,7 +517,7 @@ mod tests {
             "15".to_string(),
             "25".to_string(),
         ];
-        let drift = compute_numeric_drift(&baseline, &incoming);
+        let drift = compute_numeric_drift(&baseline, &incoming, DriftMetric::L1);
 
         // Should have some drift but not extreme
         assert!((0.0..=1.0).contains(&drift));
@@ -147,11 +528,12 @@ mod tests {
         let baseline = NumericStats {
             bins: vec![0.0, 10.0, 20.0, 30.0],
             probabilities: vec![1.0, 0.0, 0.0], // All in first bin
+            ..Default::default()
         };
 
         // Completely different - all in last bin
         let incoming = vec!["25".to_string(), "25".to_string(), "25".to_string()];
-        let drift = compute_numeric_drift(&baseline, &incoming);
+        let drift = compute_numeric_drift(&baseline, &incoming, DriftMetric::L1);
 
         // Should have high drift
         assert!(drift > 0.5);
@@ -164,10 +546,11 @@ mod tests {
         baseline_freqs.insert("suburban".to_string(), 0.5);
         let baseline = CategoricalStats {
             frequencies: baseline_freqs,
+            ..Default::default()
         };
 
         let incoming = vec!["urban".to_string(), "suburban".to_string()];
-        let drift = compute_categorical_drift(&baseline, &incoming);
+        let drift = compute_categorical_drift(&baseline, &incoming, DriftMetric::L1);
 
         // Should have low drift (same distribution)
         assert!(drift < 0.5);
@@ -180,6 +563,7 @@ mod tests {
         baseline_freqs.insert("suburban".to_string(), 0.0);
         let baseline = CategoricalStats {
             frequencies: baseline_freqs,
+            ..Default::default()
         };
 
         // All suburban (completely different)
@@ -188,7 +572,7 @@ mod tests {
             "suburban".to_string(),
             "suburban".to_string(),
         ];
-        let drift = compute_categorical_drift(&baseline, &incoming);
+        let drift = compute_categorical_drift(&baseline, &incoming, DriftMetric::L1);
 
         // Should have high drift
         assert!(drift > 0.5);
@@ -202,11 +586,12 @@ mod tests {
             stats: FeatureStatsData::Numeric(NumericStats {
                 bins: vec![0.0, 10.0, 20.0],
                 probabilities: vec![0.5, 0.5],
+                ..Default::default()
             }),
         };
 
         let incoming = vec!["5".to_string(), "15".to_string()];
-        let drift = compute_feature_drift(&baseline_stats, &incoming);
+        let drift = compute_feature_drift(&baseline_stats, &incoming, DriftMetric::L1);
         assert!((0.0..=1.0).contains(&drift));
     }
 
@@ -217,11 +602,341 @@ mod tests {
         let baseline_stats = FeatureStats {
             name: "location".to_string(),
             feature_type: FeatureType::Categorical,
-            stats: FeatureStatsData::Categorical(CategoricalStats { frequencies: freqs }),
+            stats: FeatureStatsData::Categorical(CategoricalStats {
+                frequencies: freqs,
+                ..Default::default()
+            }),
         };
 
         let incoming = vec!["suburban".to_string()];
-        let drift = compute_feature_drift(&baseline_stats, &incoming);
+        let drift = compute_feature_drift(&baseline_stats, &incoming, DriftMetric::L1);
         assert!(drift > 0.5); // High drift
     }
+
+    #[test]
+    fn test_psi_identical_distributions() {
+        let baseline = NumericStats {
+            bins: vec![0.0, 10.0, 20.0],
+            probabilities: vec![0.5, 0.5],
+            ..Default::default()
+        };
+
+        let incoming = vec!["5".to_string(), "15".to_string()];
+        let psi = compute_numeric_drift(&baseline, &incoming, DriftMetric::Psi);
+        assert!(psi.abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_psi_known_value() {
+        // PSI for [0.5, 0.5] -> [0.25, 0.75]
+        let psi = population_stability_index(&[0.5, 0.5], &[0.25, 0.75]);
+        let expected = (0.25 - 0.5) * (0.25f64 / 0.5).ln() + (0.75 - 0.5) * (0.75f64 / 0.5).ln();
+        assert!((psi - expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_psi_unseen_category_is_finite() {
+        let mut baseline_freqs = HashMap::new();
+        baseline_freqs.insert("urban".to_string(), 1.0);
+        let baseline = CategoricalStats {
+            frequencies: baseline_freqs,
+            ..Default::default()
+        };
+
+        let incoming = vec!["rural".to_string(), "rural".to_string()];
+        let psi = compute_categorical_drift(&baseline, &incoming, DriftMetric::Psi);
+
+        assert!(psi.is_finite());
+        assert!(psi > 0.25);
+    }
+
+    #[test]
+    fn test_jensen_shannon_bounds() {
+        assert!(jensen_shannon_divergence(&[0.5, 0.5], &[0.5, 0.5]).abs() < 1e-9);
+        // Disjoint supports give the maximum of 1 bit
+        assert!((jensen_shannon_divergence(&[1.0, 0.0], &[0.0, 1.0]) - 1.0).abs() < 1e-9);
+        // Symmetric
+        let forward = jensen_shannon_divergence(&[0.7, 0.3], &[0.2, 0.8]);
+        let backward = jensen_shannon_divergence(&[0.2, 0.8], &[0.7, 0.3]);
+        assert!((forward - backward).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_hellinger_bounds() {
+        assert!(hellinger_distance(&[0.5, 0.5], &[0.5, 0.5]).abs() < 1e-9);
+        assert!((hellinger_distance(&[1.0, 0.0], &[0.0, 1.0]) - 1.0).abs() < 1e-9);
+        let distance = hellinger_distance(&[0.5, 0.5], &[0.6, 0.4]);
+        assert!(distance > 0.0 && distance < 0.1);
+    }
+
+    #[test]
+    fn test_compute_feature_drift_metric_selection() {
+        let mut freqs = HashMap::new();
+        freqs.insert("urban".to_string(), 0.5);
+        freqs.insert("rural".to_string(), 0.5);
+        let baseline_stats = FeatureStats {
+            name: "location".to_string(),
+            feature_type: FeatureType::Categorical,
+            stats: FeatureStatsData::Categorical(CategoricalStats {
+                frequencies: freqs,
+                ..Default::default()
+            }),
+        };
+
+        let incoming = vec!["urban".to_string(); 4];
+        let js = compute_feature_drift(&baseline_stats, &incoming, DriftMetric::JensenShannon);
+        let hellinger = compute_feature_drift(&baseline_stats, &incoming, DriftMetric::Hellinger);
+
+        assert!((0.0..=1.0).contains(&js));
+        assert!((0.0..=1.0).contains(&hellinger));
+        assert!(js > 0.0);
+        assert!(hellinger > js);
+    }
+
+    #[test]
+    fn test_ks_test_small_sample_not_significant() {
+        let baseline = NumericStats {
+            bins: vec![0.0, 10.0, 20.0],
+            probabilities: vec![0.5, 0.5],
+            count: 1000,
+            ..Default::default()
+        };
+
+        // 20 rows skewed 60/40 is well within sampling noise
+        let mut incoming = vec!["5".to_string(); 12];
+        incoming.extend(vec!["15".to_string(); 8]);
+        let test = kolmogorov_smirnov_test(&baseline, &incoming).unwrap();
+
+        assert_eq!(test.test, "KS");
+        assert!((test.statistic - 0.1).abs() < 1e-9);
+        assert!(test.p_value > 0.05);
+    }
+
+    #[test]
+    fn test_ks_test_large_shift_significant() {
+        let baseline = NumericStats {
+            bins: vec![0.0, 10.0, 20.0],
+            probabilities: vec![1.0, 0.0],
+            count: 500,
+            ..Default::default()
+        };
+
+        let incoming = vec!["15".to_string(); 200];
+        let test = kolmogorov_smirnov_test(&baseline, &incoming).unwrap();
+
+        assert!((test.statistic - 1.0).abs() < 1e-9);
+        assert!(test.p_value < 0.001);
+    }
+
+    #[test]
+    fn test_ks_test_requires_baseline_count() {
+        let baseline = NumericStats {
+            bins: vec![0.0, 10.0, 20.0],
+            probabilities: vec![0.5, 0.5],
+            ..Default::default()
+        };
+
+        assert!(kolmogorov_smirnov_test(&baseline, &["5".to_string()]).is_none());
+    }
+
+    #[test]
+    fn test_chi_squared_test() {
+        let mut baseline_freqs = HashMap::new();
+        baseline_freqs.insert("urban".to_string(), 0.5);
+        baseline_freqs.insert("rural".to_string(), 0.5);
+        let baseline = CategoricalStats {
+            frequencies: baseline_freqs,
+            count: 1000,
+            ..Default::default()
+        };
+
+        let mut matching = vec!["urban".to_string(); 50];
+        matching.extend(vec!["rural".to_string(); 50]);
+        let test = chi_squared_test(&baseline, &matching).unwrap();
+        assert_eq!(test.test, "CHI_SQUARED");
+        assert!(test.statistic.abs() < 1e-9);
+        assert!((test.p_value - 1.0).abs() < 1e-9);
+
+        let shifted = vec!["urban".to_string(); 100];
+        let test = chi_squared_test(&baseline, &shifted).unwrap();
+        assert!(test.p_value < 0.001);
+    }
+
+    #[test]
+    fn test_evaluate_feature_drift_sample_size() {
+        let baseline_stats = FeatureStats {
+            name: "income".to_string(),
+            feature_type: FeatureType::Numeric,
+            stats: FeatureStatsData::Numeric(NumericStats {
+                bins: vec![0.0, 10.0, 20.0],
+                probabilities: vec![0.5, 0.5],
+                count: 100,
+                ..Default::default()
+            }),
+        };
+
+        let incoming = vec!["5".to_string(), "".to_string(), "15".to_string()];
+        let drift = evaluate_feature_drift(&baseline_stats, &incoming, DriftMetric::L1);
+
+        assert_eq!(drift.sample_size, 2);
+        assert!(drift.significance.is_some());
+    }
+
+    #[test]
+    fn test_wasserstein_uniform_baseline() {
+        let baseline = NumericStats {
+            bins: vec![0.0, 10.0],
+            probabilities: vec![1.0],
+            ..Default::default()
+        };
+
+        // Area between uniform(0, 10) and a point mass at 5 is 2.5
+        let distance = normalized_wasserstein_distance(&baseline, &[5.0, 5.0]);
+        let expected = 2.5 / (10.0 / 12f64.sqrt());
+        assert!((distance - expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_wasserstein_ignores_non_finite_values() {
+        let baseline = NumericStats {
+            bins: vec![0.0, 10.0],
+            probabilities: vec![1.0],
+            ..Default::default()
+        };
+
+        let with_nan =
+            normalized_wasserstein_distance(&baseline, &[5.0, f64::NAN, 5.0, f64::INFINITY]);
+        let without = normalized_wasserstein_distance(&baseline, &[5.0, 5.0]);
+        assert!((with_nan - without).abs() < 1e-9);
+        assert_eq!(normalized_wasserstein_distance(&baseline, &[f64::NAN]), 0.0);
+
+        let broken = NumericStats {
+            bins: vec![0.0, f64::NAN],
+            probabilities: vec![1.0],
+            ..Default::default()
+        };
+        assert_eq!(normalized_wasserstein_distance(&broken, &[5.0]), 0.0);
+    }
+
+    #[test]
+    fn test_wasserstein_accounts_for_shift_size() {
+        let baseline = NumericStats {
+            bins: vec![0.0, 10.0, 20.0, 30.0, 40.0],
+            probabilities: vec![1.0, 0.0, 0.0, 0.0],
+            ..Default::default()
+        };
+
+        let adjacent = vec!["15".to_string(); 10];
+        let far = vec!["35".to_string(); 10];
+
+        // L1 can't tell the two shifts apart
+        let l1_adjacent = compute_numeric_drift(&baseline, &adjacent, DriftMetric::L1);
+        let l1_far = compute_numeric_drift(&baseline, &far, DriftMetric::L1);
+        assert!((l1_adjacent - l1_far).abs() < 1e-9);
+
+        let w_adjacent = compute_numeric_drift(&baseline, &adjacent, DriftMetric::Wasserstein);
+        let w_far = compute_numeric_drift(&baseline, &far, DriftMetric::Wasserstein);
+        assert!(w_far > w_adjacent * 2.0);
+    }
+
+    #[test]
+    fn test_wasserstein_is_scale_invariant() {
+        let small = NumericStats {
+            bins: vec![0.0, 1.0, 2.0],
+            probabilities: vec![0.5, 0.5],
+            ..Default::default()
+        };
+        let large = NumericStats {
+            bins: vec![0.0, 1000.0, 2000.0],
+            probabilities: vec![0.5, 0.5],
+            ..Default::default()
+        };
+
+        let small_distance = normalized_wasserstein_distance(&small, &[1.5, 1.5, 0.5]);
+        let large_distance = normalized_wasserstein_distance(&large, &[1500.0, 1500.0, 500.0]);
+        assert!((small_distance - large_distance).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_compute_numeric_drift_out_of_range_not_clamped() {
+        let baseline = NumericStats {
+            bins: vec![0.0, 10.0, 20.0],
+            probabilities: vec![0.0, 1.0],
+            ..Default::default()
+        };
+
+        // Far above the baseline range: previously clamped into the last bin (no drift)
+        let incoming = vec!["500".to_string(); 5];
+        let drift = compute_numeric_drift(&baseline, &incoming, DriftMetric::L1);
+        assert!((drift - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_find_out_of_range_values() {
+        let baseline = NumericStats {
+            bins: vec![0.0, 10.0, 20.0],
+            probabilities: vec![0.5, 0.5],
+            ..Default::default()
+        };
+
+        let incoming: Vec<String> = ["5", "25", "25", "-1", "20", "abc", ""]
+            .iter()
+            .map(|v| v.to_string())
+            .collect();
+        let unexpected = find_out_of_range_values(&baseline, &incoming).unwrap();
+
+        assert_eq!(unexpected.count, 3);
+        assert!((unexpected.fraction - 3.0 / 5.0).abs() < 1e-9);
+        assert_eq!(unexpected.top_values[0].value, "25");
+        assert_eq!(unexpected.top_values[0].count, 2);
+        assert_eq!(unexpected.top_values[1].value, "-1");
+
+        let in_range = vec!["0".to_string(), "20".to_string()];
+        assert!(find_out_of_range_values(&baseline, &in_range).is_none());
+    }
+
+    #[test]
+    fn test_find_non_numeric_values() {
+        let incoming: Vec<String> = ["25", "twenty-five", "", "30", "twenty-five", "n/a"]
+            .iter()
+            .map(|v| v.to_string())
+            .collect();
+        let mismatches = find_non_numeric_values(&incoming).unwrap();
+
+        assert_eq!(mismatches.count, 3);
+        assert!((mismatches.fraction - 0.6).abs() < 1e-9);
+        assert_eq!(mismatches.top_values[0].value, "twenty-five");
+        assert_eq!(mismatches.top_values[0].count, 2);
+
+        let numeric = vec!["1".to_string(), "2.5".to_string()];
+        assert!(find_non_numeric_values(&numeric).is_none());
+    }
+
+    #[test]
+    fn test_compute_null_rate() {
+        assert_eq!(compute_null_rate(&[]), None);
+        let incoming: Vec<String> = ["1", "", " ", "2"].iter().map(|v| v.to_string()).collect();
+        assert_eq!(compute_null_rate(&incoming), Some(0.5));
+    }
+
+    #[test]
+    fn test_find_unseen_categories() {
+        let mut baseline_freqs = HashMap::new();
+        baseline_freqs.insert("urban".to_string(), 1.0);
+        let baseline = CategoricalStats {
+            frequencies: baseline_freqs,
+            ..Default::default()
+        };
+
+        let incoming: Vec<String> = ["urban", "rural", "rural", "coastal"]
+            .iter()
+            .map(|v| v.to_string())
+            .collect();
+        let unexpected = find_unseen_categories(&baseline, &incoming).unwrap();
+
+        assert_eq!(unexpected.count, 3);
+        assert!((unexpected.fraction - 0.75).abs() < 1e-9);
+        assert_eq!(unexpected.top_values[0].value, "rural");
+        assert_eq!(unexpected.top_values[1].value, "coastal");
+    }
 }
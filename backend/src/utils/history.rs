@@ -0,0 +1,123 @@
+use crate::models::FeatureHistoryPoint;
+
+/// Reduce a history to about `max_points` by averaging runs of consecutive
+/// points. The peak score of each run is kept in `max_drift_score`, and a run
+/// never spans a change of drift metric or baseline version, since those scores
+/// aren't comparable; such changes can leave slightly more than `max_points`.
+pub fn downsample_history(
+    points: Vec<FeatureHistoryPoint>,
+    max_points: usize,
+) -> Vec<FeatureHistoryPoint> {
+    if max_points == 0 || points.len() <= max_points {
+        return points;
+    }
+
+    let run_length = points.len().div_ceil(max_points);
+    let mut downsampled: Vec<FeatureHistoryPoint> = Vec::new();
+    let mut run: Vec<FeatureHistoryPoint> = Vec::new();
+
+    for point in points {
+        let breaks_run = run.first().is_some_and(|first| {
+            run.len() >= run_length
+                || first.drift_metric != point.drift_metric
+                || first.baseline_version != point.baseline_version
+        });
+        if breaks_run {
+            downsampled.push(merge_run(std::mem::take(&mut run)));
+        }
+        run.push(point);
+    }
+    if !run.is_empty() {
+        downsampled.push(merge_run(run));
+    }
+
+    downsampled
+}
+
+fn merge_run(run: Vec<FeatureHistoryPoint>) -> FeatureHistoryPoint {
+    if run.len() == 1 {
+        return run.into_iter().next().expect("run is not empty");
+    }
+
+    let evaluations: usize = run.iter().map(|p| p.evaluations).sum();
+    let weighted_score: f64 = run
+        .iter()
+        .map(|p| p.drift_score * p.evaluations as f64)
+        .sum();
+    let max_drift_score = run
+        .iter()
+        .map(|p| p.max_drift_score)
+        .fold(f64::NEG_INFINITY, f64::max);
+    let sample_size = run.iter().map(|p| p.sample_size).sum();
+    let last = run.last().expect("run is not empty");
+
+    FeatureHistoryPoint {
+        evaluation_id: None,
+        created_at: last.created_at,
+        drift_score: weighted_score / evaluations as f64,
+        max_drift_score,
+        drift_metric: last.drift_metric,
+        sample_size,
+        baseline_version: last.baseline_version,
+        evaluations,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::DriftMetric;
+    use chrono::{Duration, TimeZone, Utc};
+
+    fn point(index: i64, score: f64, metric: DriftMetric) -> FeatureHistoryPoint {
+        FeatureHistoryPoint {
+            evaluation_id: Some(format!("eval-{}", index)),
+            created_at: Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap() + Duration::hours(index),
+            drift_score: score,
+            max_drift_score: score,
+            drift_metric: metric,
+            sample_size: 100,
+            baseline_version: 1,
+            evaluations: 1,
+        }
+    }
+
+    #[test]
+    fn test_downsample_keeps_short_histories() {
+        let points: Vec<_> = (0..5).map(|i| point(i, 0.1, DriftMetric::L1)).collect();
+        let result = downsample_history(points, 10);
+        assert_eq!(result.len(), 5);
+        assert!(result.iter().all(|p| p.evaluation_id.is_some()));
+    }
+
+    #[test]
+    fn test_downsample_averages_runs_and_keeps_peaks() {
+        let points: Vec<_> = (0..10)
+            .map(|i| point(i, if i == 3 { 0.9 } else { 0.1 }, DriftMetric::L1))
+            .collect();
+        let result = downsample_history(points, 5);
+
+        assert_eq!(result.len(), 5);
+        assert!(result.iter().all(|p| p.evaluations == 2));
+        assert!(result.iter().all(|p| p.evaluation_id.is_none()));
+        assert!((result[1].drift_score - 0.5).abs() < 1e-12);
+        assert_eq!(result[1].max_drift_score, 0.9);
+        assert_eq!(result[1].sample_size, 200);
+        assert_eq!(
+            result[4].created_at,
+            Utc.with_ymd_and_hms(2024, 1, 1, 9, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_downsample_does_not_mix_metrics() {
+        let mut points: Vec<_> = (0..3).map(|i| point(i, 0.1, DriftMetric::L1)).collect();
+        points.extend((3..6).map(|i| point(i, 0.2, DriftMetric::Psi)));
+        let result = downsample_history(points, 2);
+
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].drift_metric, DriftMetric::L1);
+        assert_eq!(result[0].evaluations, 3);
+        assert_eq!(result[1].drift_metric, DriftMetric::Psi);
+    }
+}
@@ -1,8 +1,15 @@
+pub mod calibration;
 pub mod csv_parser;
 pub mod drift;
+pub mod history;
+pub mod stats;
+pub mod windows;
 
+pub use calibration::*;
 pub use csv_parser::*;
 pub use drift::*;
+pub use history::*;
+pub use windows::*;
 
 // Re-export compute_prediction_rate from csv_parser
 pub use csv_parser::compute_prediction_rate;
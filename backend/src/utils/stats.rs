@@ -0,0 +1,143 @@
+/// Survival function of the Kolmogorov distribution, P(K > lambda).
+pub fn kolmogorov_survival(lambda: f64) -> f64 {
+    if lambda < 0.2 {
+        return 1.0;
+    }
+
+    let mut sum = 0.0;
+    for k in 1..=100 {
+        let k = k as f64;
+        let term = 2.0 * (-2.0 * k * k * lambda * lambda).exp();
+        if (k as i64) % 2 == 1 {
+            sum += term;
+        } else {
+            sum -= term;
+        }
+        if term < 1e-12 {
+            break;
+        }
+    }
+    sum.clamp(0.0, 1.0)
+}
+
+/// Asymptotic p-value for a two-sample KS statistic `d` with sample sizes `n` and `m`.
+pub fn ks_p_value(d: f64, n: usize, m: usize) -> f64 {
+    if n == 0 || m == 0 {
+        return 1.0;
+    }
+    let effective_n = (n as f64 * m as f64) / (n as f64 + m as f64);
+    let sqrt_n = effective_n.sqrt();
+    kolmogorov_survival((sqrt_n + 0.12 + 0.11 / sqrt_n) * d)
+}
+
+/// Upper tail P(X > x) of a chi-squared distribution with `df` degrees of freedom.
+pub fn chi_squared_survival(x: f64, df: usize) -> f64 {
+    if df == 0 {
+        return 1.0;
+    }
+    if x <= 0.0 {
+        return 1.0;
+    }
+    regularized_gamma_q(df as f64 / 2.0, x / 2.0)
+}
+
+/// Lanczos approximation of ln(Gamma(x)) for x > 0.
+fn ln_gamma(x: f64) -> f64 {
+    const COEFFICIENTS: [f64; 6] = [
+        76.180_091_729_471_46,
+        -86.505_320_329_416_77,
+        24.014_098_240_830_91,
+        -1.231_739_572_450_155,
+        0.120_865_097_386_617_9e-2,
+        -0.539_523_938_495_3e-5,
+    ];
+
+    let mut y = x;
+    let tmp = x + 5.5;
+    let tmp = tmp - (x + 0.5) * tmp.ln();
+    let mut series = 1.000_000_000_190_015;
+    for coefficient in COEFFICIENTS {
+        y += 1.0;
+        series += coefficient / y;
+    }
+    -tmp + (2.506_628_274_631_000_5 * series / x).ln()
+}
+
+/// Regularized upper incomplete gamma function Q(a, x).
+fn regularized_gamma_q(a: f64, x: f64) -> f64 {
+    const MAX_ITERATIONS: usize = 500;
+    const EPSILON: f64 = 1e-14;
+    const TINY: f64 = 1e-300;
+
+    if x < a + 1.0 {
+        // Series expansion of P(a, x), then Q = 1 - P
+        let mut term = 1.0 / a;
+        let mut sum = term;
+        let mut denominator = a;
+        for _ in 0..MAX_ITERATIONS {
+            denominator += 1.0;
+            term *= x / denominator;
+            sum += term;
+            if term.abs() < sum.abs() * EPSILON {
+                break;
+            }
+        }
+        let p = sum * (-x + a * x.ln() - ln_gamma(a)).exp();
+        (1.0 - p).clamp(0.0, 1.0)
+    } else {
+        // Continued fraction (modified Lentz)
+        let mut b = x + 1.0 - a;
+        let mut c = 1.0 / TINY;
+        let mut d = 1.0 / b;
+        let mut h = d;
+        for i in 1..=MAX_ITERATIONS {
+            let an = -(i as f64) * (i as f64 - a);
+            b += 2.0;
+            d = an * d + b;
+            if d.abs() < TINY {
+                d = TINY;
+            }
+            c = b + an / c;
+            if c.abs() < TINY {
+                c = TINY;
+            }
+            d = 1.0 / d;
+            let delta = d * c;
+            h *= delta;
+            if (delta - 1.0).abs() < EPSILON {
+                break;
+            }
+        }
+        ((-x + a * x.ln() - ln_gamma(a)).exp() * h).clamp(0.0, 1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chi_squared_survival_known_values() {
+        // Critical values at the 5% level
+        assert!((chi_squared_survival(3.841, 1) - 0.05).abs() < 1e-3);
+        assert!((chi_squared_survival(5.991, 2) - 0.05).abs() < 1e-3);
+        assert!((chi_squared_survival(18.307, 10) - 0.05).abs() < 1e-3);
+        assert_eq!(chi_squared_survival(0.0, 3), 1.0);
+    }
+
+    #[test]
+    fn test_kolmogorov_survival_known_values() {
+        // 5% critical value of the Kolmogorov distribution
+        assert!((kolmogorov_survival(1.358) - 0.05).abs() < 1e-3);
+        assert_eq!(kolmogorov_survival(0.0), 1.0);
+        assert!(kolmogorov_survival(3.0) < 1e-6);
+    }
+
+    #[test]
+    fn test_ks_p_value_depends_on_sample_size() {
+        let small = ks_p_value(0.2, 1000, 20);
+        let large = ks_p_value(0.2, 1000, 1000);
+        assert!(small > 0.05);
+        assert!(large < 0.001);
+    }
+}
@@ -0,0 +1,121 @@
+use crate::models::TimeWindow;
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Timelike, Utc};
+use std::collections::{BTreeMap, HashMap};
+
+/// Parse an ISO 8601 timestamp. Values without an offset are taken as UTC and
+/// a bare date is taken as midnight.
+pub fn parse_timestamp(value: &str) -> Option<DateTime<Utc>> {
+    let value = value.trim();
+    if let Ok(ts) = DateTime::parse_from_rfc3339(value) {
+        return Some(ts.with_timezone(&Utc));
+    }
+    for format in [
+        "%Y-%m-%dT%H:%M:%S%.f",
+        "%Y-%m-%d %H:%M:%S%.f",
+        "%Y-%m-%dT%H:%M",
+    ] {
+        if let Ok(ts) = NaiveDateTime::parse_from_str(value, format) {
+            return Some(ts.and_utc());
+        }
+    }
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .ok()
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .map(|ts| ts.and_utc())
+}
+
+/// Start of the window containing `ts`.
+pub fn window_start(ts: DateTime<Utc>, window: TimeWindow) -> DateTime<Utc> {
+    let hour_start = ts
+        .with_nanosecond(0)
+        .and_then(|t| t.with_second(0))
+        .and_then(|t| t.with_minute(0))
+        .unwrap_or(ts);
+    match window {
+        TimeWindow::Hourly => hour_start,
+        TimeWindow::Daily => hour_start.with_hour(0).unwrap_or(hour_start),
+    }
+}
+
+pub type WindowBuckets = BTreeMap<DateTime<Utc>, Vec<HashMap<String, String>>>;
+
+/// Group records by the window their `timestamp` falls in, in chronological order.
+/// Also returns how many rows were skipped for a missing or unparseable timestamp.
+pub fn bucket_by_window(
+    records: &[HashMap<String, String>],
+    window: TimeWindow,
+) -> (WindowBuckets, usize) {
+    let mut buckets = WindowBuckets::new();
+    let mut skipped = 0;
+
+    for record in records {
+        match record.get("timestamp").and_then(|v| parse_timestamp(v)) {
+            Some(ts) => buckets
+                .entry(window_start(ts, window))
+                .or_default()
+                .push(record.clone()),
+            None => skipped += 1,
+        }
+    }
+
+    (buckets, skipped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_parse_timestamp_formats() {
+        let expected = Utc.with_ymd_and_hms(2024, 3, 1, 14, 30, 0).unwrap();
+        assert_eq!(parse_timestamp("2024-03-01T14:30:00Z"), Some(expected));
+        assert_eq!(parse_timestamp("2024-03-01T16:30:00+02:00"), Some(expected));
+        assert_eq!(parse_timestamp("2024-03-01 14:30:00"), Some(expected));
+        assert_eq!(parse_timestamp("2024-03-01T14:30"), Some(expected));
+        assert_eq!(
+            parse_timestamp("2024-03-01"),
+            Some(Utc.with_ymd_and_hms(2024, 3, 1, 0, 0, 0).unwrap())
+        );
+        assert_eq!(parse_timestamp("yesterday"), None);
+        assert_eq!(parse_timestamp(""), None);
+    }
+
+    #[test]
+    fn test_window_start_truncates() {
+        let ts = Utc.with_ymd_and_hms(2024, 3, 1, 14, 30, 15).unwrap();
+        assert_eq!(
+            window_start(ts, TimeWindow::Hourly),
+            Utc.with_ymd_and_hms(2024, 3, 1, 14, 0, 0).unwrap()
+        );
+        assert_eq!(
+            window_start(ts, TimeWindow::Daily),
+            Utc.with_ymd_and_hms(2024, 3, 1, 0, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_bucket_by_window_orders_and_skips() {
+        let record = |ts: &str| {
+            let mut r = HashMap::new();
+            r.insert("timestamp".to_string(), ts.to_string());
+            r
+        };
+        let records = vec![
+            record("2024-03-02T09:00:00Z"),
+            record("2024-03-01T10:00:00Z"),
+            record("2024-03-01T23:59:59Z"),
+            record("not a date"),
+            HashMap::new(),
+        ];
+
+        let (buckets, skipped) = bucket_by_window(&records, TimeWindow::Daily);
+        assert_eq!(skipped, 2);
+        let sizes: Vec<usize> = buckets.values().map(|rows| rows.len()).collect();
+        assert_eq!(sizes, vec![2, 1]);
+        assert_eq!(
+            *buckets.keys().next().unwrap(),
+            Utc.with_ymd_and_hms(2024, 3, 1, 0, 0, 0).unwrap()
+        );
+    }
+}
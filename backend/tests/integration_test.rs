@@ -1,5 +1,85 @@
-use drift_guard_backend::storage::projects;
+use drift_guard_backend::models::{
+    AlertCursor, AlertFilter, AlertNotification, AlertOutcome, AlertRule, AlertRulesResponse,
+    AlertSort, AlertSummaryParams, AlertTransition, BinningConfig, BulkAlertAction,
+    CreateAlertParams, CreateEvaluationParams, DriftMetric, EvaluationFeature, FeatureHistory,
+    FeatureSettings, FeatureStats, NotificationEvent, NotificationSettings, SortOrder, TimeWindow,
+    UploadIncomingResponse, ValidateRulesResponse, WebhookFormat,
+};
+use drift_guard_backend::routes;
+use drift_guard_backend::services::alert_engine::{self, AlertContext, GeneratedAlerts};
+use drift_guard_backend::services::email::{self, Mailer, SmtpConfig, SmtpSecurity};
+use drift_guard_backend::services::live_events::{LiveEventKind, LiveEvents};
+use drift_guard_backend::services::webhooks::{self as webhook_service, RetryPolicy};
+use drift_guard_backend::services::{baseline_builder, drift_evaluator, rule_engine};
+use drift_guard_backend::storage::{
+    alert_events, alerts, baselines, evaluations, incidents, notifications, projects, settings,
+    webhooks, windows,
+};
 use sqlx::sqlite::SqlitePoolOptions;
+use sqlx::SqlitePool;
+
+/// Evaluates an incoming CSV and generates its alerts the way the upload route does:
+/// context loaded first, then alerts and the evaluation stored in one transaction.
+async fn upload_incoming(pool: &SqlitePool, project_id: &str, csv: &str) -> GeneratedAlerts {
+    let drift_result = drift_evaluator::evaluate_drift(pool, project_id, csv.as_bytes(), None)
+        .await
+        .unwrap();
+    let baseline = baselines::get_latest_baseline(pool, project_id)
+        .await
+        .unwrap()
+        .unwrap();
+    let baseline_features: Vec<FeatureStats> =
+        baselines::get_baseline_features(pool, &baseline.baseline_id)
+            .await
+            .unwrap()
+            .iter()
+            .filter_map(FeatureStats::from_baseline_feature)
+            .collect();
+    let headers: Vec<String> = csv
+        .lines()
+        .next()
+        .unwrap()
+        .split(',')
+        .map(str::to_string)
+        .collect();
+
+    let context = AlertContext::load(pool, project_id).await.unwrap();
+    let mut tx = pool.begin().await.unwrap();
+    let generated = alert_engine::generate_alerts(
+        &mut tx,
+        &context,
+        project_id,
+        baseline.baseline_version,
+        &drift_result,
+        &baseline_features,
+        &headers,
+    )
+    .await
+    .unwrap();
+    evaluations::create_evaluation(
+        &mut *tx,
+        &CreateEvaluationParams {
+            project_id: project_id.to_string(),
+            baseline_version: baseline.baseline_version,
+            row_count: drift_result.row_count,
+            prediction_rate: drift_result.incoming_prediction_rate,
+            prediction_shift: drift_result.prediction_shift,
+            drift_metric: drift_result.drift_metric,
+            health: generated.health.clone(),
+            alerts_created: generated.created,
+            content_hash: "test".to_string(),
+            features: drift_result
+                .feature_drifts
+                .iter()
+                .map(|(name, drift)| EvaluationFeature::from_drift(name, drift))
+                .collect(),
+        },
+    )
+    .await
+    .unwrap();
+    tx.commit().await.unwrap();
+    generated
+}
 
 #[tokio::test]
 async fn test_create_project() {
@@ -36,3 +116,2566 @@ async fn test_list_projects() {
     let all_projects = projects::list_projects(&pool).await.unwrap();
     assert_eq!(all_projects.len(), 2);
 }
+
+#[tokio::test]
+async fn test_project_settings_defaults_and_update() {
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+
+    sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+
+    let project = projects::create_project(&pool, "Settings Project")
+        .await
+        .unwrap();
+
+    let defaults = settings::get_project_settings(&pool, &project.project_id)
+        .await
+        .unwrap();
+    assert_eq!(defaults.drift_metric, DriftMetric::L1);
+    assert!(defaults.updated_at.is_none());
+
+    let mut updated = defaults.clone();
+    updated.drift_metric = DriftMetric::Psi;
+    updated.prediction_shift_critical_threshold = Some(0.3);
+    settings::upsert_project_settings(&pool, &updated)
+        .await
+        .unwrap();
+
+    let stored = settings::get_project_settings(&pool, &project.project_id)
+        .await
+        .unwrap();
+    assert_eq!(stored.drift_metric, DriftMetric::Psi);
+    assert_eq!(stored.prediction_shift_critical_threshold, Some(0.3));
+    assert_eq!(stored.feature_drift_warn_threshold, None);
+    assert!(stored.updated_at.is_some());
+}
+
+#[tokio::test]
+async fn test_feature_settings_overrides() {
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+
+    sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+
+    let project = projects::create_project(&pool, "Feature Settings")
+        .await
+        .unwrap();
+
+    let location = FeatureSettings {
+        project_id: project.project_id.clone(),
+        feature_name: "location".to_string(),
+        drift_warn_threshold: Some(0.3),
+        drift_critical_threshold: None,
+        updated_at: None,
+    };
+    settings::upsert_feature_settings(&pool, &location)
+        .await
+        .unwrap();
+    settings::upsert_feature_settings(
+        &pool,
+        &FeatureSettings {
+            drift_critical_threshold: Some(0.6),
+            ..location.clone()
+        },
+    )
+    .await
+    .unwrap();
+
+    let stored = settings::list_feature_settings(&pool, &project.project_id)
+        .await
+        .unwrap();
+    assert_eq!(stored.len(), 1);
+    assert_eq!(stored[0].drift_warn_threshold, Some(0.3));
+    assert_eq!(stored[0].drift_critical_threshold, Some(0.6));
+
+    assert!(
+        settings::delete_feature_settings(&pool, &project.project_id, "location")
+            .await
+            .unwrap()
+    );
+    assert!(
+        !settings::delete_feature_settings(&pool, &project.project_id, "location")
+            .await
+            .unwrap()
+    );
+}
+
+#[tokio::test]
+async fn test_windowed_drift_evaluation() {
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+
+    sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+
+    let project = projects::create_project(&pool, "Windowed").await.unwrap();
+    let baseline_csv = "prediction,income\n0,10\n0,20\n1,30\n0,40\n";
+    baseline_builder::build_baseline_from_csv(
+        &pool,
+        &project.project_id,
+        baseline_csv.as_bytes(),
+        &BinningConfig::default(),
+    )
+    .await
+    .unwrap();
+
+    // First day matches the baseline, the second has shifted
+    let incoming_csv = "prediction,income,timestamp\n\
+        0,10,2024-03-01T08:00:00Z\n\
+        0,30,2024-03-01T20:00:00Z\n\
+        1,400,2024-03-02T09:00:00Z\n\
+        1,500,2024-03-02T10:00:00Z\n\
+        0,25,\n";
+    let result = drift_evaluator::evaluate_drift(
+        &pool,
+        &project.project_id,
+        incoming_csv.as_bytes(),
+        Some(TimeWindow::Daily),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(result.skipped_timestamps, 1);
+    assert_eq!(result.windows.len(), 2);
+    assert_eq!(result.windows[0].row_count, 2);
+    assert!(
+        result.windows[0].feature_scores["income"] < result.windows[1].feature_scores["income"]
+    );
+    assert_eq!(result.windows[1].prediction_rate, 1.0);
+
+    windows::create_drift_windows(
+        &pool,
+        &project.project_id,
+        "evaluation-1",
+        1,
+        TimeWindow::Daily,
+        &result.windows,
+    )
+    .await
+    .unwrap();
+    let stored = windows::list_drift_windows(&pool, &project.project_id, None)
+        .await
+        .unwrap();
+    assert_eq!(stored.len(), 2);
+    assert!(stored[0].window_start < stored[1].window_start);
+    assert!(
+        windows::list_drift_windows(&pool, &project.project_id, Some(TimeWindow::Hourly))
+            .await
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[tokio::test]
+async fn test_non_finite_values_are_type_mismatches() {
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+
+    sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+
+    let project = projects::create_project(&pool, "NaN").await.unwrap();
+    let baseline_csv = "prediction,income\n0,10\n0,20\n1,NaN\n0,30\n1,40\n";
+    baseline_builder::build_baseline_from_csv(
+        &pool,
+        &project.project_id,
+        baseline_csv.as_bytes(),
+        &BinningConfig::default(),
+    )
+    .await
+    .unwrap();
+
+    let incoming_csv = "prediction,income\n0,15\n1,NaN\n0,inf\n0,35\n";
+    let result =
+        drift_evaluator::evaluate_drift(&pool, &project.project_id, incoming_csv.as_bytes(), None)
+            .await
+            .unwrap();
+
+    // NaN and inf are left out of the score and reported like other non-numbers
+    let income = &result.feature_drifts["income"];
+    assert!(income.score.is_finite());
+    assert_eq!(income.sample_size, 2);
+    assert_eq!(income.type_mismatches.as_ref().unwrap().count, 2);
+    assert!(income.significance.as_ref().unwrap().p_value.is_finite());
+}
+
+#[tokio::test]
+async fn test_evaluations_round_trip() {
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+
+    sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+
+    let project = projects::create_project(&pool, "Evaluations")
+        .await
+        .unwrap();
+    let other = projects::create_project(&pool, "Other").await.unwrap();
+
+    let created = evaluations::create_evaluation(
+        &pool,
+        &CreateEvaluationParams {
+            project_id: project.project_id.clone(),
+            baseline_version: 1,
+            row_count: 20,
+            prediction_rate: 0.25,
+            prediction_shift: 0.05,
+            drift_metric: DriftMetric::Psi,
+            health: "OK".to_string(),
+            alerts_created: 0,
+            content_hash: "abc123".to_string(),
+            features: vec![EvaluationFeature {
+                feature_name: "income".to_string(),
+                drift_score: 0.04,
+                sample_size: 20,
+                p_value: Some(0.6),
+                null_rate: Some(0.0),
+                unexpected_fraction: None,
+            }],
+        },
+    )
+    .await
+    .unwrap();
+    let evaluation_id = created.evaluation.evaluation_id;
+
+    let listed = evaluations::list_evaluations(&pool, &project.project_id)
+        .await
+        .unwrap();
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0].drift_metric, DriftMetric::Psi);
+    assert_eq!(listed[0].content_hash, "abc123");
+
+    let fetched = evaluations::get_evaluation(&pool, &project.project_id, &evaluation_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(fetched.features.len(), 1);
+    assert_eq!(fetched.features[0].p_value, Some(0.6));
+
+    // Evaluations are scoped to their project
+    assert!(
+        evaluations::get_evaluation(&pool, &other.project_id, &evaluation_id)
+            .await
+            .unwrap()
+            .is_none()
+    );
+}
+
+#[tokio::test]
+async fn test_alert_dedup_and_cooldown() {
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+
+    sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+
+    let project = projects::create_project(&pool, "Dedup").await.unwrap();
+    let params = |metric_value: f64| CreateAlertParams {
+        project_id: project.project_id.clone(),
+        baseline_version: 1,
+        severity: "WARN".to_string(),
+        alert_type: "FEATURE_DRIFT".to_string(),
+        feature_name: Some("income".to_string()),
+        metric_value: Some(metric_value),
+        message: format!("income drift {}", metric_value),
+        drift_metric: Some("L1".to_string()),
+        rule_name: Some("Feature drift WARN".to_string()),
+    };
+    let filter = AlertFilter {
+        status: None,
+        assignee: None,
+        severity: None,
+        feature_name: None,
+        alert_type: None,
+        start_time: None,
+        end_time: None,
+        incident_id: None,
+    };
+    let list = || async {
+        alerts::list_alerts(&pool, &project.project_id, &filter)
+            .await
+            .unwrap()
+    };
+
+    let first = alerts::record_alert(&pool, &params(0.2), 60).await.unwrap();
+    let AlertOutcome::Created(alert_id) = first else {
+        panic!("expected a new alert, got {:?}", first);
+    };
+
+    // A repeat while OPEN updates the existing alert
+    let repeat = alerts::record_alert(&pool, &params(0.3), 60).await.unwrap();
+    assert_eq!(repeat, AlertOutcome::Updated(alert_id.clone()));
+    let listed = list().await;
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0].occurrence_count, 2);
+    assert_eq!(listed[0].metric_value, Some(0.3));
+    assert_eq!(listed[0].rule_name.as_deref(), Some("Feature drift WARN"));
+
+    // Within the cooldown an acknowledged alert absorbs repeats
+    alerts::ack_alert(&pool, &project.project_id, &alert_id, "alice")
+        .await
+        .unwrap();
+    let suppressed = alerts::record_alert(&pool, &params(0.4), 60).await.unwrap();
+    assert_eq!(suppressed, AlertOutcome::Suppressed(alert_id.clone()));
+    let listed = list().await;
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0].status, "ACK");
+    assert_eq!(listed[0].occurrence_count, 3);
+    assert!(listed[0].acked_at.is_some());
+
+    // Once the cooldown is over a new alert is raised
+    let reopened = alerts::record_alert(&pool, &params(0.5), 0).await.unwrap();
+    assert!(reopened.is_created());
+    assert_eq!(list().await.len(), 2);
+}
+
+#[tokio::test]
+async fn test_alert_resolution() {
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+
+    sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+
+    let project = projects::create_project(&pool, "Resolution").await.unwrap();
+    let params = CreateAlertParams {
+        project_id: project.project_id.clone(),
+        baseline_version: 1,
+        severity: "CRITICAL".to_string(),
+        alert_type: "PREDICTION_SHIFT".to_string(),
+        feature_name: None,
+        metric_value: Some(0.4),
+        message: "Prediction rate shift".to_string(),
+        drift_metric: None,
+        rule_name: None,
+    };
+    let filter = AlertFilter {
+        status: Some("RESOLVED".to_string()),
+        assignee: None,
+        severity: None,
+        feature_name: None,
+        alert_type: None,
+        start_time: None,
+        end_time: None,
+        incident_id: None,
+    };
+
+    alerts::record_alert(&pool, &params, 0).await.unwrap();
+    let resolved = alerts::resolve_matching_alerts(
+        &pool,
+        &project.project_id,
+        "PREDICTION_SHIFT",
+        None,
+        "auto",
+        "Recovered to 0.0100",
+    )
+    .await
+    .unwrap();
+    assert_eq!(resolved.len(), 1);
+
+    let listed = alerts::list_alerts(&pool, &project.project_id, &filter)
+        .await
+        .unwrap();
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0].resolved_by.as_deref(), Some("auto"));
+    assert!(listed[0].resolved_at.is_some());
+
+    // A resolved alert is not reopened; the drift coming back raises a new one
+    assert!(alerts::record_alert(&pool, &params, 0)
+        .await
+        .unwrap()
+        .is_created());
+}
+
+#[tokio::test]
+async fn test_alert_lifecycle_and_audit_trail() {
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+
+    sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+
+    let project = projects::create_project(&pool, "Lifecycle").await.unwrap();
+    let params = CreateAlertParams {
+        project_id: project.project_id.clone(),
+        baseline_version: 1,
+        severity: "WARN".to_string(),
+        alert_type: "DATA_QUALITY".to_string(),
+        feature_name: Some("age".to_string()),
+        metric_value: Some(0.2),
+        message: "Feature 'age' null rate changed".to_string(),
+        drift_metric: None,
+        rule_name: None,
+    };
+    let filter = AlertFilter {
+        status: None,
+        assignee: Some("bob".to_string()),
+        severity: None,
+        feature_name: None,
+        alert_type: None,
+        start_time: None,
+        end_time: None,
+        incident_id: None,
+    };
+
+    let alert_id = match alerts::record_alert(&pool, &params, 0).await.unwrap() {
+        AlertOutcome::Created(alert_id) => alert_id,
+        other => panic!("expected a new alert, got {:?}", other),
+    };
+
+    // Repeats are absorbed while snoozed
+    let until = chrono::Utc::now() + chrono::Duration::hours(1);
+    alerts::snooze_alert(
+        &pool,
+        &project.project_id,
+        &alert_id,
+        until,
+        "alice",
+        Some("Upstream fix"),
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        alerts::record_alert(&pool, &params, 0).await.unwrap(),
+        AlertOutcome::Suppressed(alert_id.clone())
+    );
+
+    // Once the snooze is over the alert reopens
+    let past = chrono::Utc::now() - chrono::Duration::minutes(1);
+    alerts::snooze_alert(&pool, &project.project_id, &alert_id, past, "alice", None)
+        .await
+        .unwrap();
+    assert_eq!(
+        alerts::record_alert(&pool, &params, 0).await.unwrap(),
+        AlertOutcome::Updated(alert_id.clone())
+    );
+
+    alerts::assign_alert(&pool, &project.project_id, &alert_id, Some("bob"), "alice")
+        .await
+        .unwrap();
+    alerts::add_comment(
+        &pool,
+        &project.project_id,
+        &alert_id,
+        "bob",
+        "Known schema change",
+    )
+    .await
+    .unwrap();
+
+    let assigned = alerts::list_alerts(&pool, &project.project_id, &filter)
+        .await
+        .unwrap();
+    assert_eq!(assigned.len(), 1);
+    assert_eq!(assigned[0].status, "OPEN");
+    assert!(assigned[0].snoozed_until.is_none());
+
+    let events = alert_events::list_alert_events(&pool, &alert_id)
+        .await
+        .unwrap();
+    let event_types: Vec<&str> = events.iter().map(|e| e.event_type.as_str()).collect();
+    assert_eq!(
+        event_types,
+        vec!["CREATED", "SNOOZED", "SNOOZED", "REOPENED", "ASSIGNED", "COMMENT"]
+    );
+    assert_eq!(events[1].actor, "alice");
+    assert_eq!(events[1].from_status.as_deref(), Some("OPEN"));
+    assert_eq!(events[3].actor, "auto");
+    assert_eq!(events[5].details.as_deref(), Some("Known schema change"));
+}
+
+#[tokio::test]
+async fn test_invalid_alert_transitions_are_rejected() {
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+
+    sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+
+    let project = projects::create_project(&pool, "Transitions")
+        .await
+        .unwrap();
+    let alert = alerts::create_alert(
+        &pool,
+        &CreateAlertParams {
+            project_id: project.project_id.clone(),
+            baseline_version: 1,
+            severity: "WARN".to_string(),
+            alert_type: "FEATURE_DRIFT".to_string(),
+            feature_name: Some("age".to_string()),
+            metric_value: Some(0.2),
+            message: "Feature 'age' drifted".to_string(),
+            drift_metric: None,
+            rule_name: None,
+        },
+    )
+    .await
+    .unwrap();
+    let alert_id = alert.alert_id;
+    let until = chrono::Utc::now() + chrono::Duration::hours(1);
+
+    assert_eq!(
+        alerts::ack_alert(&pool, &project.project_id, &alert_id, "alice")
+            .await
+            .unwrap(),
+        AlertTransition::Applied
+    );
+    // An acknowledged alert can't be acknowledged again
+    assert_eq!(
+        alerts::ack_alert(&pool, &project.project_id, &alert_id, "bob")
+            .await
+            .unwrap(),
+        AlertTransition::NotAllowed("ACK".to_string())
+    );
+    // and bulk actions follow the same rule
+    assert_eq!(
+        alerts::bulk_update_alerts(
+            &pool,
+            &project.project_id,
+            &AlertFilter::default(),
+            BulkAlertAction::Ack,
+            "bob",
+            None,
+        )
+        .await
+        .unwrap(),
+        Vec::<String>::new()
+    );
+
+    assert_eq!(
+        alerts::resolve_alert(&pool, &project.project_id, &alert_id, "alice", None)
+            .await
+            .unwrap(),
+        AlertTransition::Applied
+    );
+    // A resolved alert stays resolved, by whoever resolved it
+    assert_eq!(
+        alerts::ack_alert(&pool, &project.project_id, &alert_id, "bob")
+            .await
+            .unwrap(),
+        AlertTransition::NotAllowed("RESOLVED".to_string())
+    );
+    assert_eq!(
+        alerts::snooze_alert(&pool, &project.project_id, &alert_id, until, "bob", None)
+            .await
+            .unwrap(),
+        AlertTransition::NotAllowed("RESOLVED".to_string())
+    );
+    assert_eq!(
+        alerts::resolve_alert(&pool, &project.project_id, &alert_id, "bob", Some("Again"))
+            .await
+            .unwrap(),
+        AlertTransition::NotAllowed("RESOLVED".to_string())
+    );
+
+    let resolved = alerts::get_alert(&pool, &project.project_id, &alert_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(resolved.status, "RESOLVED");
+    assert_eq!(resolved.resolved_by.as_deref(), Some("alice"));
+    assert!(resolved.snoozed_until.is_none());
+
+    let events = alert_events::list_alert_events(&pool, &alert_id)
+        .await
+        .unwrap();
+    let event_types: Vec<&str> = events.iter().map(|e| e.event_type.as_str()).collect();
+    assert_eq!(event_types, vec!["CREATED", "ACK", "RESOLVED"]);
+}
+
+#[tokio::test]
+async fn test_alert_updates_are_published() {
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+
+    sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+
+    let project = projects::create_project(&pool, "Live").await.unwrap();
+    let alert = alerts::create_alert(
+        &pool,
+        &CreateAlertParams {
+            project_id: project.project_id.clone(),
+            baseline_version: 1,
+            severity: "CRITICAL".to_string(),
+            alert_type: "PREDICTION_SHIFT".to_string(),
+            feature_name: None,
+            metric_value: Some(0.3),
+            message: "Prediction rate shifted".to_string(),
+            drift_metric: None,
+            rule_name: None,
+        },
+    )
+    .await
+    .unwrap();
+    let live_events = LiveEvents::new();
+    let mut receiver = live_events.subscribe();
+
+    let acked = alerts::bulk_update_alerts(
+        &pool,
+        &project.project_id,
+        &AlertFilter::default(),
+        BulkAlertAction::Ack,
+        "alice",
+        None,
+    )
+    .await
+    .unwrap();
+    live_events
+        .publish_updated(&pool, &project.project_id, &acked)
+        .await;
+
+    // Subscribers get the alert as it stands after the change
+    let event = receiver.try_recv().unwrap();
+    assert_eq!(event.kind, LiveEventKind::AlertUpdated);
+    assert_eq!(event.project_id, project.project_id);
+    assert_eq!(event.data["alert_id"], alert.alert_id.as_str());
+    assert_eq!(event.data["status"], "ACK");
+
+    // Alerts that no longer exist are skipped
+    live_events
+        .publish_updated(&pool, &project.project_id, &["missing".to_string()])
+        .await;
+    assert!(receiver.try_recv().is_err());
+}
+
+#[tokio::test]
+async fn test_alert_actions_are_scoped_and_bulk() {
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+
+    sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+
+    let project = projects::create_project(&pool, "Bulk").await.unwrap();
+    let other = projects::create_project(&pool, "Other").await.unwrap();
+    let params = |severity: &str, feature_name: &str| CreateAlertParams {
+        project_id: project.project_id.clone(),
+        baseline_version: 1,
+        severity: severity.to_string(),
+        alert_type: "SCHEMA".to_string(),
+        feature_name: Some(feature_name.to_string()),
+        metric_value: None,
+        message: format!("Extra feature detected: {}", feature_name),
+        drift_metric: None,
+        rule_name: None,
+    };
+
+    let warn = alerts::create_alert(&pool, &params("WARN", "zip"))
+        .await
+        .unwrap();
+    alerts::create_alert(&pool, &params("WARN", "city"))
+        .await
+        .unwrap();
+    alerts::create_alert(&pool, &params("CRITICAL", "zip"))
+        .await
+        .unwrap();
+
+    // Alert IDs are only valid within their own project
+    assert_eq!(
+        alerts::ack_alert(&pool, &other.project_id, &warn.alert_id, "alice")
+            .await
+            .unwrap(),
+        AlertTransition::NotFound
+    );
+    assert_eq!(
+        alerts::ack_alert(&pool, &project.project_id, "missing", "alice")
+            .await
+            .unwrap(),
+        AlertTransition::NotFound
+    );
+
+    let warn_zip = AlertFilter {
+        severity: Some("WARN".to_string()),
+        feature_name: Some("zip".to_string()),
+        alert_type: Some("SCHEMA".to_string()),
+        ..AlertFilter::default()
+    };
+    let acked = alerts::bulk_update_alerts(
+        &pool,
+        &project.project_id,
+        &warn_zip,
+        BulkAlertAction::Ack,
+        "alice",
+        None,
+    )
+    .await
+    .unwrap();
+    assert_eq!(acked, vec![warn.alert_id.clone()]);
+
+    // Resolving everything picks up the ACK alert too; a repeat finds nothing left
+    let resolved = alerts::bulk_update_alerts(
+        &pool,
+        &project.project_id,
+        &AlertFilter::default(),
+        BulkAlertAction::Resolve,
+        "alice",
+        Some("Schema migration finished"),
+    )
+    .await
+    .unwrap();
+    assert_eq!(resolved.len(), 3);
+    let repeat = alerts::bulk_update_alerts(
+        &pool,
+        &project.project_id,
+        &AlertFilter::default(),
+        BulkAlertAction::Resolve,
+        "alice",
+        None,
+    )
+    .await
+    .unwrap();
+    assert!(repeat.is_empty());
+
+    let events = alert_events::list_alert_events(&pool, &warn.alert_id)
+        .await
+        .unwrap();
+    let transitions: Vec<_> = events
+        .iter()
+        .map(|e| (e.from_status.as_deref(), e.to_status.as_deref()))
+        .collect();
+    assert_eq!(
+        transitions,
+        vec![
+            (None, Some("OPEN")),
+            (Some("OPEN"), Some("ACK")),
+            (Some("ACK"), Some("RESOLVED"))
+        ]
+    );
+}
+
+#[actix_web::test]
+async fn test_webhook_delivery_signs_and_retries() {
+    use actix_web::{web, App, HttpRequest, HttpResponse, HttpServer};
+    use std::sync::{Arc, Mutex};
+
+    // Local receiver that fails the first request and accepts the rest
+    type Received = Arc<Mutex<Vec<(String, String, Vec<u8>)>>>;
+    let received: Received = Arc::new(Mutex::new(Vec::new()));
+    let server_received = received.clone();
+    let server = HttpServer::new(move || {
+        App::new()
+            .app_data(web::Data::new(server_received.clone()))
+            .route(
+                "/hook",
+                web::post().to(
+                    |req: HttpRequest, body: web::Bytes, received: web::Data<Received>| async move {
+                        let header = |name: &str| {
+                            req.headers()
+                                .get(name)
+                                .and_then(|v| v.to_str().ok())
+                                .unwrap_or_default()
+                                .to_string()
+                        };
+                        let mut received = received.lock().unwrap();
+                        received.push((
+                            header(webhook_service::SIGNATURE_HEADER),
+                            header(webhook_service::EVENT_HEADER),
+                            body.to_vec(),
+                        ));
+                        if received.len() == 1 {
+                            HttpResponse::InternalServerError().finish()
+                        } else {
+                            HttpResponse::Ok().finish()
+                        }
+                    },
+                ),
+            )
+    })
+    .workers(1)
+    .bind(("127.0.0.1", 0))
+    .unwrap();
+    let url = format!("http://{}/hook", server.addrs()[0]);
+    actix_web::rt::spawn(server.run());
+
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+
+    sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+
+    let project = projects::create_project(&pool, "Webhooks").await.unwrap();
+    let webhook = webhooks::create_webhook(
+        &pool,
+        &project.project_id,
+        &url,
+        "s3cret",
+        "WARN",
+        WebhookFormat::Json,
+    )
+    .await
+    .unwrap();
+    webhooks::create_webhook(
+        &pool,
+        &project.project_id,
+        &url,
+        "other",
+        "CRITICAL",
+        WebhookFormat::Json,
+    )
+    .await
+    .unwrap();
+    let slack = webhooks::create_webhook(
+        &pool,
+        &project.project_id,
+        &url,
+        "slack",
+        "WARN",
+        WebhookFormat::Slack,
+    )
+    .await
+    .unwrap();
+
+    let params = |severity: &str| CreateAlertParams {
+        project_id: project.project_id.clone(),
+        baseline_version: 1,
+        severity: severity.to_string(),
+        alert_type: "PREDICTION_SHIFT".to_string(),
+        feature_name: None,
+        metric_value: Some(0.15),
+        message: "Prediction rate shift".to_string(),
+        drift_metric: None,
+        rule_name: None,
+    };
+    let outcome = alerts::record_alert(&pool, &params("WARN"), 0)
+        .await
+        .unwrap();
+    let notification = outcome.notification().unwrap();
+    assert!(alerts::record_alert(&pool, &params("WARN"), 0)
+        .await
+        .unwrap()
+        .notification()
+        .is_none());
+    assert!(matches!(
+        alerts::record_alert(&pool, &params("CRITICAL"), 0)
+            .await
+            .unwrap(),
+        AlertOutcome::Escalated(_)
+    ));
+
+    let evaluation = evaluations::create_evaluation(
+        &pool,
+        &CreateEvaluationParams {
+            project_id: project.project_id.clone(),
+            baseline_version: 1,
+            row_count: 20,
+            prediction_rate: 0.2,
+            prediction_shift: 0.15,
+            drift_metric: DriftMetric::L1,
+            health: "WARN".to_string(),
+            alerts_created: 1,
+            content_hash: "abc123".to_string(),
+            features: vec![EvaluationFeature {
+                feature_name: "income".to_string(),
+                drift_score: 0.42,
+                sample_size: 20,
+                p_value: Some(0.01),
+                null_rate: Some(0.0),
+                unexpected_fraction: None,
+            }],
+        },
+    )
+    .await
+    .unwrap();
+    let upload = UploadIncomingResponse {
+        evaluation_id: evaluation.evaluation.evaluation_id.clone(),
+        rows_processed: 20,
+        alerts_created: 1,
+        alerts_resolved: 0,
+        incident_id: None,
+        health: "CRITICAL".to_string(),
+        drift_metric: DriftMetric::L1,
+        window: None,
+        windows: Vec::new(),
+        skipped_timestamps: 0,
+    };
+
+    // The alert is CRITICAL by now, so every webhook is sent it
+    webhook_service::notify_alerts(
+        &pool,
+        &project.project_id,
+        &evaluation,
+        &upload,
+        &[notification],
+        RetryPolicy {
+            max_attempts: 3,
+            initial_backoff: std::time::Duration::from_millis(10),
+        },
+    )
+    .await
+    .unwrap();
+
+    let received = received.lock().unwrap().clone();
+    assert_eq!(received.len(), 4);
+    let (signature, event, body) = &received[1];
+    assert_eq!(event, "alert.created");
+    assert_eq!(signature, &webhook_service::sign_payload("s3cret", body));
+    let payload: serde_json::Value = serde_json::from_slice(body).unwrap();
+    assert_eq!(payload["project"]["name"], "Webhooks");
+    assert_eq!(payload["alert"]["severity"], "CRITICAL");
+    assert_eq!(
+        payload["evaluation"]["evaluation_id"],
+        evaluation.evaluation.evaluation_id.as_str()
+    );
+
+    // The Slack webhook gets one summary message for the upload
+    let (signature, event, body) = &received[3];
+    assert_eq!(event, "alerts.summary");
+    assert_eq!(signature, &webhook_service::sign_payload("slack", body));
+    let message: serde_json::Value = serde_json::from_slice(body).unwrap();
+    assert_eq!(message["text"], "DriftGuard: Webhooks is CRITICAL");
+    let blocks = message["blocks"].to_string();
+    assert!(blocks.contains("`income`: 0.420"));
+    assert!(blocks.contains("CRITICAL PREDICTION_SHIFT: Prediction rate shift"));
+    let slack_deliveries = webhooks::list_deliveries(&pool, &slack.webhook_id, 10)
+        .await
+        .unwrap();
+    assert_eq!(slack_deliveries[0].alert_id, None);
+
+    let deliveries = webhooks::list_deliveries(&pool, &webhook.webhook_id, 10)
+        .await
+        .unwrap();
+    assert_eq!(deliveries.len(), 1);
+    assert_eq!(deliveries[0].status, "SUCCESS");
+    assert_eq!(deliveries[0].attempts, 2);
+    assert_eq!(deliveries[0].response_status, Some(200));
+}
+
+#[actix_web::test]
+async fn test_failed_webhook_does_not_stop_the_others() {
+    use actix_web::{web, App, HttpResponse, HttpServer};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    let received = Arc::new(AtomicUsize::new(0));
+    let server_received = received.clone();
+    let server = HttpServer::new(move || {
+        let received = server_received.clone();
+        App::new().route(
+            "/hook",
+            web::post().to(move || {
+                received.fetch_add(1, Ordering::SeqCst);
+                async { HttpResponse::Ok().finish() }
+            }),
+        )
+    })
+    .workers(1)
+    .bind(("127.0.0.1", 0))
+    .unwrap();
+    let url = format!("http://{}/hook", server.addrs()[0]);
+    actix_web::rt::spawn(server.run());
+
+    // A port nothing listens on
+    let closed = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let closed_url = format!("http://{}/hook", closed.local_addr().unwrap());
+    drop(closed);
+
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+
+    sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+
+    let project = projects::create_project(&pool, "Webhooks").await.unwrap();
+    let broken = webhooks::create_webhook(
+        &pool,
+        &project.project_id,
+        &closed_url,
+        "broken",
+        "WARN",
+        WebhookFormat::Json,
+    )
+    .await
+    .unwrap();
+    let working = webhooks::create_webhook(
+        &pool,
+        &project.project_id,
+        &url,
+        "working",
+        "WARN",
+        WebhookFormat::Json,
+    )
+    .await
+    .unwrap();
+    // The broken webhook goes first, and logging its delivery fails as well
+    sqlx::query("UPDATE webhooks SET created_at = ?1 WHERE webhook_id = ?2")
+        .bind((chrono::Utc::now() - chrono::Duration::hours(1)).to_rfc3339())
+        .bind(&broken.webhook_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query(&format!(
+        "CREATE TRIGGER fail_delivery_log BEFORE INSERT ON webhook_deliveries \
+         WHEN NEW.webhook_id = '{}' BEGIN SELECT RAISE(ABORT, 'disk full'); END",
+        broken.webhook_id
+    ))
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let outcome = alerts::record_alert(
+        &pool,
+        &CreateAlertParams {
+            project_id: project.project_id.clone(),
+            baseline_version: 1,
+            severity: "WARN".to_string(),
+            alert_type: "PREDICTION_SHIFT".to_string(),
+            feature_name: None,
+            metric_value: Some(0.15),
+            message: "Prediction rate shift".to_string(),
+            drift_metric: None,
+            rule_name: None,
+        },
+        0,
+    )
+    .await
+    .unwrap();
+    let evaluation = evaluations::create_evaluation(
+        &pool,
+        &CreateEvaluationParams {
+            project_id: project.project_id.clone(),
+            baseline_version: 1,
+            row_count: 20,
+            prediction_rate: 0.2,
+            prediction_shift: 0.15,
+            drift_metric: DriftMetric::L1,
+            health: "WARN".to_string(),
+            alerts_created: 1,
+            content_hash: "abc123".to_string(),
+            features: Vec::new(),
+        },
+    )
+    .await
+    .unwrap();
+    let upload = UploadIncomingResponse {
+        evaluation_id: evaluation.evaluation.evaluation_id.clone(),
+        rows_processed: 20,
+        alerts_created: 1,
+        alerts_resolved: 0,
+        incident_id: None,
+        health: "WARN".to_string(),
+        drift_metric: DriftMetric::L1,
+        window: None,
+        windows: Vec::new(),
+        skipped_timestamps: 0,
+    };
+
+    webhook_service::notify_alerts(
+        &pool,
+        &project.project_id,
+        &evaluation,
+        &upload,
+        &[outcome.notification().unwrap()],
+        RetryPolicy {
+            max_attempts: 2,
+            initial_backoff: std::time::Duration::from_millis(10),
+        },
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(received.load(Ordering::SeqCst), 1);
+    assert!(webhooks::list_deliveries(&pool, &broken.webhook_id, 10)
+        .await
+        .unwrap()
+        .is_empty());
+    let deliveries = webhooks::list_deliveries(&pool, &working.webhook_id, 10)
+        .await
+        .unwrap();
+    assert_eq!(deliveries.len(), 1);
+    assert_eq!(deliveries[0].status, "SUCCESS");
+}
+
+/// A minimal SMTP server that accepts every message and keeps its data, with
+/// folded headers and quoted-printable soft line breaks joined back up.
+async fn smtp_sink() -> (u16, std::sync::Arc<std::sync::Mutex<Vec<String>>>) {
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let messages = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    let sink = messages.clone();
+    tokio::spawn(async move {
+        loop {
+            let (socket, _) = listener.accept().await.unwrap();
+            let sink = sink.clone();
+            tokio::spawn(async move {
+                let (reader, mut writer) = socket.into_split();
+                let mut lines = BufReader::new(reader).lines();
+                writer.write_all(b"220 sink ESMTP\r\n").await.unwrap();
+                let mut data: Option<String> = None;
+                while let Ok(Some(line)) = lines.next_line().await {
+                    if let Some(message) = data.as_mut() {
+                        if line == "." {
+                            let message = data.take().unwrap();
+                            sink.lock()
+                                .unwrap()
+                                .push(message.replace("\n ", " ").replace("=\n", ""));
+                            writer.write_all(b"250 OK\r\n").await.unwrap();
+                        } else {
+                            message.push_str(&line);
+                            message.push('\n');
+                        }
+                        continue;
+                    }
+                    let command = line.get(..4).unwrap_or(&line).to_ascii_uppercase();
+                    let reply: &[u8] = match command.as_str() {
+                        "DATA" => {
+                            data = Some(String::new());
+                            b"354 End data with <CR><LF>.<CR><LF>\r\n"
+                        }
+                        "QUIT" => b"221 Bye\r\n",
+                        _ => b"250 OK\r\n",
+                    };
+                    writer.write_all(reply).await.unwrap();
+                    if command == "QUIT" {
+                        break;
+                    }
+                }
+            });
+        }
+    });
+    (port, messages)
+}
+
+#[tokio::test]
+async fn test_email_notifications_and_digest() {
+    let (port, messages) = smtp_sink().await;
+    let mailer = Mailer::new(&SmtpConfig {
+        host: "127.0.0.1".to_string(),
+        port,
+        username: None,
+        password: None,
+        from: "driftguard@example.com".to_string(),
+        security: SmtpSecurity::None,
+    })
+    .unwrap();
+
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+
+    sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+
+    let project = projects::create_project(&pool, "Mail").await.unwrap();
+    let mut notification_settings = NotificationSettings::defaults(&project.project_id);
+    notification_settings.email_recipients =
+        vec!["ops@example.com".to_string(), "ml@example.com".to_string()];
+    notifications::upsert_notification_settings(&pool, &notification_settings)
+        .await
+        .unwrap();
+
+    let params = |severity: &str, alert_type: &str| CreateAlertParams {
+        project_id: project.project_id.clone(),
+        baseline_version: 1,
+        severity: severity.to_string(),
+        alert_type: alert_type.to_string(),
+        feature_name: Some("income".to_string()),
+        metric_value: Some(0.3),
+        message: format!("{} alert", severity),
+        drift_metric: None,
+        rule_name: None,
+    };
+    let mut raised: Vec<AlertNotification> = Vec::new();
+    for (severity, alert_type) in [("WARN", "FEATURE_DRIFT"), ("CRITICAL", "OUT_OF_RANGE")] {
+        let outcome = alerts::record_alert(&pool, &params(severity, alert_type), 0)
+            .await
+            .unwrap();
+        raised.push(outcome.notification().unwrap());
+    }
+
+    // Only the CRITICAL alert is mailed straight away
+    email::notify_alerts(&pool, &mailer, &project.project_id, &raised)
+        .await
+        .unwrap();
+    {
+        let messages = messages.lock().unwrap();
+        assert_eq!(messages.len(), 1);
+        assert!(messages[0].contains("To: ops@example.com, ml@example.com"));
+        assert!(messages[0].contains(
+            "Subject: [DriftGuard] New CRITICAL alert in Mail: [CRITICAL] OUT_OF_RANGE (income)"
+        ));
+        assert!(messages[0].contains("CRITICAL alert\n"));
+    }
+
+    // The digest covers both open alerts and is not due again the same day
+    let now = chrono::Utc::now();
+    let digest = email::send_digest(&pool, &mailer, &project.project_id, now)
+        .await
+        .unwrap();
+    assert!(digest.sent);
+    assert_eq!(digest.recipients, 2);
+    assert_eq!(digest.open_alerts, 2);
+    {
+        let messages = messages.lock().unwrap();
+        assert_eq!(messages.len(), 2);
+        assert!(messages[1].contains("Subject: [DriftGuard] Daily digest for Mail: 2 open alerts"));
+        assert!(messages[1].contains("- [CRITICAL] OUT_OF_RANGE (income): CRITICAL alert"));
+        assert!(messages[1].contains("- [WARN] FEATURE_DRIFT (income): WARN alert"));
+    }
+    let stored = notifications::get_notification_settings(&pool, &project.project_id)
+        .await
+        .unwrap();
+    assert_eq!(stored.last_digest_at, Some(now));
+    assert!(!email::digest_due(&stored, now));
+
+    // Switching immediate mails off silences new CRITICAL alerts
+    notification_settings.email_immediate = false;
+    notifications::upsert_notification_settings(&pool, &notification_settings)
+        .await
+        .unwrap();
+    email::notify_alerts(&pool, &mailer, &project.project_id, &raised)
+        .await
+        .unwrap();
+    assert_eq!(messages.lock().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn test_incidents_group_alerts_and_follow_their_status() {
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+
+    sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+
+    let project = projects::create_project(&pool, "Incidents").await.unwrap();
+    let raise = |severity: &str, alert_type: &str, feature_name: Option<&str>| {
+        let params = CreateAlertParams {
+            project_id: project.project_id.clone(),
+            baseline_version: 1,
+            severity: severity.to_string(),
+            alert_type: alert_type.to_string(),
+            feature_name: feature_name.map(str::to_string),
+            metric_value: Some(0.3),
+            message: format!("{} alert", alert_type),
+            drift_metric: None,
+            rule_name: None,
+        };
+        let pool = pool.clone();
+        async move {
+            match alerts::record_alert(&pool, &params, 0).await.unwrap() {
+                AlertOutcome::Created(alert_id) => alert_id,
+                other => panic!("expected a new alert, got {:?}", other),
+            }
+        }
+    };
+
+    // One upload: a schema alert, two drifted features and a prediction shift
+    let first = vec![
+        raise("CRITICAL", "SCHEMA", None).await,
+        raise("WARN", "FEATURE_DRIFT", Some("income")).await,
+        raise("WARN", "FEATURE_DRIFT", Some("age")).await,
+        raise("WARN", "PREDICTION_SHIFT", None).await,
+    ];
+    let incident_id = incidents::group_alerts(&pool, &project.project_id, &first, 0)
+        .await
+        .unwrap();
+
+    let incident = incidents::get_incident(&pool, &project.project_id, &incident_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(incident.severity, "CRITICAL");
+    assert_eq!(incident.status, "OPEN");
+    assert_eq!(incident.alert_count, 4);
+    assert_eq!(incident.alert_types.len(), 3);
+    let alert = alerts::get_alert(&pool, &project.project_id, &first[1])
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(alert.incident_id.as_deref(), Some(incident_id.as_str()));
+
+    // Without a window the next upload opens its own incident; within one it joins
+    let second = vec![raise("WARN", "DATA_QUALITY", Some("zip")).await];
+    let separate = incidents::group_alerts(&pool, &project.project_id, &second, 0)
+        .await
+        .unwrap();
+    assert_ne!(separate, incident_id);
+    let third = vec![raise("WARN", "NEW_CATEGORY", Some("zip")).await];
+    let joined = incidents::group_alerts(&pool, &project.project_id, &third, 60)
+        .await
+        .unwrap();
+    assert_eq!(joined, separate);
+
+    // Acking the incident's alerts as a unit moves it to ACK
+    let filter = AlertFilter {
+        incident_id: Some(incident_id.clone()),
+        ..AlertFilter::default()
+    };
+    let acked = alerts::bulk_update_alerts(
+        &pool,
+        &project.project_id,
+        &filter,
+        BulkAlertAction::Ack,
+        "oncall",
+        None,
+    )
+    .await
+    .unwrap();
+    assert_eq!(acked.len(), 4);
+    let incident = incidents::get_incident(&pool, &project.project_id, &incident_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(incident.status, "ACK");
+    assert_eq!(incident.open_alerts, 0);
+
+    // It resolves once all of its alerts are, however they were resolved
+    alerts::resolve_alert(&pool, &project.project_id, &first[0], "oncall", None)
+        .await
+        .unwrap();
+    alerts::bulk_update_alerts(
+        &pool,
+        &project.project_id,
+        &filter,
+        BulkAlertAction::Resolve,
+        "oncall",
+        Some("Upstream deploy rolled back"),
+    )
+    .await
+    .unwrap();
+    let resolved = incidents::list_incidents(&pool, &project.project_id, Some("RESOLVED"))
+        .await
+        .unwrap();
+    assert_eq!(resolved.len(), 1);
+    assert_eq!(resolved[0].incident_id, incident_id);
+    let open = incidents::list_incidents(&pool, &project.project_id, Some("OPEN"))
+        .await
+        .unwrap();
+    assert_eq!(open.len(), 1);
+    assert_eq!(open[0].alert_count, 2);
+
+    // A resolved incident is not joined even within the window
+    alerts::bulk_update_alerts(
+        &pool,
+        &project.project_id,
+        &AlertFilter {
+            incident_id: Some(separate.clone()),
+            ..AlertFilter::default()
+        },
+        BulkAlertAction::Resolve,
+        "oncall",
+        None,
+    )
+    .await
+    .unwrap();
+    let fourth = vec![raise("WARN", "FEATURE_DRIFT", Some("income")).await];
+    let newest = incidents::group_alerts(&pool, &project.project_id, &fourth, 60)
+        .await
+        .unwrap();
+    assert_ne!(newest, separate);
+    assert_ne!(newest, incident_id);
+}
+
+#[tokio::test]
+async fn test_upload_writes_share_one_transaction() {
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+
+    sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+
+    let project = projects::create_project(&pool, "Upload").await.unwrap();
+    let alert = CreateAlertParams {
+        project_id: project.project_id.clone(),
+        baseline_version: 1,
+        severity: "WARN".to_string(),
+        alert_type: "FEATURE_DRIFT".to_string(),
+        feature_name: Some("income".to_string()),
+        metric_value: Some(0.15),
+        message: "Feature 'income' drifted".to_string(),
+        drift_metric: Some("L1".to_string()),
+        rule_name: None,
+    };
+    let evaluation = CreateEvaluationParams {
+        project_id: project.project_id.clone(),
+        baseline_version: 1,
+        row_count: 20,
+        prediction_rate: 0.2,
+        prediction_shift: 0.05,
+        drift_metric: DriftMetric::L1,
+        health: "WARN".to_string(),
+        alerts_created: 1,
+        content_hash: "abc123".to_string(),
+        features: vec![],
+    };
+    let store = |commit: bool| {
+        let pool = pool.clone();
+        let (alert, evaluation) = (alert.clone(), evaluation.clone());
+        async move {
+            let mut tx = pool.begin().await.unwrap();
+            let outcome = alerts::record_alert(&mut *tx, &alert, 0).await.unwrap();
+            incidents::group_alerts(
+                &mut *tx,
+                &alert.project_id,
+                &[outcome.alert_id().to_string()],
+                30,
+            )
+            .await
+            .unwrap();
+            evaluations::create_evaluation(&mut *tx, &evaluation)
+                .await
+                .unwrap();
+            if commit {
+                tx.commit().await.unwrap();
+            }
+            outcome
+        }
+    };
+
+    // An upload that fails before committing leaves no alert for a retry to fold into
+    assert!(store(false).await.is_created());
+    let filter = AlertFilter::default();
+    assert!(alerts::list_alerts(&pool, &project.project_id, &filter)
+        .await
+        .unwrap()
+        .is_empty());
+    assert!(incidents::list_incidents(&pool, &project.project_id, None)
+        .await
+        .unwrap()
+        .is_empty());
+    assert!(evaluations::list_evaluations(&pool, &project.project_id)
+        .await
+        .unwrap()
+        .is_empty());
+
+    assert!(store(true).await.is_created());
+    let listed = alerts::list_alerts(&pool, &project.project_id, &filter)
+        .await
+        .unwrap();
+    assert_eq!(listed.len(), 1);
+    assert!(listed[0].incident_id.is_some());
+    assert_eq!(
+        evaluations::list_evaluations(&pool, &project.project_id)
+            .await
+            .unwrap()
+            .len(),
+        1
+    );
+}
+
+#[tokio::test]
+async fn test_upload_generates_alerts_on_one_connection() {
+    // One connection, as the upload holds it for its transaction the whole time
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .acquire_timeout(std::time::Duration::from_secs(5))
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+
+    sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+
+    let project = projects::create_project(&pool, "Upload").await.unwrap();
+    baseline_builder::build_baseline_from_csv(
+        &pool,
+        &project.project_id,
+        "prediction,income\n0,10\n0,20\n1,30\n0,40\n0,25\n".as_bytes(),
+        &BinningConfig::default(),
+    )
+    .await
+    .unwrap();
+
+    let generated = upload_incoming(
+        &pool,
+        &project.project_id,
+        "prediction,income\n1,400\n1,450\n1,500\n1,550\n",
+    )
+    .await;
+    assert_eq!(generated.health, "CRITICAL");
+    assert!(generated.created > 0);
+    assert!(generated.incident_id.is_some());
+
+    let listed = alerts::list_alerts(&pool, &project.project_id, &AlertFilter::default())
+        .await
+        .unwrap();
+    assert_eq!(listed.len(), generated.created);
+    let evaluations = evaluations::list_evaluations(&pool, &project.project_id)
+        .await
+        .unwrap();
+    assert_eq!(evaluations.len(), 1);
+    assert_eq!(evaluations[0].alerts_created, generated.created);
+}
+
+#[tokio::test]
+async fn test_repeated_uploads_update_escalate_and_cool_down() {
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+
+    sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+
+    let project = projects::create_project(&pool, "Repeats").await.unwrap();
+    // 20 rows with `positives` predicted 1 and the same income spread every time
+    let csv = |positives: usize| {
+        let mut csv = "prediction,income\n".to_string();
+        for i in 0..20 {
+            csv.push_str(&format!(
+                "{},{}\n",
+                u8::from(i < positives),
+                10 * (i % 5 + 1)
+            ));
+        }
+        csv
+    };
+    baseline_builder::build_baseline_from_csv(
+        &pool,
+        &project.project_id,
+        csv(4).as_bytes(),
+        &BinningConfig::default(),
+    )
+    .await
+    .unwrap();
+    let shift_alerts = || {
+        let pool = pool.clone();
+        let project_id = project.project_id.clone();
+        async move {
+            let filter = AlertFilter {
+                alert_type: Some("PREDICTION_SHIFT".to_string()),
+                ..AlertFilter::default()
+            };
+            alerts::list_alerts(&pool, &project_id, &filter)
+                .await
+                .unwrap()
+        }
+    };
+
+    // Prediction rate 0.2 -> 0.35 is a WARN shift
+    let first = upload_incoming(&pool, &project.project_id, &csv(7)).await;
+    assert_eq!(first.created, 1);
+    let alert_id = first.notifications[0].alert_id.clone();
+    assert_eq!(
+        first.notifications[0].event,
+        NotificationEvent::AlertCreated
+    );
+
+    // The same drift again is folded into the open alert, without a notification
+    let repeat = upload_incoming(&pool, &project.project_id, &csv(7)).await;
+    assert_eq!(repeat.created, 0);
+    assert!(repeat.notifications.is_empty());
+    let listed = shift_alerts().await;
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0].occurrence_count, 2);
+    assert_eq!(listed[0].severity, "WARN");
+
+    // 0.2 -> 0.6 is CRITICAL and escalates the open alert
+    let escalated = upload_incoming(&pool, &project.project_id, &csv(12)).await;
+    assert_eq!(escalated.created, 0);
+    assert_eq!(
+        escalated.notifications,
+        vec![AlertNotification {
+            alert_id: alert_id.clone(),
+            event: NotificationEvent::AlertEscalated,
+        }]
+    );
+    let listed = shift_alerts().await;
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0].severity, "CRITICAL");
+    assert_eq!(listed[0].occurrence_count, 3);
+
+    // Within the cooldown an acknowledged alert absorbs the repeat and stays ACK
+    let mut project_settings = settings::get_project_settings(&pool, &project.project_id)
+        .await
+        .unwrap();
+    project_settings.alert_cooldown_minutes = 60;
+    settings::upsert_project_settings(&pool, &project_settings)
+        .await
+        .unwrap();
+    alerts::ack_alert(&pool, &project.project_id, &alert_id, "oncall")
+        .await
+        .unwrap();
+    let suppressed = upload_incoming(&pool, &project.project_id, &csv(12)).await;
+    assert_eq!(suppressed.created, 0);
+    assert!(suppressed.notifications.is_empty());
+    let listed = shift_alerts().await;
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0].status, "ACK");
+    assert_eq!(listed[0].occurrence_count, 4);
+
+    // Once the cooldown is over the drift is raised as a new alert
+    project_settings.alert_cooldown_minutes = 0;
+    settings::upsert_project_settings(&pool, &project_settings)
+        .await
+        .unwrap();
+    let reraised = upload_incoming(&pool, &project.project_id, &csv(12)).await;
+    assert_eq!(reraised.created, 1);
+    let listed = shift_alerts().await;
+    assert_eq!(listed.len(), 2);
+    assert_eq!(listed.iter().filter(|a| a.status == "OPEN").count(), 1);
+}
+
+#[tokio::test]
+async fn test_recovered_alerts_resolve_past_the_hysteresis_band() {
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+
+    sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+
+    let project = projects::create_project(&pool, "Recovery").await.unwrap();
+    // 100 rows with `positives` predicted 1 and the same income spread every time
+    let csv = |positives: usize| {
+        let mut csv = "prediction,income\n".to_string();
+        for i in 0..100 {
+            csv.push_str(&format!(
+                "{},{}\n",
+                u8::from(i < positives),
+                10 * (i % 5 + 1)
+            ));
+        }
+        csv
+    };
+    baseline_builder::build_baseline_from_csv(
+        &pool,
+        &project.project_id,
+        csv(20).as_bytes(),
+        &BinningConfig::default(),
+    )
+    .await
+    .unwrap();
+    // WARN at a 0.10 shift; resolved only once the shift is under 0.05
+    let mut project_settings = settings::get_project_settings(&pool, &project.project_id)
+        .await
+        .unwrap();
+    project_settings.alert_resolve_hysteresis = 0.5;
+    settings::upsert_project_settings(&pool, &project_settings)
+        .await
+        .unwrap();
+    let shift_alert = || {
+        let pool = pool.clone();
+        let project_id = project.project_id.clone();
+        async move {
+            let filter = AlertFilter {
+                alert_type: Some("PREDICTION_SHIFT".to_string()),
+                ..AlertFilter::default()
+            };
+            let mut listed = alerts::list_alerts(&pool, &project_id, &filter)
+                .await
+                .unwrap();
+            assert_eq!(listed.len(), 1);
+            listed.remove(0)
+        }
+    };
+
+    // 0.20 -> 0.35 raises the alert
+    let drifted = upload_incoming(&pool, &project.project_id, &csv(35)).await;
+    assert_eq!(drifted.created, 1);
+    assert_eq!(shift_alert().await.status, "OPEN");
+
+    // A 0.07 shift is under the threshold but inside the band, so the alert stays open
+    let partial = upload_incoming(&pool, &project.project_id, &csv(27)).await;
+    assert_eq!(partial.created, 0);
+    assert_eq!(partial.resolved, 0);
+    assert_eq!(shift_alert().await.status, "OPEN");
+
+    // Back to the baseline rate resolves it automatically
+    let recovered = upload_incoming(&pool, &project.project_id, &csv(20)).await;
+    assert_eq!(recovered.created, 0);
+    assert_eq!(recovered.resolved, 1);
+    assert_eq!(recovered.health, "OK");
+    let alert = shift_alert().await;
+    assert_eq!(alert.status, "RESOLVED");
+    assert_eq!(alert.resolved_by.as_deref(), Some("auto"));
+    assert!(alert
+        .resolution_note
+        .as_deref()
+        .unwrap()
+        .starts_with("Recovered to 0.0000"));
+}
+
+#[tokio::test]
+async fn test_upload_alerts_are_grouped_into_incidents() {
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+
+    sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+
+    let project = projects::create_project(&pool, "Incidents").await.unwrap();
+    // 20 rows with a fixed prediction rate, followed by the feature cells from `row`
+    let csv = |header: &str, row: &dyn Fn(usize) -> String| {
+        let mut csv = format!("{}\n", header);
+        for i in 0..20 {
+            csv.push_str(&format!("{},{}\n", u8::from(i < 4), row(i)));
+        }
+        csv
+    };
+    let age = |i: usize| 20 + i % 5 * 10;
+    baseline_builder::build_baseline_from_csv(
+        &pool,
+        &project.project_id,
+        csv("prediction,income,age", &|i| {
+            format!("{},{}", 10 * (i % 5 + 1), age(i))
+        })
+        .as_bytes(),
+        &BinningConfig::default(),
+    )
+    .await
+    .unwrap();
+    let mut project_settings = settings::get_project_settings(&pool, &project.project_id)
+        .await
+        .unwrap();
+    project_settings.incident_window_minutes = 30;
+    settings::upsert_project_settings(&pool, &project_settings)
+        .await
+        .unwrap();
+
+    let incident_of = |alert_id: String| {
+        let pool = pool.clone();
+        let project_id = project.project_id.clone();
+        async move {
+            alerts::get_alert(&pool, &project_id, &alert_id)
+                .await
+                .unwrap()
+                .unwrap()
+                .incident_id
+        }
+    };
+
+    // Drifted income raises several alerts, all in one incident
+    let drifted_income = |i: usize| 400 + 10 * i;
+    let first = upload_incoming(
+        &pool,
+        &project.project_id,
+        &csv("prediction,income,age", &|i| {
+            format!("{},{}", drifted_income(i), age(i))
+        }),
+    )
+    .await;
+    assert!(first.created > 1);
+    let first_incident = first.incident_id.clone().unwrap();
+    for notification in &first.notifications {
+        assert_eq!(
+            incident_of(notification.alert_id.clone()).await,
+            Some(first_incident.clone())
+        );
+    }
+
+    // A missing column minutes later is a new alert that joins the open incident
+    let second = upload_incoming(
+        &pool,
+        &project.project_id,
+        &csv("prediction,income", &|i| drifted_income(i).to_string()),
+    )
+    .await;
+    assert_eq!(second.created, 1);
+    assert_eq!(second.incident_id, Some(first_incident.clone()));
+    assert_eq!(
+        incident_of(second.notifications[0].alert_id.clone()).await,
+        Some(first_incident.clone())
+    );
+
+    // Once the incident is older than the window, new alerts open another one
+    sqlx::query("UPDATE incidents SET created_at = ?1 WHERE incident_id = ?2")
+        .bind((chrono::Utc::now() - chrono::Duration::hours(2)).to_rfc3339())
+        .bind(&first_incident)
+        .execute(&pool)
+        .await
+        .unwrap();
+    let third = upload_incoming(
+        &pool,
+        &project.project_id,
+        &csv("prediction,income,age", &|i| {
+            let age = if i % 2 == 0 {
+                String::new()
+            } else {
+                age(i).to_string()
+            };
+            format!("{},{}", drifted_income(i), age)
+        }),
+    )
+    .await;
+    assert_eq!(third.created, 1);
+    let third_incident = third.incident_id.clone().unwrap();
+    assert_ne!(third_incident, first_incident);
+
+    // Repeats of the earlier alerts stay in their original incident
+    let incidents = incidents::list_incidents(&pool, &project.project_id, None)
+        .await
+        .unwrap();
+    assert_eq!(incidents.len(), 2);
+    let alert_counts: Vec<(String, i64)> = incidents
+        .iter()
+        .map(|i| (i.incident_id.clone(), i.alert_count))
+        .collect();
+    assert!(alert_counts.contains(&(third_incident, 1)));
+    assert!(alert_counts.contains(&(first_incident, first.created as i64 + 1)));
+}
+
+#[actix_web::test]
+async fn test_rule_routes_store_and_reset_rules() {
+    use actix_web::{test, web, App};
+
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+
+    sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+
+    let project = projects::create_project(&pool, "Rules").await.unwrap();
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(pool.clone()))
+            .route(
+                "/api/projects/{project_id}/rules",
+                web::get().to(routes::rules::get_rules),
+            )
+            .route(
+                "/api/projects/{project_id}/rules",
+                web::put().to(routes::rules::update_rules),
+            )
+            .route(
+                "/api/projects/{project_id}/rules",
+                web::delete().to(routes::rules::reset_rules),
+            ),
+    )
+    .await;
+    let uri = format!("/api/projects/{}/rules", project.project_id);
+
+    let resp = test::call_service(
+        &app,
+        test::TestRequest::get()
+            .uri("/api/projects/missing/rules")
+            .to_request(),
+    )
+    .await;
+    assert_eq!(resp.status(), 404);
+
+    // Without stored rules the defaults are served
+    let defaults: AlertRulesResponse =
+        test::call_and_read_body_json(&app, test::TestRequest::get().uri(&uri).to_request()).await;
+    assert!(!defaults.custom);
+    assert_eq!(defaults.rules.len(), rule_engine::default_rules().len());
+
+    // Invalid rules are rejected and nothing is stored
+    let resp = test::call_service(
+        &app,
+        test::TestRequest::put()
+            .uri(&uri)
+            .set_json(serde_json::json!([
+                {"name": "Shift", "severity": "INFO",
+                 "condition": {"type": "prediction_shift", "value": 0.1}}
+            ]))
+            .to_request(),
+    )
+    .await;
+    assert_eq!(resp.status(), 400);
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["error"], "Invalid rules");
+    assert!(settings::get_alert_rules(&pool, &project.project_id)
+        .await
+        .unwrap()
+        .is_none());
+
+    let stored: AlertRulesResponse = test::call_and_read_body_json(
+        &app,
+        test::TestRequest::put()
+            .uri(&uri)
+            .set_json(serde_json::json!([
+                {"name": "Shift", "severity": "CRITICAL",
+                 "condition": {"type": "prediction_shift", "value": 0.1}}
+            ]))
+            .to_request(),
+    )
+    .await;
+    assert!(stored.custom);
+    assert_eq!(stored.rules.len(), 1);
+
+    let fetched: AlertRulesResponse =
+        test::call_and_read_body_json(&app, test::TestRequest::get().uri(&uri).to_request()).await;
+    assert!(fetched.custom);
+    assert_eq!(fetched.rules.len(), 1);
+    assert_eq!(fetched.rules[0].name, "Shift");
+    assert_eq!(fetched.rules[0].alert_type, "RULE");
+
+    let reset: AlertRulesResponse =
+        test::call_and_read_body_json(&app, test::TestRequest::delete().uri(&uri).to_request())
+            .await;
+    assert!(!reset.custom);
+    assert!(settings::get_alert_rules(&pool, &project.project_id)
+        .await
+        .unwrap()
+        .is_none());
+}
+
+#[actix_web::test]
+async fn test_validate_rules_dry_runs_without_creating_alerts() {
+    use actix_web::{test, web, App};
+
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+
+    sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+
+    let project = projects::create_project(&pool, "Dry run").await.unwrap();
+    let app = test::init_service(App::new().app_data(web::Data::new(pool.clone())).route(
+        "/api/projects/{project_id}/rules/validate",
+        web::post().to(routes::rules::validate_rules),
+    ))
+    .await;
+    let uri = format!("/api/projects/{}/rules/validate", project.project_id);
+    let shift_rule = serde_json::json!([
+        {"name": "Shift", "severity": "WARN",
+         "condition": {"type": "prediction_shift", "value": 0.1}}
+    ]);
+
+    // Nothing to dry-run against before the first upload
+    let resp = test::call_service(
+        &app,
+        test::TestRequest::post()
+            .uri(&uri)
+            .set_json(serde_json::json!({"rules": shift_rule}))
+            .to_request(),
+    )
+    .await;
+    assert_eq!(resp.status(), 404);
+
+    baseline_builder::build_baseline_from_csv(
+        &pool,
+        &project.project_id,
+        format!(
+            "prediction,income\n{}",
+            "0,10\n0,20\n1,30\n0,40\n0,50\n".repeat(8)
+        )
+        .as_bytes(),
+        &BinningConfig::default(),
+    )
+    .await
+    .unwrap();
+    let generated = upload_incoming(
+        &pool,
+        &project.project_id,
+        &format!("prediction,income\n{}", "1,10\n".repeat(40)),
+    )
+    .await;
+    let evaluation = evaluations::list_evaluations(&pool, &project.project_id)
+        .await
+        .unwrap()
+        .remove(0);
+
+    let dry_run: ValidateRulesResponse = test::call_and_read_body_json(
+        &app,
+        test::TestRequest::post()
+            .uri(&uri)
+            .set_json(serde_json::json!({"rules": shift_rule}))
+            .to_request(),
+    )
+    .await;
+    assert_eq!(dry_run.evaluation_id, evaluation.evaluation_id);
+    assert_eq!(dry_run.alerts.len(), 1);
+    assert_eq!(dry_run.alerts[0].rule_name, "Shift");
+    assert_eq!(dry_run.alerts[0].alert_type, "RULE");
+
+    // Without rules in the request the project's current (default) rules run
+    let dry_run: ValidateRulesResponse = test::call_and_read_body_json(
+        &app,
+        test::TestRequest::post()
+            .uri(&uri)
+            .set_json(serde_json::json!({"evaluation_id": evaluation.evaluation_id}))
+            .to_request(),
+    )
+    .await;
+    assert_eq!(dry_run.alerts.len(), generated.created);
+    assert!(dry_run
+        .alerts
+        .iter()
+        .any(|a| a.alert_type == "FEATURE_DRIFT"));
+
+    let resp = test::call_service(
+        &app,
+        test::TestRequest::post()
+            .uri(&uri)
+            .set_json(serde_json::json!({"rules": shift_rule, "evaluation_id": "missing"}))
+            .to_request(),
+    )
+    .await;
+    assert_eq!(resp.status(), 404);
+
+    let resp = test::call_service(
+        &app,
+        test::TestRequest::post()
+            .uri(&uri)
+            .set_json(serde_json::json!({"rules": [
+                {"name": "", "severity": "WARN",
+                 "condition": {"type": "prediction_shift", "value": 0.1}}
+            ]}))
+            .to_request(),
+    )
+    .await;
+    assert_eq!(resp.status(), 400);
+
+    // Dry runs never store alerts
+    let listed = alerts::list_alerts(&pool, &project.project_id, &AlertFilter::default())
+        .await
+        .unwrap();
+    assert_eq!(listed.len(), generated.created);
+}
+
+#[tokio::test]
+async fn test_stored_rules_apply_to_uploads() {
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+
+    sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+
+    let project = projects::create_project(&pool, "Custom rules")
+        .await
+        .unwrap();
+    baseline_builder::build_baseline_from_csv(
+        &pool,
+        &project.project_id,
+        format!(
+            "prediction,income\n{}",
+            "0,10\n0,20\n1,30\n0,40\n0,50\n".repeat(8)
+        )
+        .as_bytes(),
+        &BinningConfig::default(),
+    )
+    .await
+    .unwrap();
+    let rules: Vec<AlertRule> = serde_json::from_value(serde_json::json!([
+        {"name": "Any shift", "severity": "CRITICAL",
+         "condition": {"type": "prediction_shift", "value": 0.1}}
+    ]))
+    .unwrap();
+    settings::set_alert_rules(&pool, &project.project_id, Some(&rules))
+        .await
+        .unwrap();
+
+    // Income drifts within its baseline range too, but only the stored rule is evaluated
+    let generated = upload_incoming(
+        &pool,
+        &project.project_id,
+        &format!("prediction,income\n{}", "1,10\n".repeat(40)),
+    )
+    .await;
+    assert_eq!(generated.created, 1);
+    assert_eq!(generated.health, "CRITICAL");
+
+    let listed = alerts::list_alerts(&pool, &project.project_id, &AlertFilter::default())
+        .await
+        .unwrap();
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0].alert_type, "RULE");
+    assert_eq!(listed[0].severity, "CRITICAL");
+    assert_eq!(listed[0].rule_name.as_deref(), Some("Any shift"));
+    assert!(listed[0].message.starts_with("Any shift"));
+}
+
+#[actix_web::test]
+async fn test_feature_history_filters_and_downsamples() {
+    use actix_web::{test, web, App};
+    use chrono::SecondsFormat;
+
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+
+    sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+
+    let project = projects::create_project(&pool, "History").await.unwrap();
+    baseline_builder::build_baseline_from_csv(
+        &pool,
+        &project.project_id,
+        "prediction,income,age\n0,10,30\n0,20,40\n1,30,50\n".as_bytes(),
+        &BinningConfig::default(),
+    )
+    .await
+    .unwrap();
+
+    // Six hourly evaluations, the latest an hour ago, with income drifting 0.1 to 0.6
+    let now = chrono::Utc::now();
+    let mut evaluation_ids = Vec::new();
+    for i in 0..6 {
+        let evaluation = evaluations::create_evaluation(
+            &pool,
+            &CreateEvaluationParams {
+                project_id: project.project_id.clone(),
+                baseline_version: 1,
+                row_count: 10,
+                prediction_rate: 0.3,
+                prediction_shift: 0.0,
+                drift_metric: DriftMetric::Psi,
+                health: "OK".to_string(),
+                alerts_created: 0,
+                content_hash: format!("hash{}", i),
+                features: vec![EvaluationFeature {
+                    feature_name: "income".to_string(),
+                    drift_score: 0.1 * (i + 1) as f64,
+                    sample_size: 10,
+                    p_value: None,
+                    null_rate: Some(0.0),
+                    unexpected_fraction: None,
+                }],
+            },
+        )
+        .await
+        .unwrap();
+        sqlx::query("UPDATE evaluations SET created_at = ?1 WHERE evaluation_id = ?2")
+            .bind((now - chrono::Duration::hours(6 - i)).to_rfc3339())
+            .bind(&evaluation.evaluation.evaluation_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        evaluation_ids.push(evaluation.evaluation.evaluation_id);
+    }
+
+    let app = test::init_service(App::new().app_data(web::Data::new(pool.clone())).route(
+        "/api/projects/{project_id}/features/{feature_name}/history",
+        web::get().to(routes::history::get_feature_history),
+    ))
+    .await;
+    let uri = |feature: &str, query: &str| {
+        format!(
+            "/api/projects/{}/features/{}/history?{}",
+            project.project_id, feature, query
+        )
+    };
+    let time = |hours_ago: i64| {
+        (now - chrono::Duration::hours(hours_ago) + chrono::Duration::minutes(1))
+            .to_rfc3339_opts(SecondsFormat::Secs, true)
+    };
+    let get = |uri: String| test::TestRequest::get().uri(&uri).to_request();
+
+    let full: FeatureHistory = test::call_and_read_body_json(&app, get(uri("income", ""))).await;
+    assert_eq!(full.total_evaluations, 6);
+    assert_eq!(full.points.len(), 6);
+    assert_eq!(
+        full.points[0].evaluation_id.as_deref(),
+        Some(evaluation_ids[0].as_str())
+    );
+
+    // Runs of two evaluations are averaged, keeping their peak
+    let downsampled: FeatureHistory =
+        test::call_and_read_body_json(&app, get(uri("income", "max_points=3"))).await;
+    assert_eq!(downsampled.total_evaluations, 6);
+    assert_eq!(downsampled.points.len(), 3);
+    for (point, expected) in downsampled.points.iter().zip([0.15, 0.35, 0.55]) {
+        assert_eq!(point.evaluation_id, None);
+        assert_eq!(point.evaluations, 2);
+        assert_eq!(point.sample_size, 20);
+        assert!((point.drift_score - expected).abs() < 1e-9);
+        assert!((point.max_drift_score - (expected + 0.05)).abs() < 1e-9);
+    }
+
+    // From just after the second evaluation to just after the fourth
+    let filtered: FeatureHistory = test::call_and_read_body_json(
+        &app,
+        get(uri(
+            "income",
+            &format!("start_time={}&end_time={}", time(5), time(3)),
+        )),
+    )
+    .await;
+    assert_eq!(filtered.total_evaluations, 2);
+    let ids: Vec<_> = filtered
+        .points
+        .iter()
+        .map(|p| p.evaluation_id.clone().unwrap())
+        .collect();
+    assert_eq!(ids, evaluation_ids[2..4]);
+    let since: FeatureHistory =
+        test::call_and_read_body_json(&app, get(uri("income", &format!("start_time={}", time(2)))))
+            .await;
+    assert_eq!(since.total_evaluations, 1);
+    assert_eq!(
+        since.points[0].evaluation_id,
+        Some(evaluation_ids[5].clone())
+    );
+
+    // A known feature with nothing in range is empty rather than missing
+    let empty: FeatureHistory =
+        test::call_and_read_body_json(&app, get(uri("age", &format!("end_time={}", time(7)))))
+            .await;
+    assert_eq!(empty.total_evaluations, 0);
+    assert!(empty.points.is_empty());
+
+    let resp = test::call_service(&app, get(uri("income", "max_points=0"))).await;
+    assert_eq!(resp.status(), 400);
+    let resp = test::call_service(&app, get(uri("unknown", ""))).await;
+    assert_eq!(resp.status(), 404);
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["error"], "Feature not found");
+    let resp = test::call_service(
+        &app,
+        get("/api/projects/missing/features/income/history".to_string()),
+    )
+    .await;
+    assert_eq!(resp.status(), 404);
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["error"], "Project not found");
+}
+
+#[tokio::test]
+async fn test_alert_summary() {
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+
+    sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+
+    let project = projects::create_project(&pool, "Summary").await.unwrap();
+    let raise = |severity: &str, alert_type: &str, feature_name: Option<&str>| {
+        let params = CreateAlertParams {
+            project_id: project.project_id.clone(),
+            baseline_version: 1,
+            severity: severity.to_string(),
+            alert_type: alert_type.to_string(),
+            feature_name: feature_name.map(str::to_string),
+            metric_value: Some(0.3),
+            message: format!("{} alert", alert_type),
+            drift_metric: None,
+            rule_name: None,
+        };
+        let pool = pool.clone();
+        async move {
+            alerts::record_alert(&pool, &params, 0)
+                .await
+                .unwrap()
+                .alert_id()
+                .to_string()
+        }
+    };
+    // Backdates an alert, and its ack when given, to spread them over several days
+    let backdate = |alert_id: String, created_at: &str, acked_at: Option<&str>| {
+        let pool = pool.clone();
+        let created_at = created_at.to_string();
+        let acked_at = acked_at.map(str::to_string);
+        async move {
+            sqlx::query(
+                "UPDATE alerts SET created_at = ?2, last_seen_at = ?2, \
+                 acked_at = COALESCE(?3, acked_at) WHERE alert_id = ?1",
+            )
+            .bind(alert_id)
+            .bind(created_at)
+            .bind(acked_at)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+    };
+
+    let income = raise("CRITICAL", "FEATURE_DRIFT", Some("income")).await;
+    backdate(income.clone(), "2024-03-01T10:00:00.123456+00:00", None).await;
+    // A repeat folds into the alert and counts as another occurrence
+    raise("CRITICAL", "FEATURE_DRIFT", Some("income")).await;
+
+    let age = raise("WARN", "FEATURE_DRIFT", Some("age")).await;
+    alerts::ack_alert(&pool, &project.project_id, &age, "oncall")
+        .await
+        .unwrap();
+    backdate(
+        age,
+        "2024-03-01T12:00:00+00:00",
+        Some("2024-03-01T12:10:00+00:00"),
+    )
+    .await;
+
+    let zip = raise("WARN", "DATA_QUALITY", Some("zip")).await;
+    alerts::ack_alert(&pool, &project.project_id, &zip, "oncall")
+        .await
+        .unwrap();
+    backdate(
+        zip,
+        "2024-03-04T08:00:00+00:00",
+        Some("2024-03-04T08:30:00+00:00"),
+    )
+    .await;
+
+    let schema = raise("CRITICAL", "SCHEMA", None).await;
+    backdate(schema, "2024-03-04T09:00:00+00:00", None).await;
+
+    let summary = alerts::alert_summary(&pool, &project.project_id, &AlertSummaryParams::default())
+        .await
+        .unwrap();
+    assert_eq!(summary.open_by_severity.get("CRITICAL"), Some(&2));
+    assert_eq!(summary.open_by_severity.get("WARN"), None);
+    assert_eq!(summary.open_by_type.get("FEATURE_DRIFT"), Some(&1));
+    assert_eq!(summary.open_by_type.get("SCHEMA"), Some(&1));
+
+    let features: Vec<(&str, i64, i64)> = summary
+        .top_features
+        .iter()
+        .map(|f| (f.feature_name.as_str(), f.alert_count, f.occurrences))
+        .collect();
+    // Only drift alerts count towards the top features, not zip's data quality alert
+    assert_eq!(features, vec![("income", 1, 2), ("age", 1, 1)]);
+
+    // Acked after 10 and 30 minutes
+    assert_eq!(summary.acked_alerts, 2);
+    let mean = summary.mean_time_to_ack_seconds.unwrap();
+    assert!((mean - 1200.0).abs() < 0.01, "mean time to ack {}", mean);
+
+    // Days without alerts in between are reported as zero
+    let days: Vec<(String, i64, i64, i64)> = summary
+        .alerts_per_day
+        .iter()
+        .map(|d| (d.date.to_string(), d.alerts, d.critical, d.warn))
+        .collect();
+    assert_eq!(
+        days,
+        vec![
+            ("2024-03-01".to_string(), 2, 1, 1),
+            ("2024-03-02".to_string(), 0, 0, 0),
+            ("2024-03-03".to_string(), 0, 0, 0),
+            ("2024-03-04".to_string(), 2, 1, 1),
+        ]
+    );
+
+    // The time bounds narrow every aggregate
+    let params = AlertSummaryParams {
+        start_time: Some("2024-03-01T11:00:00+00:00".to_string()),
+        end_time: None,
+    };
+    let summary = alerts::alert_summary(&pool, &project.project_id, &params)
+        .await
+        .unwrap();
+    assert_eq!(summary.open_by_type.len(), 1);
+    assert_eq!(summary.top_features.len(), 1);
+    assert_eq!(summary.top_features[0].feature_name, "age");
+    assert_eq!(summary.acked_alerts, 2);
+    assert_eq!(summary.alerts_per_day.len(), 4);
+}
+
+#[tokio::test]
+async fn test_alert_pagination_and_sorting() {
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+
+    sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+
+    let project = projects::create_project(&pool, "Paging").await.unwrap();
+    let raises = [
+        ("WARN", "FEATURE_DRIFT", Some("income"), Some(0.12)),
+        ("CRITICAL", "FEATURE_DRIFT", Some("age"), Some(0.35)),
+        ("WARN", "PREDICTION_SHIFT", None, Some(0.12)),
+        ("CRITICAL", "SCHEMA", Some("zip"), None),
+        ("WARN", "DATA_QUALITY", Some("zip"), Some(0.08)),
+        (
+            "CRITICAL",
+            "CONFIDENCE_DRIFT",
+            Some("confidence"),
+            Some(0.25),
+        ),
+        ("WARN", "SCHEMA", Some("extra"), None),
+    ];
+    for (severity, alert_type, feature_name, metric_value) in raises {
+        let params = CreateAlertParams {
+            project_id: project.project_id.clone(),
+            baseline_version: 1,
+            severity: severity.to_string(),
+            alert_type: alert_type.to_string(),
+            feature_name: feature_name.map(str::to_string),
+            metric_value,
+            message: format!("{} alert", alert_type),
+            drift_metric: None,
+            rule_name: None,
+        };
+        alerts::record_alert(&pool, &params, 0).await.unwrap();
+    }
+
+    // Walks every page of three and checks the order against the full listing
+    let walk = |filter: AlertFilter, sort: AlertSort, order: SortOrder| {
+        let pool = pool.clone();
+        let project_id = project.project_id.clone();
+        async move {
+            let mut seen = Vec::new();
+            let mut after = None;
+            loop {
+                let page = alerts::list_alerts_page(
+                    &pool,
+                    &project_id,
+                    &filter,
+                    sort,
+                    order,
+                    3,
+                    after.as_ref(),
+                )
+                .await
+                .unwrap();
+                assert!(page.alerts.len() <= 3);
+                seen.extend(page.alerts);
+                match page.next_cursor {
+                    Some(cursor) => {
+                        after = Some(AlertCursor::decode(&cursor, sort, order).unwrap())
+                    }
+                    None => {
+                        assert_eq!(seen.len() as i64, page.total);
+                        return seen;
+                    }
+                }
+            }
+        }
+    };
+
+    let newest_first = walk(
+        AlertFilter::default(),
+        AlertSort::CreatedAt,
+        SortOrder::Desc,
+    )
+    .await;
+    assert_eq!(newest_first.len(), 7);
+    assert!(newest_first
+        .windows(2)
+        .all(|w| w[0].created_at >= w[1].created_at));
+    let listed = alerts::list_alerts(&pool, &project.project_id, &AlertFilter::default())
+        .await
+        .unwrap();
+    let ids = |alerts: &[drift_guard_backend::models::Alert]| {
+        alerts
+            .iter()
+            .map(|a| a.alert_id.clone())
+            .collect::<Vec<_>>()
+    };
+    let mut all_ids = ids(&listed);
+    all_ids.sort();
+
+    for order in [SortOrder::Asc, SortOrder::Desc] {
+        let by_severity = walk(AlertFilter::default(), AlertSort::Severity, order).await;
+        let mut seen_ids = ids(&by_severity);
+        seen_ids.sort();
+        assert_eq!(seen_ids, all_ids);
+        let severities: Vec<&str> = by_severity.iter().map(|a| a.severity.as_str()).collect();
+        let expected = match order {
+            SortOrder::Asc => [
+                "WARN", "WARN", "WARN", "WARN", "CRITICAL", "CRITICAL", "CRITICAL",
+            ],
+            SortOrder::Desc => [
+                "CRITICAL", "CRITICAL", "CRITICAL", "WARN", "WARN", "WARN", "WARN",
+            ],
+        };
+        assert_eq!(severities, expected);
+
+        // Equal metric values are split across pages by alert id; no value sorts lowest
+        let by_metric = walk(AlertFilter::default(), AlertSort::MetricValue, order).await;
+        let mut seen_ids = ids(&by_metric);
+        seen_ids.sort();
+        assert_eq!(seen_ids, all_ids);
+        let mut metrics: Vec<Option<f64>> = by_metric.iter().map(|a| a.metric_value).collect();
+        if order == SortOrder::Desc {
+            metrics.reverse();
+        }
+        assert_eq!(
+            metrics,
+            vec![
+                None,
+                None,
+                Some(0.08),
+                Some(0.12),
+                Some(0.12),
+                Some(0.25),
+                Some(0.35)
+            ]
+        );
+    }
+
+    // Comma-separated values match any of them
+    let filter = AlertFilter {
+        alert_type: Some("SCHEMA, CONFIDENCE_DRIFT".to_string()),
+        severity: Some("CRITICAL".to_string()),
+        ..AlertFilter::default()
+    };
+    let matched = walk(filter, AlertSort::CreatedAt, SortOrder::Desc).await;
+    assert_eq!(matched.len(), 2);
+    assert!(matched.iter().all(|a| a.severity == "CRITICAL"));
+
+    // Alerts raised while paging don't shift the later pages of a newest-first listing
+    let first = alerts::list_alerts_page(
+        &pool,
+        &project.project_id,
+        &AlertFilter::default(),
+        AlertSort::CreatedAt,
+        SortOrder::Desc,
+        3,
+        None,
+    )
+    .await
+    .unwrap();
+    let params = CreateAlertParams {
+        project_id: project.project_id.clone(),
+        baseline_version: 1,
+        severity: "WARN".to_string(),
+        alert_type: "NEW_CATEGORY".to_string(),
+        feature_name: Some("state".to_string()),
+        metric_value: Some(0.02),
+        message: "NEW_CATEGORY alert".to_string(),
+        drift_metric: None,
+        rule_name: None,
+    };
+    alerts::record_alert(&pool, &params, 0).await.unwrap();
+    let cursor = first.next_cursor.unwrap();
+    let after = AlertCursor::decode(&cursor, AlertSort::CreatedAt, SortOrder::Desc).unwrap();
+    let second = alerts::list_alerts_page(
+        &pool,
+        &project.project_id,
+        &AlertFilter::default(),
+        AlertSort::CreatedAt,
+        SortOrder::Desc,
+        3,
+        Some(&after),
+    )
+    .await
+    .unwrap();
+    assert_eq!(second.total, 8);
+    assert_eq!(ids(&second.alerts), ids(&newest_first[3..6]));
+
+    // A cursor only works with the sort and order it was issued for
+    assert!(AlertCursor::decode(&cursor, AlertSort::Severity, SortOrder::Desc).is_none());
+    assert!(AlertCursor::decode(&cursor, AlertSort::CreatedAt, SortOrder::Asc).is_none());
+    assert!(AlertCursor::decode("not-a-cursor", AlertSort::CreatedAt, SortOrder::Desc).is_none());
+}
@@ -15,7 +15,7 @@ import { MatTableDataSource, MatTableModule } from '@angular/material/table';
 import { MatTooltipModule } from '@angular/material/tooltip';
 import { ActivatedRoute, Router, RouterModule } from '@angular/router';
 
-import { Alert, AlertsService } from '../services/alerts.service';
+import { Alert, AlertsService, MAX_ALERT_PAGE_SIZE } from '../services/alerts.service';
 import { NotificationService } from '../services/notification.service';
 import { AlertDetailsDialog } from './alert-details-dialog.component';
 
@@ -82,20 +82,31 @@ export class AlertsComponent implements OnInit, AfterViewInit {
 	ngOnInit() {
 		this.projectId = this.route.snapshot.paramMap.get("id") || "";
 		// Load all alerts first to populate filter dropdowns
-		this.alertsService.listAlerts(this.projectId).subscribe({
-			next: (allAlerts) => {
-				this.alertTypes = [
-					...new Set(
-						allAlerts.map((a) => a.alert_type).filter((t) => t),
-					),
-				].sort();
-				this.featureNames = [
-					...new Set(
-						allAlerts.map((a) => a.feature_name).filter((f) => f),
-					),
-				].sort();
-			},
-		});
+		this.alertsService
+			.listAlerts(
+				this.projectId,
+				undefined,
+				undefined,
+				undefined,
+				undefined,
+				undefined,
+				undefined,
+				{ limit: MAX_ALERT_PAGE_SIZE },
+			)
+			.subscribe({
+				next: ({ alerts: allAlerts }) => {
+					this.alertTypes = [
+						...new Set(
+							allAlerts.map((a) => a.alert_type).filter((t) => t),
+						),
+					].sort();
+					this.featureNames = [
+						...new Set(
+							allAlerts.map((a) => a.feature_name).filter((f) => f),
+						),
+					].sort();
+				},
+			});
 		this.loadAlerts();
 	}
 
@@ -126,9 +137,10 @@ export class AlertsComponent implements OnInit, AfterViewInit {
 				alertType,
 				startTime,
 				endTime,
+				{ limit: MAX_ALERT_PAGE_SIZE },
 			)
 			.subscribe({
-				next: (alerts) => {
+				next: ({ alerts }) => {
 					this.alerts = alerts;
 					this.dataSource.data = alerts;
 					if (this.paginator) {
@@ -27,7 +27,7 @@ describe('AppComponent', () => {
     jest.useFakeTimers();
     
     const alertsServiceMock = {
-      listAlerts: jest.fn().mockReturnValue(of([]))
+      listAlerts: jest.fn().mockReturnValue(of({ alerts: [], total: 0, next_cursor: null }))
     };
 
     const projectsServiceMock = {
@@ -98,7 +98,7 @@ describe('AppComponent', () => {
     ];
 
     projectsService.listProjects.mockReturnValue(of(mockProjects));
-    alertsService.listAlerts.mockReturnValue(of(mockAlerts));
+    alertsService.listAlerts.mockReturnValue(of({ alerts: mockAlerts, total: 1, next_cursor: null }));
 
     // Reset call counts
     projectsService.listProjects.mockClear();
@@ -14,7 +14,7 @@ import { AlertsService } from './services/alerts.service';
 import { ProjectsService } from './services/projects.service';
 import { NotificationsPanelComponent } from './notifications/notifications-panel.component';
 import { forkJoin, of } from 'rxjs';
-import { catchError } from 'rxjs/operators';
+import { catchError, map } from 'rxjs/operators';
 
 @Component({
   selector: 'app-root',
@@ -61,16 +61,19 @@ export class AppComponent implements OnInit {
           return;
         }
         
-        const alertObservables = projects.map(project => 
-          this.alertsService.listAlerts(project.project_id, 'OPEN').pipe(
-            catchError(() => of([]))
-          )
+        // Only the totals are needed, so fetch the smallest page
+        const countObservables = projects.map(project => 
+          this.alertsService
+            .listAlerts(project.project_id, 'OPEN', undefined, undefined, undefined, undefined, undefined, { limit: 1 })
+            .pipe(
+              map(page => page.total),
+              catchError(() => of(0))
+            )
         );
         
-        forkJoin(alertObservables).subscribe({
-          next: (results) => {
-            const allAlerts = results.flat().filter(alert => alert !== undefined);
-            this.openAlertsCount = allAlerts.length;
+        forkJoin(countObservables).subscribe({
+          next: (totals) => {
+            this.openAlertsCount = totals.reduce((sum, total) => sum + total, 0);
           },
           error: () => {
             this.openAlertsCount = 0;
@@ -54,6 +54,7 @@ export class NotificationsPanelComponent implements OnInit {
         // Then get alerts for each project using forkJoin
         const alertObservables = projects.map(project => 
           this.alertsService.listAlerts(project.project_id, 'OPEN').pipe(
+            map(page => page.alerts),
             catchError(() => of([] as Alert[]))
           )
         );
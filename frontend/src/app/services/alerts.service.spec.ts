@@ -1,6 +1,6 @@
 import { TestBed } from '@angular/core/testing';
 import { HttpClientTestingModule, HttpTestingController } from '@angular/common/http/testing';
-import { AlertsService, Alert } from './alerts.service';
+import { AlertsService, Alert, AlertPage } from './alerts.service';
 
 describe('AlertsService', () => {
   let service: AlertsService;
@@ -39,35 +39,37 @@ describe('AlertsService', () => {
       }
     ];
 
-    service.listAlerts('123').subscribe(alerts => {
-      expect(alerts.length).toBe(1);
-      expect(alerts).toEqual(mockAlerts);
+    const mockPage: AlertPage = { alerts: mockAlerts, total: 1, next_cursor: null };
+
+    service.listAlerts('123').subscribe(page => {
+      expect(page.alerts.length).toBe(1);
+      expect(page).toEqual(mockPage);
     });
 
     const req = httpMock.expectOne('http://127.0.0.1:8080/api/projects/123/alerts');
     expect(req.request.method).toBe('GET');
-    req.flush(mockAlerts);
+    req.flush(mockPage);
   });
 
   it('should list alerts with status and severity filters', () => {
-    const mockAlerts: Alert[] = [];
+    const mockPage: AlertPage = { alerts: [], total: 0, next_cursor: null };
 
-    service.listAlerts('123', 'OPEN', 'WARN').subscribe(alerts => {
-      expect(alerts).toEqual(mockAlerts);
+    service.listAlerts('123', 'OPEN', 'WARN').subscribe(page => {
+      expect(page).toEqual(mockPage);
     });
 
     const req = httpMock.expectOne('http://127.0.0.1:8080/api/projects/123/alerts?status=OPEN&severity=WARN');
     expect(req.request.method).toBe('GET');
-    req.flush(mockAlerts);
+    req.flush(mockPage);
   });
 
   it('should list alerts with all filters', () => {
-    const mockAlerts: Alert[] = [];
+    const mockPage: AlertPage = { alerts: [], total: 0, next_cursor: null };
     const startTime = '2024-01-01T00:00:00Z';
     const endTime = '2024-01-31T23:59:59Z';
 
-    service.listAlerts('123', 'OPEN', 'WARN', 'income', 'FEATURE_DRIFT', startTime, endTime).subscribe(alerts => {
-      expect(alerts).toEqual(mockAlerts);
+    service.listAlerts('123', 'OPEN', 'WARN', 'income', 'FEATURE_DRIFT', startTime, endTime).subscribe(page => {
+      expect(page).toEqual(mockPage);
     });
 
     const req = httpMock.expectOne(
@@ -80,31 +82,57 @@ describe('AlertsService', () => {
         request.params.get('end_time') === endTime
     );
     expect(req.request.method).toBe('GET');
-    req.flush(mockAlerts);
+    req.flush(mockPage);
   });
 
   it('should list alerts with feature name filter', () => {
-    const mockAlerts: Alert[] = [];
+    const mockPage: AlertPage = { alerts: [], total: 0, next_cursor: null };
 
-    service.listAlerts('123', undefined, undefined, 'income').subscribe(alerts => {
-      expect(alerts).toEqual(mockAlerts);
+    service.listAlerts('123', undefined, undefined, 'income').subscribe(page => {
+      expect(page).toEqual(mockPage);
     });
 
     const req = httpMock.expectOne('http://127.0.0.1:8080/api/projects/123/alerts?feature_name=income');
     expect(req.request.method).toBe('GET');
-    req.flush(mockAlerts);
+    req.flush(mockPage);
   });
 
   it('should list alerts with alert type filter', () => {
-    const mockAlerts: Alert[] = [];
+    const mockPage: AlertPage = { alerts: [], total: 0, next_cursor: null };
 
-    service.listAlerts('123', undefined, undefined, undefined, 'FEATURE_DRIFT').subscribe(alerts => {
-      expect(alerts).toEqual(mockAlerts);
+    service.listAlerts('123', undefined, undefined, undefined, 'FEATURE_DRIFT').subscribe(page => {
+      expect(page).toEqual(mockPage);
     });
 
     const req = httpMock.expectOne('http://127.0.0.1:8080/api/projects/123/alerts?alert_type=FEATURE_DRIFT');
     expect(req.request.method).toBe('GET');
-    req.flush(mockAlerts);
+    req.flush(mockPage);
+  });
+
+  it('should request a page with sorting and a cursor', () => {
+    const mockPage: AlertPage = { alerts: [], total: 120, next_cursor: 'abc123' };
+
+    service
+      .listAlerts('123', undefined, 'WARN,CRITICAL', undefined, undefined, undefined, undefined, {
+        limit: 25,
+        cursor: 'abc123',
+        sort: 'severity',
+        order: 'asc'
+      })
+      .subscribe(page => {
+        expect(page).toEqual(mockPage);
+      });
+
+    const req = httpMock.expectOne(
+      (request) => request.url === 'http://127.0.0.1:8080/api/projects/123/alerts' &&
+        request.params.get('severity') === 'WARN,CRITICAL' &&
+        request.params.get('limit') === '25' &&
+        request.params.get('cursor') === 'abc123' &&
+        request.params.get('sort') === 'severity' &&
+        request.params.get('order') === 'asc'
+    );
+    expect(req.request.method).toBe('GET');
+    req.flush(mockPage);
   });
 
   it('should acknowledge alert', () => {
@@ -15,6 +15,22 @@ export interface Alert {
   status: string;
 }
 
+export interface AlertPage {
+  alerts: Alert[];
+  total: number;
+  next_cursor: string | null;
+}
+
+// Largest page the backend returns
+export const MAX_ALERT_PAGE_SIZE = 500;
+
+export interface AlertPageOptions {
+  limit?: number;
+  cursor?: string;
+  sort?: 'created_at' | 'severity' | 'metric_value';
+  order?: 'asc' | 'desc';
+}
+
 @Injectable({
   providedIn: 'root'
 })
@@ -30,8 +46,9 @@ export class AlertsService {
     featureName?: string,
     alertType?: string,
     startTime?: string,
-    endTime?: string
-  ): Observable<Alert[]> {
+    endTime?: string,
+    page: AlertPageOptions = {}
+  ): Observable<AlertPage> {
     let params = new HttpParams();
     if (status) {
       params = params.set('status', status);
@@ -51,7 +68,19 @@ export class AlertsService {
     if (endTime) {
       params = params.set('end_time', endTime);
     }
-    return this.http.get<Alert[]>(
+    if (page.limit) {
+      params = params.set('limit', page.limit);
+    }
+    if (page.cursor) {
+      params = params.set('cursor', page.cursor);
+    }
+    if (page.sort) {
+      params = params.set('sort', page.sort);
+    }
+    if (page.order) {
+      params = params.set('order', page.order);
+    }
+    return this.http.get<AlertPage>(
       `${this.apiUrl}/projects/${projectId}/alerts`,
       { params }
     );